@@ -8,6 +8,25 @@ pub enum OrderStatusArg {
     Created,
     Canceled,
     Completed,
+    Declined,
+    DeliveryClaimed,
+    Disputed,
+}
+
+#[derive(Debug, ArgEnum, Clone)]
+pub enum PriceCurveArg {
+    Fixed,
+    Linear,
+    Quadratic,
+}
+
+/// Parse base58 encoded 32 bytes (hashlock or secret).
+fn parse_bytes32(value: &str) -> Result<[u8; 32], String> {
+    let bytes = bs58::decode(value)
+        .into_vec()
+        .map_err(|error| error.to_string())?;
+
+    <[u8; 32]>::try_from(bytes).map_err(|_| String::from("expected 32 bytes"))
 }
 
 #[derive(Subcommand, Debug)]
@@ -39,6 +58,91 @@ pub enum Commands {
 
         #[clap(long, help = "order expiration date")]
         expire_date: Option<i64>,
+
+        #[clap(long, arg_enum, help = "recipient tokens price curve")]
+        price_curve: Option<PriceCurveArg>,
+
+        #[clap(long, help = "lowest recipient tokens amount (dutch auction)")]
+        floor_quote_amount: Option<f64>,
+
+        #[clap(long, help = "referrer wallet address")]
+        referrer: Option<Pubkey>,
+
+        #[clap(long, help = "share of the protocol fee paid to referrer (bps)")]
+        referral_share_bps: Option<u16>,
+
+        #[clap(long, help = "accept any NFT of verified collection (quote mint)")]
+        collection_offer: bool,
+
+        #[clap(
+            long,
+            help = "date until which order can't be canceled (HTLC timelock)"
+        )]
+        cancel_locked_until: Option<i64>,
+
+        #[clap(long, parse(try_from_str = parse_bytes32), help = "secret hash, base58 (HTLC)")]
+        hashlock: Option<[u8; 32]>,
+
+        #[clap(long, help = "arbiter address (deal with off-chain leg)")]
+        arbiter: Option<Pubkey>,
+
+        #[clap(long, help = "seconds to dispute delivery and resolve dispute")]
+        dispute_window: Option<i64>,
+
+        #[clap(long, help = "order memo")]
+        memo: Option<String>,
+
+        #[clap(long, parse(try_from_str = parse_bytes32), help = "client reference, base58")]
+        reference: Option<[u8; 32]>,
+
+        #[clap(long, help = "store only recipient commitment (hidden order)")]
+        hidden: bool,
+
+        #[clap(long, help = "rent payer (sponsor) keypair path")]
+        rent_payer: Option<String>,
+
+        #[clap(long, help = "mint, that executing wallet should hold (token gate)")]
+        gate_mint: Option<Pubkey>,
+
+        #[clap(long, help = "minimum balance of gate mint tokens (token gate)")]
+        gate_min_amount: Option<f64>,
+
+        #[clap(
+            long,
+            help = "collection, NFT of which executing wallet should hold (token gate)"
+        )]
+        gate_collection: Option<Pubkey>,
+    },
+    BatchCreateOrders {
+        #[clap(
+            short,
+            long,
+            help = "batch file, line format: <recipient> <base_amount> <quote_amount> [expire_date]"
+        )]
+        file: String,
+
+        #[clap(short, long, help = "funder token account address")]
+        token_account: Option<Pubkey>,
+
+        #[clap(short, long, help = "funder token account address for receiving")]
+        quote_token_account: Option<Pubkey>,
+
+        #[clap(short, long, help = "mint for funder tokens")]
+        base_mint: Option<Pubkey>,
+
+        #[clap(long, help = "mint for recipient tokens")]
+        quote_mint: Option<Pubkey>,
+    },
+    BatchCancelOrders {
+        #[clap(short, long, help = "batch file, line format: <order>")]
+        file: String,
+
+        #[clap(
+            short,
+            long,
+            help = "funder token account address for escrow tokens receiving"
+        )]
+        token_account: Option<Pubkey>,
     },
     CancelOrder {
         #[clap(short, long, help = "order address")]
@@ -51,6 +155,14 @@ pub enum Commands {
         )]
         token_account: Option<Pubkey>,
     },
+    DeclineOrder {
+        #[clap(short, long, help = "order address")]
+        order: Pubkey,
+    },
+    CloseOrder {
+        #[clap(short, long, help = "order address")]
+        order: Pubkey,
+    },
     ExecuteOrder {
         #[clap(short, long, help = "order address")]
         order: Pubkey,
@@ -60,7 +172,63 @@ pub enum Commands {
 
         #[clap(short, long, help = "signer token account address for receive")]
         receive_token_account: Option<Pubkey>,
+
+        #[clap(long, help = "NFT mint (collection offer)")]
+        nft_mint: Option<Pubkey>,
+
+        #[clap(
+            long,
+            help = "funder token account address for NFT receiving (collection offer)"
+        )]
+        funder_nft_token_account: Option<Pubkey>,
+
+        #[clap(
+            long,
+            help = "treasury token account address for base tokens fee (if fee is charged)"
+        )]
+        treasury_base_token_account: Option<Pubkey>,
+
+        #[clap(
+            long,
+            help = "treasury token account address for quote tokens fee (if fee is charged)"
+        )]
+        treasury_quote_token_account: Option<Pubkey>,
+
+        #[clap(long, help = "referrer wallet address")]
+        referrer: Option<Pubkey>,
+
+        #[clap(long, help = "share of the protocol fee paid to referrer (bps)")]
+        referral_share_bps: Option<u16>,
+
+        #[clap(long, help = "signer token account of p2swap token (fee discount)")]
+        discount_token_account: Option<Pubkey>,
+
+        #[clap(long, parse(try_from_str = parse_bytes32), help = "recipient salt, base58 (hidden order)")]
+        recipient_salt: Option<[u8; 32]>,
+
+        #[clap(
+            long,
+            help = "signer token account of gate mint or collection NFT (token gate)"
+        )]
+        gate_token_account: Option<Pubkey>,
     },
+    ClaimOrder {
+        #[clap(short, long, help = "order address")]
+        order: Pubkey,
+
+        #[clap(short, long, parse(try_from_str = parse_bytes32), help = "secret, base58")]
+        secret: [u8; 32],
+
+        #[clap(short, long, help = "signer token account address for receive")]
+        receive_token_account: Option<Pubkey>,
+
+        #[clap(
+            long,
+            help = "treasury token account address for base tokens fee (if fee is charged)"
+        )]
+        treasury_base_token_account: Option<Pubkey>,
+    },
+    GenerateSecret,
     GetOrder {
         #[clap(short, long, help = "order address")]
         order: Pubkey,
@@ -71,6 +239,70 @@ pub enum Commands {
 
         #[clap(short, long, arg_enum, help = "order status")]
         status: Option<OrderStatusArg>,
+
+        #[clap(long, parse(try_from_str = parse_bytes32), help = "client reference, base58")]
+        reference: Option<[u8; 32]>,
+    },
+    GetOrderBook {
+        #[clap(short, long, help = "mint for funder tokens")]
+        base_mint: Option<Pubkey>,
+
+        #[clap(short, long, help = "mint for recipient tokens")]
+        quote_mint: Option<Pubkey>,
+    },
+    PruneOrderBook {
+        #[clap(short, long, help = "mint for funder tokens")]
+        base_mint: Option<Pubkey>,
+
+        #[clap(short, long, help = "mint for recipient tokens")]
+        quote_mint: Option<Pubkey>,
+    },
+    GetRfqQuotes {
+        #[clap(short, long, help = "rfq address")]
+        rfq: Pubkey,
+    },
+    Stats {
+        #[clap(short, long, help = "mint for funder tokens of pair")]
+        base_mint: Option<Pubkey>,
+
+        #[clap(short, long, help = "mint for recipient tokens of pair")]
+        quote_mint: Option<Pubkey>,
+
+        #[clap(long, help = "TWAP window of pair, seconds")]
+        twap_window: Option<i64>,
+    },
+    GetProfile {
+        #[clap(short, long, help = "wallet address, default is current wallet")]
+        wallet: Option<Pubkey>,
+    },
+    Stake {
+        #[clap(short, long, help = "p2swap token account address")]
+        token_account: Pubkey,
+
+        #[clap(short, long, help = "amount of p2swap tokens to stake")]
+        amount: f64,
+    },
+    Unstake {
+        #[clap(short, long, help = "p2swap token account address for receiving")]
+        token_account: Pubkey,
+
+        #[clap(short, long, help = "amount of p2swap tokens to unstake")]
+        amount: f64,
+    },
+    WithdrawStake {
+        #[clap(short, long, help = "p2swap token account address for receiving")]
+        token_account: Pubkey,
+    },
+    ClaimRewards {
+        #[clap(short, long, help = "mint of rewards, default is native SOL")]
+        mint: Option<Pubkey>,
+
+        #[clap(short, long, help = "token account address for rewards receiving")]
+        token_account: Option<Pubkey>,
+    },
+    GetStake {
+        #[clap(short, long, help = "wallet address, default is current wallet")]
+        wallet: Option<Pubkey>,
     },
 }
 
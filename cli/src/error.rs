@@ -15,6 +15,9 @@ pub enum Error {
 
     #[error("I/O error.")]
     IoError(io::Error),
+
+    #[error("Invalid batch file line {0}.")]
+    InvalidBatchLine(usize),
 }
 
 impl From<io::Error> for Error {
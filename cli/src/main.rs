@@ -8,7 +8,9 @@ use chrono::Utc;
 use clap::Parser;
 use indicatif;
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::{signature::read_keypair_file, signer::Signer};
+use solana_sdk::{
+    program_error::ProgramError, pubkey::Pubkey, signature::read_keypair_file, signer::Signer,
+};
 use std::{env, time::Duration};
 
 fn main() -> Result<(), error::Error> {
@@ -48,6 +50,22 @@ fn main() -> Result<(), error::Error> {
             quote_amount,
             start_date,
             expire_date,
+            price_curve,
+            floor_quote_amount,
+            referrer,
+            referral_share_bps,
+            collection_offer,
+            cancel_locked_until,
+            hashlock,
+            arbiter,
+            dispute_window,
+            memo,
+            reference,
+            hidden,
+            rent_payer,
+            gate_mint,
+            gate_min_amount,
+            gate_collection,
         } => {
             let base_decimals = if base_mint.is_none() {
                 9
@@ -63,6 +81,48 @@ fn main() -> Result<(), error::Error> {
                 mint.decimals
             };
 
+            let price_curve = match price_curve.unwrap_or(args::PriceCurveArg::Fixed) {
+                args::PriceCurveArg::Fixed => p2swap::state::PriceCurve::Fixed,
+                args::PriceCurveArg::Linear => p2swap::state::PriceCurve::Linear,
+                args::PriceCurveArg::Quadratic => p2swap::state::PriceCurve::Quadratic,
+            };
+
+            let arbitration = arbiter.map(|arbiter| p2swap::state::ArbitrationArgs {
+                arbiter,
+                dispute_window: dispute_window.unwrap_or(86400),
+            });
+
+            let base_mint = base_mint.unwrap_or(System::id());
+            let referrer = referrer
+                .map(|referrer| p2swap::utils::find_referrer_address(&referrer, &base_mint).0);
+
+            // Hidden order stores commitment of recipient and salt, salt should be
+            // passed to recipient off-chain
+            let recipient_salt = if hidden {
+                Some(rand::random::<[u8; 32]>())
+            } else {
+                None
+            };
+            let recipient_commitment =
+                recipient_salt.map(|salt| p2swap::utils::hash_recipient(&recipient, &salt));
+
+            let rent_payer = rent_payer.map(|rent_payer| read_keypair_file(rent_payer).unwrap());
+
+            let gate = match (gate_mint, gate_collection) {
+                (Some(mint), None) => {
+                    let gate_decimals = utils::get_mint(&client, &mint)?.decimals;
+                    let min_amount = gate_min_amount.ok_or(ProgramError::InvalidArgument)?;
+
+                    Some(p2swap::state::Gate::MinBalance {
+                        mint,
+                        min_amount: spl_token::ui_amount_to_amount(min_amount, gate_decimals),
+                    })
+                }
+                (None, Some(collection)) => Some(p2swap::state::Gate::Collection { collection }),
+                (None, None) => None,
+                _ => return Err(ProgramError::InvalidArgument.into()),
+            };
+
             let pb = indicatif::ProgressBar::new_spinner();
             pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
             pb.set_message("Creating order..");
@@ -70,10 +130,10 @@ fn main() -> Result<(), error::Error> {
             let (order_pubkey, tx) = processor::create_order(
                 &client,
                 &wallet,
-                &recipient,
+                &recipient_commitment.map_or(recipient, |_| System::id()),
                 &token_account.unwrap_or(wallet.pubkey()),
                 &quote_token_account.unwrap_or(wallet.pubkey()),
-                &base_mint.unwrap_or(System::id()),
+                &base_mint,
                 &quote_mint.unwrap_or(System::id()),
                 spl_token::ui_amount_to_amount(base_amount, base_decimals),
                 spl_token::ui_amount_to_amount(quote_amount, quote_decimals),
@@ -84,11 +144,134 @@ fn main() -> Result<(), error::Error> {
                         .unwrap()
                         .timestamp(),
                 ),
+                price_curve,
+                spl_token::ui_amount_to_amount(
+                    floor_quote_amount.unwrap_or(quote_amount),
+                    quote_decimals,
+                ),
+                referrer.as_ref(),
+                referral_share_bps.unwrap_or(0),
+                collection_offer,
+                cancel_locked_until,
+                hashlock,
+                arbitration,
+                memo,
+                reference,
+                recipient_commitment,
+                gate,
+                rent_payer.as_ref(),
             )?;
 
             pb.finish_and_clear();
 
             println!("[+] Order created: {}, tx: {}", order_pubkey, tx);
+
+            if let Some(recipient_salt) = recipient_salt {
+                println!(
+                    "recipient_salt: {}",
+                    bs58::encode(recipient_salt).into_string()
+                );
+            }
+        }
+        args::Commands::BatchCreateOrders {
+            file,
+            token_account,
+            quote_token_account,
+            base_mint,
+            quote_mint,
+        } => {
+            let base_decimals = if base_mint.is_none() {
+                9
+            } else {
+                let mint = utils::get_mint(&client, &base_mint.unwrap())?;
+                mint.decimals
+            };
+
+            let quote_decimals = if quote_mint.is_none() {
+                9
+            } else {
+                let mint = utils::get_mint(&client, &quote_mint.unwrap())?;
+                mint.decimals
+            };
+
+            let default_expire_date = Utc::now()
+                .checked_add_signed(chrono::Duration::hours(1))
+                .unwrap()
+                .timestamp();
+
+            let orders = utils::read_create_batch(&file)?
+                .into_iter()
+                .map(|(recipient, base_amount, quote_amount, expire_date)| {
+                    (
+                        recipient,
+                        spl_token::ui_amount_to_amount(base_amount, base_decimals),
+                        spl_token::ui_amount_to_amount(quote_amount, quote_decimals),
+                        expire_date.unwrap_or(default_expire_date),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Creating orders..");
+
+            for chunk in orders.chunks(processor::MAX_BATCH_CREATE_ORDERS) {
+                let (order_pubkeys, tx) = processor::batch_create_orders(
+                    &client,
+                    &wallet,
+                    &token_account.unwrap_or(wallet.pubkey()),
+                    &quote_token_account.unwrap_or(wallet.pubkey()),
+                    &base_mint.unwrap_or(System::id()),
+                    &quote_mint.unwrap_or(System::id()),
+                    chunk,
+                )?;
+
+                for order_pubkey in order_pubkeys {
+                    pb.println(format!("[+] Order created: {}, tx: {}", order_pubkey, tx));
+                }
+            }
+
+            pb.finish_and_clear();
+        }
+        args::Commands::BatchCancelOrders {
+            file,
+            token_account,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Canceling orders..");
+
+            // Group `Order`'s by mint pair `OrderBook`
+            let mut order_books: Vec<(Pubkey, Vec<(Pubkey, Pubkey)>)> = Vec::new();
+
+            for order in utils::read_cancel_batch(&file)? {
+                let order_state = utils::get_order(&client, &order)?;
+                let order = (order, order_state.rent_payer);
+
+                match order_books
+                    .iter_mut()
+                    .find(|(order_book, _)| *order_book == order_state.order_book)
+                {
+                    Some((_, orders)) => orders.push(order),
+                    None => order_books.push((order_state.order_book, vec![order])),
+                }
+            }
+
+            for (order_book, orders) in order_books {
+                for chunk in orders.chunks(processor::MAX_BATCH_CANCEL_ORDERS) {
+                    let tx = processor::batch_cancel_orders(
+                        &client,
+                        &wallet,
+                        chunk,
+                        &token_account.unwrap_or(wallet.pubkey()),
+                        &order_book,
+                    )?;
+
+                    pb.println(format!("[+] {} orders canceled, tx: {}", chunk.len(), tx));
+                }
+            }
+
+            pb.finish_and_clear();
         }
         args::Commands::CancelOrder {
             order,
@@ -98,43 +281,220 @@ fn main() -> Result<(), error::Error> {
             pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
             pb.set_message("Canceling order..");
 
+            let order_state = utils::get_order(&client, &order)?;
+
             let tx = processor::cancel_order(
                 &client,
                 &wallet,
                 &order,
+                &order_state.rent_payer,
                 &token_account.unwrap_or(wallet.pubkey()),
+                &order_state.order_book,
             )?;
 
             pb.finish_and_clear();
 
             println!("[+] Order canceled, tx: {}", tx);
         }
+        args::Commands::DeclineOrder { order } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Declining order..");
+
+            let order_state = utils::get_order(&client, &order)?;
+
+            let tx = processor::decline_order(
+                &client,
+                &wallet,
+                &order,
+                &order_state.funder,
+                &order_state.rent_payer,
+                &order_state.funder_token_account,
+                &order_state.order_book,
+            )?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Order declined, tx: {}", tx);
+        }
+        args::Commands::CloseOrder { order } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Closing order..");
+
+            let order_state = utils::get_order(&client, &order)?;
+
+            let tx = processor::close_order(
+                &client,
+                &wallet,
+                &order,
+                &order_state.funder,
+                &order_state.rent_payer,
+            )?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Order closed, tx: {}", tx);
+        }
         args::Commands::ExecuteOrder {
             order,
             token_account,
             receive_token_account,
+            nft_mint,
+            funder_nft_token_account,
+            treasury_base_token_account,
+            treasury_quote_token_account,
+            referrer,
+            referral_share_bps,
+            discount_token_account,
+            recipient_salt,
+            gate_token_account,
         } => {
             let pb = indicatif::ProgressBar::new_spinner();
             pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
             pb.set_message("Executing order..");
 
             let order_state = utils::get_order(&client, &order)?;
+            // Treasury accounts are not checked while fee is not charged
+            let treasury =
+                utils::get_protocol(&client)?.map_or(wallet.pubkey(), |protocol| protocol.treasury);
+
+            // Collection gate is passed with metadata of NFT, that is held in gate token account
+            let gate = match order_state.gate() {
+                Some(gate) => {
+                    let gate_token_account =
+                        gate_token_account.ok_or(ProgramError::InvalidArgument)?;
+
+                    let gate_metadata = match gate {
+                        p2swap::state::Gate::Collection { .. } => {
+                            let token_account =
+                                utils::get_token_account(&client, &gate_token_account)?;
+                            Some(p2swap::utils::find_metadata_address(&token_account.mint).0)
+                        }
+                        p2swap::state::Gate::MinBalance { .. } => None,
+                    };
+
+                    Some((gate_token_account, gate_metadata))
+                }
+                None => None,
+            };
+
+            let collection_nft = if order_state.is_collection_offer() {
+                let nft_mint = nft_mint.ok_or(ProgramError::InvalidArgument)?;
+                let (metadata, _) = p2swap::utils::find_metadata_address(&nft_mint);
+
+                Some((
+                    metadata,
+                    funder_nft_token_account.ok_or(ProgramError::InvalidArgument)?,
+                ))
+            } else {
+                None
+            };
+
+            let maker_referrer = if order_state.has_referrer() {
+                let referrer_state = utils::get_referrer(&client, &order_state.referrer)?;
+                Some((order_state.referrer, referrer_state.token_account))
+            } else {
+                None
+            };
+
+            let taker_referrer = if let Some(referrer) = referrer {
+                let (referrer, _) =
+                    p2swap::utils::find_referrer_address(&referrer, &order_state.quote_mint);
+                let referrer_state = utils::get_referrer(&client, &referrer)?;
+                Some((referrer, referrer_state.token_account))
+            } else {
+                None
+            };
 
             let tx = processor::execute_order(
                 &client,
                 &wallet,
                 &order,
                 &order_state.funder,
+                &order_state.rent_payer,
                 &token_account.unwrap_or(wallet.pubkey()),
                 &receive_token_account.unwrap_or(wallet.pubkey()),
                 &order_state.quote_token_account,
+                &order_state.order_book,
+                &treasury_base_token_account.unwrap_or(treasury),
+                &treasury_quote_token_account.unwrap_or(treasury),
+                gate,
+                collection_nft,
+                maker_referrer,
+                taker_referrer,
+                referral_share_bps.unwrap_or(0),
+                discount_token_account.as_ref(),
+                None,
+                recipient_salt,
             )?;
 
             pb.finish_and_clear();
 
             println!("[+] Order executed, tx: {}", tx);
         }
-        args::Commands::GetOrdersHistory { funder, status } => {
+        args::Commands::ClaimOrder {
+            order,
+            secret,
+            receive_token_account,
+            treasury_base_token_account,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Claiming order..");
+
+            let order_state = utils::get_order(&client, &order)?;
+            // Treasury accounts are not checked while fee is not charged
+            let treasury =
+                utils::get_protocol(&client)?.map_or(wallet.pubkey(), |protocol| protocol.treasury);
+
+            let maker_referrer = if order_state.has_referrer() {
+                let referrer_state = utils::get_referrer(&client, &order_state.referrer)?;
+                Some((order_state.referrer, referrer_state.token_account))
+            } else {
+                None
+            };
+
+            let tx = processor::execute_order(
+                &client,
+                &wallet,
+                &order,
+                &order_state.funder,
+                &order_state.rent_payer,
+                &wallet.pubkey(),
+                &receive_token_account.unwrap_or(wallet.pubkey()),
+                &order_state.quote_token_account,
+                &order_state.order_book,
+                &treasury_base_token_account.unwrap_or(treasury),
+                &treasury,
+                None,
+                None,
+                maker_referrer,
+                None,
+                0,
+                None,
+                Some(secret),
+                None,
+            )?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Order claimed, tx: {}", tx);
+        }
+        args::Commands::GenerateSecret => {
+            let secret: [u8; 32] = rand::random();
+
+            println!("secret: {}", bs58::encode(secret).into_string());
+            println!(
+                "hashlock: {}",
+                bs58::encode(p2swap::utils::hash_preimage(&secret)).into_string()
+            );
+        }
+        args::Commands::GetOrdersHistory {
+            funder,
+            status,
+            reference,
+        } => {
             let pb = indicatif::ProgressBar::new_spinner();
             pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
             pb.set_message("Obtaining orders history..");
@@ -144,6 +504,11 @@ fn main() -> Result<(), error::Error> {
                     args::OrderStatusArg::Created => Some(p2swap::state::OrderStatus::Created),
                     args::OrderStatusArg::Canceled => Some(p2swap::state::OrderStatus::Canceled),
                     args::OrderStatusArg::Completed => Some(p2swap::state::OrderStatus::Created),
+                    args::OrderStatusArg::Declined => Some(p2swap::state::OrderStatus::Declined),
+                    args::OrderStatusArg::DeliveryClaimed => {
+                        Some(p2swap::state::OrderStatus::DeliveryClaimed)
+                    }
+                    args::OrderStatusArg::Disputed => Some(p2swap::state::OrderStatus::Disputed),
                 }
             } else {
                 None
@@ -153,6 +518,7 @@ fn main() -> Result<(), error::Error> {
                 &client,
                 &funder.unwrap_or(wallet.pubkey()),
                 order_status,
+                reference,
             )?;
 
             pb.finish_and_clear();
@@ -162,6 +528,228 @@ fn main() -> Result<(), error::Error> {
                 println!();
             }
         }
+        args::Commands::GetOrderBook {
+            base_mint,
+            quote_mint,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Obtaining order book..");
+
+            let (order_book_pubkey, _) = p2swap::utils::find_order_book_address(
+                &base_mint.unwrap_or(System::id()),
+                &quote_mint.unwrap_or(System::id()),
+            );
+            let order_book = utils::get_order_book(&client, &order_book_pubkey)?;
+
+            pb.finish_and_clear();
+
+            utils::print_order_book(&client, &order_book_pubkey, &order_book)?;
+        }
+        args::Commands::PruneOrderBook {
+            base_mint,
+            quote_mint,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Pruning order book..");
+
+            let (order_book_pubkey, _) = p2swap::utils::find_order_book_address(
+                &base_mint.unwrap_or(System::id()),
+                &quote_mint.unwrap_or(System::id()),
+            );
+            let order_book = utils::get_order_book(&client, &order_book_pubkey)?;
+
+            // Expired `Order`'s are pruned by program, finished and closed ones are passed
+            let mut inactive_orders = Vec::new();
+            for entry in order_book.entries.iter() {
+                let is_active = match client
+                    .get_account_with_commitment(&entry.order, client.commitment())?
+                    .value
+                {
+                    Some(_) => {
+                        utils::get_order(&client, &entry.order)?.status
+                            == p2swap::state::OrderStatus::Created as u8
+                    }
+                    None => false,
+                };
+
+                if !is_active {
+                    inactive_orders.push(entry.order);
+                }
+            }
+
+            let tx = processor::prune_order_book(
+                &client,
+                &wallet,
+                &order_book_pubkey,
+                &inactive_orders,
+            )?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Order book pruned, tx: {}", tx);
+        }
+        args::Commands::GetRfqQuotes { rfq } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Obtaining rfq quotes..");
+
+            let rfq_state = utils::get_rfq(&client, &rfq)?;
+            let quotes = utils::get_rfq_quotes(&client, &rfq)?;
+
+            pb.finish_and_clear();
+
+            utils::print_rfq(&client, &rfq, &rfq_state)?;
+            println!();
+
+            for (quote_pubkey, quote) in quotes {
+                utils::print_quote(&client, &quote_pubkey, &quote)?;
+                println!();
+            }
+        }
+        args::Commands::Stats {
+            base_mint,
+            quote_mint,
+            twap_window,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Obtaining stats..");
+
+            let (stats_pubkey, _) = p2swap::utils::find_stats_address();
+            let stats = utils::get_stats(&client, &stats_pubkey)?;
+
+            // Pair stats are shown only if pair is specified
+            let pair_stats = if base_mint.is_some() || quote_mint.is_some() {
+                let (order_book, _) = p2swap::utils::find_order_book_address(
+                    &base_mint.unwrap_or(System::id()),
+                    &quote_mint.unwrap_or(System::id()),
+                );
+                let (pair_stats_pubkey, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
+                Some((
+                    pair_stats_pubkey,
+                    utils::get_pair_stats(&client, &pair_stats_pubkey)?,
+                ))
+            } else {
+                None
+            };
+
+            pb.finish_and_clear();
+
+            utils::print_stats(&stats_pubkey, &stats);
+
+            if let Some((pair_stats_pubkey, pair_stats)) = pair_stats {
+                println!();
+                utils::print_pair_stats(&client, &pair_stats_pubkey, &pair_stats, twap_window)?;
+            }
+        }
+        args::Commands::GetProfile {
+            wallet: profile_wallet,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Obtaining profile..");
+
+            let (profile_pubkey, _) =
+                p2swap::utils::find_profile_address(&profile_wallet.unwrap_or(wallet.pubkey()));
+            let profile = utils::get_profile(&client, &profile_pubkey)?;
+
+            pb.finish_and_clear();
+
+            utils::print_profile(&profile_pubkey, &profile);
+        }
+        args::Commands::Stake {
+            token_account,
+            amount,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Staking tokens..");
+
+            let staking_pool = utils::get_staking_pool(&client)?;
+            let mint = utils::get_mint(&client, &staking_pool.stake_mint)?;
+
+            let tx = processor::stake_tokens(
+                &client,
+                &wallet,
+                &token_account,
+                spl_token::ui_amount_to_amount(amount, mint.decimals),
+            )?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Tokens staked, tx: {}", tx);
+        }
+        args::Commands::Unstake {
+            token_account,
+            amount,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Unstaking tokens..");
+
+            let staking_pool = utils::get_staking_pool(&client)?;
+            let mint = utils::get_mint(&client, &staking_pool.stake_mint)?;
+
+            let tx = processor::unstake(
+                &client,
+                &wallet,
+                &token_account,
+                spl_token::ui_amount_to_amount(amount, mint.decimals),
+            )?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Tokens unstaked, tx: {}", tx);
+        }
+        args::Commands::WithdrawStake { token_account } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Withdrawing stake..");
+
+            let tx = processor::withdraw_stake(&client, &wallet, &token_account)?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Stake withdrawn, tx: {}", tx);
+        }
+        args::Commands::ClaimRewards {
+            mint,
+            token_account,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Claiming rewards..");
+
+            let tx = processor::claim_rewards(
+                &client,
+                &wallet,
+                &token_account.unwrap_or(wallet.pubkey()),
+                &mint.unwrap_or(System::id()),
+            )?;
+
+            pb.finish_and_clear();
+
+            println!("[+] Rewards claimed, tx: {}", tx);
+        }
+        args::Commands::GetStake {
+            wallet: stake_wallet,
+        } => {
+            let pb = indicatif::ProgressBar::new_spinner();
+            pb.enable_steady_tick(Duration::from_millis(120).as_millis() as u64);
+            pb.set_message("Obtaining stake..");
+
+            let (stake_pubkey, _) =
+                p2swap::utils::find_stake_address(&stake_wallet.unwrap_or(wallet.pubkey()));
+            let stake = utils::get_stake(&client, &stake_pubkey)?;
+            let staking_pool = utils::get_staking_pool(&client)?;
+
+            pb.finish_and_clear();
+
+            utils::print_stake(&stake_pubkey, &stake, &staking_pool);
+        }
     }
 
     Ok(())
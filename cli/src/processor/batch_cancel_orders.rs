@@ -0,0 +1,72 @@
+//! Module provide `BatchCancelOrders` instruction handler.
+
+use crate::error;
+use anchor_client::anchor_lang::{Id, InstructionData, System, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    signature::{Keypair, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+
+/// Highest quantity of `Order`'s, that fits in one transaction.
+pub const MAX_BATCH_CANCEL_ORDERS: usize = 8;
+
+/// Handler.
+///
+/// `orders` (`Order` and its rent payer) should belong to the same `order_book`.
+pub fn batch_cancel_orders(
+    client: &RpcClient,
+    wallet: &Keypair,
+    orders: &[(Pubkey, Pubkey)],
+    token_account: &Pubkey,
+    order_book: &Pubkey,
+) -> Result<Signature, error::Error> {
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&wallet.pubkey());
+
+    let mut accounts = p2swap::accounts::BatchCancelOrders {
+        funder: wallet.pubkey(),
+        funder_token_account: token_account.clone(),
+        order_book: order_book.clone(),
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let mut escrow_bumps = Vec::new();
+
+    for (order, rent_payer) in orders {
+        let (escrow, escrow_bump) =
+            p2swap::utils::find_order_escrow_address(&wallet.pubkey(), order);
+
+        accounts.push(AccountMeta::new(*order, false));
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new(*rent_payer, false));
+
+        escrow_bumps.push(escrow_bump);
+    }
+
+    let data = p2swap::instruction::BatchCancelOrders { escrow_bumps }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let last_blockhash = client.get_latest_blockhash()?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&wallet.pubkey()),
+        &[wallet],
+        last_blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&tx)?)
+}
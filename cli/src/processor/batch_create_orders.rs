@@ -0,0 +1,101 @@
+//! Module provide `BatchCreateOrders` instruction handler.
+
+use crate::error;
+use anchor_client::anchor_lang::{Id, InstructionData, System, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    signature::{Keypair, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+
+/// Highest quantity of `Order`'s, that fits in one transaction.
+pub const MAX_BATCH_CREATE_ORDERS: usize = 4;
+
+/// Handler.
+///
+/// `orders` - `(recipient, base_amount, quote_amount, expire_date)` of each `Order`.
+pub fn batch_create_orders(
+    client: &RpcClient,
+    wallet: &Keypair,
+    token_account: &Pubkey,
+    quote_token_account: &Pubkey,
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    orders: &[(Pubkey, u64, u64, i64)],
+) -> Result<(Vec<Pubkey>, Signature), error::Error> {
+    let (order_book, _) = p2swap::utils::find_order_book_address(base_mint, quote_mint);
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&wallet.pubkey());
+
+    let mut accounts = p2swap::accounts::BatchCreateOrders {
+        funder: wallet.pubkey(),
+        funder_token_account: token_account.clone(),
+        quote_token_account: quote_token_account.clone(),
+        escrow_mint: base_mint.clone(),
+        quote_mint: quote_mint.clone(),
+        order_book,
+        funder_profile,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let order_keypairs: Vec<Keypair> = orders.iter().map(|_| Keypair::new()).collect();
+    let mut args = Vec::new();
+
+    for (order, (recipient, base_amount, quote_amount, expire_date)) in
+        order_keypairs.iter().zip(orders)
+    {
+        let (escrow, escrow_bump) =
+            p2swap::utils::find_order_escrow_address(&wallet.pubkey(), &order.pubkey());
+
+        accounts.push(AccountMeta::new(order.pubkey(), true));
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new_readonly(*recipient, false));
+
+        args.push(p2swap::state::BatchOrderArgs {
+            escrow_bump,
+            base_amount: *base_amount,
+            quote_amount: *quote_amount,
+            start_date: None,
+            expire_date: *expire_date,
+            price_curve: p2swap::state::PriceCurve::Fixed,
+            floor_quote_amount: *quote_amount,
+            cancel_locked_until: None,
+            memo: None,
+            reference: None,
+        });
+    }
+
+    let data = p2swap::instruction::BatchCreateOrders { orders: args }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let last_blockhash = client.get_latest_blockhash()?;
+
+    let mut signers = vec![wallet];
+    signers.extend(order_keypairs.iter());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&wallet.pubkey()),
+        &signers,
+        last_blockhash,
+    );
+
+    let signature = client.send_and_confirm_transaction(&tx)?;
+
+    Ok((
+        order_keypairs.iter().map(|order| order.pubkey()).collect(),
+        signature,
+    ))
+}
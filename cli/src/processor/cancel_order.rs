@@ -8,6 +8,7 @@ use solana_sdk::{
     pubkey::Pubkey,
     signature::Signature,
     signature::{Keypair, Signer},
+    sysvar,
     transaction::Transaction,
 };
 
@@ -16,15 +17,22 @@ pub fn cancel_order(
     client: &RpcClient,
     wallet: &Keypair,
     order: &Pubkey,
+    rent_payer: &Pubkey,
     token_account: &Pubkey,
+    order_book: &Pubkey,
 ) -> Result<Signature, error::Error> {
     let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(&wallet.pubkey(), order);
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&wallet.pubkey());
 
     let accounts = p2swap::accounts::CancelOrder {
         order: order.clone(),
         funder: wallet.pubkey(),
+        rent_payer: rent_payer.clone(),
         funder_token_account: token_account.clone(),
         escrow,
+        order_book: order_book.clone(),
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
     }
@@ -0,0 +1,54 @@
+//! Module provide `ClaimRewards` instruction handler.
+
+use crate::error;
+use anchor_client::anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Handler.
+pub fn claim_rewards(
+    client: &RpcClient,
+    wallet: &Keypair,
+    token_account: &Pubkey,
+    reward_mint: &Pubkey,
+) -> Result<Signature, error::Error> {
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let (stake, _) = p2swap::utils::find_stake_address(&wallet.pubkey());
+    let (reward_vault, reward_vault_bump) = p2swap::utils::find_reward_vault_address(reward_mint);
+
+    let accounts = p2swap::accounts::ClaimRewards {
+        staking_pool,
+        stake,
+        wallet: wallet.pubkey(),
+        wallet_token_account: token_account.clone(),
+        reward_mint: reward_mint.clone(),
+        reward_vault,
+        token_program: spl_token::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::ClaimRewards { reward_vault_bump }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let last_blockhash = client.get_latest_blockhash()?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&wallet.pubkey()),
+        &[wallet],
+        last_blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&tx)?)
+}
@@ -0,0 +1,48 @@
+//! Module provide `CloseOrder` instruction handler.
+
+use crate::error;
+use anchor_client::anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Handler.
+pub fn close_order(
+    client: &RpcClient,
+    wallet: &Keypair,
+    order: &Pubkey,
+    funder: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Result<Signature, error::Error> {
+    let accounts = p2swap::accounts::CloseOrder {
+        order: order.clone(),
+        authority: wallet.pubkey(),
+        funder: funder.clone(),
+        rent_payer: rent_payer.clone(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CloseOrder {}.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let last_blockhash = client.get_latest_blockhash()?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&wallet.pubkey()),
+        &[wallet],
+        last_blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&tx)?)
+}
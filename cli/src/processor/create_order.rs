@@ -4,7 +4,7 @@ use crate::error;
 use anchor_client::anchor_lang::{Id, InstructionData, System, ToAccountMetas};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Signature,
     signature::{Keypair, Signer},
@@ -25,21 +25,45 @@ pub fn create_order(
     quote_amount: u64,
     start_date: Option<i64>,
     expire_date: i64,
+    price_curve: p2swap::state::PriceCurve,
+    floor_quote_amount: u64,
+    referrer: Option<&Pubkey>,
+    referral_share_bps: u16,
+    collection_offer: bool,
+    cancel_locked_until: Option<i64>,
+    hashlock: Option<[u8; 32]>,
+    arbitration: Option<p2swap::state::ArbitrationArgs>,
+    memo: Option<String>,
+    reference: Option<[u8; 32]>,
+    recipient_commitment: Option<[u8; 32]>,
+    gate: Option<p2swap::state::Gate>,
+    rent_payer: Option<&Keypair>,
 ) -> Result<(Pubkey, Signature), error::Error> {
     let order = Keypair::new();
 
+    // Sponsor pays rent and transaction fee, so `wallet` doesn't need native `SOL`'s
+    let rent_payer = rent_payer.unwrap_or(wallet);
+
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&wallet.pubkey(), &order.pubkey());
+    let (order_book, _) = p2swap::utils::find_order_book_address(base_mint, quote_mint);
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&wallet.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
 
-    let accounts = p2swap::accounts::CreateOrder {
+    let mut accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey().clone(),
         recipient: recipient.clone(),
         quote_token_account: quote_token_account.clone(),
         escrow_mint: base_mint.clone(),
         quote_mint: quote_mint.clone(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         funder: wallet.pubkey(),
+        rent_payer: rent_payer.pubkey(),
         funder_token_account: token_account.clone(),
         escrow,
         token_program: spl_token::id(),
@@ -47,12 +71,27 @@ pub fn create_order(
     }
     .to_account_metas(None);
 
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new_readonly(*referrer, false));
+    }
+
     let data = p2swap::instruction::CreateOrder {
         escrow_bump,
         base_amount,
         quote_amount,
         start_date,
         expire_date,
+        price_curve,
+        floor_quote_amount,
+        referral_share_bps,
+        collection_offer,
+        cancel_locked_until,
+        hashlock,
+        arbitration,
+        memo,
+        reference,
+        recipient_commitment,
+        gate,
     }
     .data();
 
@@ -66,8 +105,8 @@ pub fn create_order(
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction],
-        Some(&wallet.pubkey()),
-        &[wallet, &order],
+        Some(&rent_payer.pubkey()),
+        &[rent_payer, wallet, &order],
         last_blockhash,
     );
 
@@ -0,0 +1,57 @@
+//! Module provide `DeclineOrder` instruction handler.
+
+use crate::error;
+use anchor_client::anchor_lang::{Id, InstructionData, System, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Handler.
+pub fn decline_order(
+    client: &RpcClient,
+    wallet: &Keypair,
+    order: &Pubkey,
+    funder: &Pubkey,
+    rent_payer: &Pubkey,
+    funder_token_account: &Pubkey,
+    order_book: &Pubkey,
+) -> Result<Signature, error::Error> {
+    let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(funder, order);
+
+    let accounts = p2swap::accounts::DeclineOrder {
+        order: order.clone(),
+        funder: funder.clone(),
+        rent_payer: rent_payer.clone(),
+        recipient: wallet.pubkey(),
+        funder_token_account: funder_token_account.clone(),
+        escrow,
+        order_book: order_book.clone(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::DeclineOrder { escrow_bump }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let last_blockhash = client.get_latest_blockhash()?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&wallet.pubkey()),
+        &[wallet],
+        last_blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&tx)?)
+}
@@ -4,7 +4,7 @@ use crate::error;
 use anchor_client::anchor_lang::{Id, InstructionData, System, ToAccountMetas};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Signature,
     signature::{Keypair, Signer},
@@ -18,13 +18,30 @@ pub fn execute_order(
     wallet: &Keypair,
     order: &Pubkey,
     funder: &Pubkey,
+    rent_payer: &Pubkey,
     token_account: &Pubkey,
     receive_token_account: &Pubkey,
     quote_token_account: &Pubkey,
+    order_book: &Pubkey,
+    treasury_base_token_account: &Pubkey,
+    treasury_quote_token_account: &Pubkey,
+    gate: Option<(Pubkey, Option<Pubkey>)>,
+    collection_nft: Option<(Pubkey, Pubkey)>,
+    maker_referrer: Option<(Pubkey, Pubkey)>,
+    taker_referrer: Option<(Pubkey, Pubkey)>,
+    taker_referral_share_bps: u16,
+    discount_token_account: Option<&Pubkey>,
+    preimage: Option<[u8; 32]>,
+    recipient_salt: Option<[u8; 32]>,
 ) -> Result<Signature, error::Error> {
     let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(funder, order);
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+    let (funder_profile, _) = p2swap::utils::find_profile_address(funder);
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&wallet.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(order_book);
 
-    let accounts = p2swap::accounts::ExecuteOrder {
+    let mut accounts = p2swap::accounts::ExecuteOrder {
         order: order.clone(),
         recipient: wallet.pubkey(),
         recipient_token_account: token_account.clone(),
@@ -32,13 +49,60 @@ pub fn execute_order(
         quote_token_account: quote_token_account.clone(),
         clock_sysvar: sysvar::clock::id(),
         funder: funder.clone(),
+        rent_payer: rent_payer.clone(),
         escrow,
+        order_book: order_book.clone(),
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
+        protocol,
+        treasury_base_token_account: treasury_base_token_account.clone(),
+        treasury_quote_token_account: treasury_quote_token_account.clone(),
         token_program: spl_token::id(),
         system_program: System::id(),
     }
     .to_account_metas(None);
 
-    let data = p2swap::instruction::ExecuteOrder { escrow_bump }.data();
+    // Gate accounts are passed as `[gate token account, NFT metadata]`,
+    // metadata only for collection gate
+    if let Some((gate_token_account, gate_metadata)) = gate {
+        accounts.push(AccountMeta::new_readonly(gate_token_account, false));
+
+        if let Some(gate_metadata) = gate_metadata {
+            accounts.push(AccountMeta::new_readonly(gate_metadata, false));
+        }
+    }
+
+    if let Some((metadata, funder_nft_token_account)) = collection_nft {
+        accounts.push(AccountMeta::new_readonly(metadata, false));
+        accounts.push(AccountMeta::new(funder_nft_token_account, false));
+    }
+
+    // Referrer accounts are passed as `[referrer, referrer token account]` pairs,
+    // maker referrer goes first
+    let taker_referral_share_bps = if taker_referrer.is_some() {
+        taker_referral_share_bps
+    } else {
+        0
+    };
+
+    for (referrer, referrer_token_account) in maker_referrer.into_iter().chain(taker_referrer) {
+        accounts.push(AccountMeta::new(referrer, false));
+        accounts.push(AccountMeta::new(referrer_token_account, false));
+    }
+
+    if let Some(discount_token_account) = discount_token_account {
+        accounts.push(AccountMeta::new_readonly(*discount_token_account, false));
+    }
+
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps,
+        preimage,
+        recipient_salt,
+    }
+    .data();
 
     let instruction = Instruction {
         program_id: p2swap::id(),
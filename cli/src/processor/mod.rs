@@ -1,9 +1,27 @@
 //! Module provide handlers for `p2swap` program instructions.
 
+mod batch_cancel_orders;
+mod batch_create_orders;
 mod cancel_order;
+mod claim_rewards;
+mod close_order;
 mod create_order;
+mod decline_order;
 mod execute_order;
+mod prune_order_book;
+mod stake_tokens;
+mod unstake;
+mod withdraw_stake;
 
+pub use batch_cancel_orders::*;
+pub use batch_create_orders::*;
 pub use cancel_order::*;
+pub use claim_rewards::*;
+pub use close_order::*;
 pub use create_order::*;
+pub use decline_order::*;
 pub use execute_order::*;
+pub use prune_order_book::*;
+pub use stake_tokens::*;
+pub use unstake::*;
+pub use withdraw_stake::*;
@@ -0,0 +1,50 @@
+//! Module provide `PruneOrderBook` instruction handler.
+
+use crate::error;
+use anchor_client::anchor_lang::{InstructionData, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signature,
+    signature::{Keypair, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+
+/// Handler.
+pub fn prune_order_book(
+    client: &RpcClient,
+    wallet: &Keypair,
+    order_book: &Pubkey,
+    orders: &[Pubkey],
+) -> Result<Signature, error::Error> {
+    let mut accounts = p2swap::accounts::PruneOrderBook {
+        order_book: order_book.clone(),
+        clock_sysvar: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    for order in orders {
+        accounts.push(AccountMeta::new_readonly(order.clone(), false));
+    }
+
+    let data = p2swap::instruction::PruneOrderBook {}.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let last_blockhash = client.get_latest_blockhash()?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&wallet.pubkey()),
+        &[wallet],
+        last_blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&tx)?)
+}
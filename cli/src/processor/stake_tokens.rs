@@ -0,0 +1,58 @@
+//! Module provide `StakeTokens` instruction handler.
+
+use crate::error;
+use anchor_client::anchor_lang::{Id, InstructionData, System, ToAccountMetas};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Handler.
+pub fn stake_tokens(
+    client: &RpcClient,
+    wallet: &Keypair,
+    token_account: &Pubkey,
+    amount: u64,
+) -> Result<Signature, error::Error> {
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let (stake, _) = p2swap::utils::find_stake_address(&wallet.pubkey());
+    let (stake_vault, stake_vault_bump) = p2swap::utils::find_stake_vault_address();
+
+    let accounts = p2swap::accounts::StakeTokens {
+        staking_pool,
+        stake,
+        wallet: wallet.pubkey(),
+        wallet_token_account: token_account.clone(),
+        stake_vault,
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::StakeTokens {
+        stake_vault_bump,
+        amount,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let last_blockhash = client.get_latest_blockhash()?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&wallet.pubkey()),
+        &[wallet],
+        last_blockhash,
+    );
+
+    Ok(client.send_and_confirm_transaction(&tx)?)
+}
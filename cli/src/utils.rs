@@ -1,33 +1,102 @@
 //! Module provide application utils.
 
 use crate::error;
+use anchor_client::anchor_lang::{AccountDeserialize, Id, System};
+use chrono::Utc;
 use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use solana_client::{
     rpc_client::RpcClient,
     rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
     rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
 };
-use solana_sdk::{borsh::try_from_slice_unchecked, program_pack::Pack, pubkey::Pubkey};
+use solana_sdk::{
+    borsh::try_from_slice_unchecked, program_error::ProgramError, program_pack::Pack,
+    pubkey::Pubkey,
+};
+use std::{fs, str::FromStr};
+
+/// Return lines of batch file with line numbers, split by whitespace
+/// (empty lines and `#` comments are skipped).
+fn read_batch_file(path: &str) -> Result<Vec<(usize, Vec<String>)>, error::Error> {
+    let content = fs::read_to_string(path)?;
+
+    Ok(content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            (
+                index + 1,
+                line.split('#')
+                    .next()
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .filter(|(_, fields)| !fields.is_empty())
+        .collect())
+}
+
+/// Return `(recipient, base_amount, quote_amount, expire_date)` of each `Order` in batch file.
+pub fn read_create_batch(path: &str) -> Result<Vec<(Pubkey, f64, f64, Option<i64>)>, error::Error> {
+    read_batch_file(path)?
+        .into_iter()
+        .map(|(line, fields)| {
+            let invalid_line = || error::Error::InvalidBatchLine(line);
+
+            if fields.len() < 3 || fields.len() > 4 {
+                return Err(invalid_line());
+            }
+
+            let recipient = Pubkey::from_str(&fields[0]).map_err(|_| invalid_line())?;
+            let base_amount = fields[1].parse::<f64>().map_err(|_| invalid_line())?;
+            let quote_amount = fields[2].parse::<f64>().map_err(|_| invalid_line())?;
+            let expire_date = fields
+                .get(3)
+                .map(|expire_date| expire_date.parse::<i64>().map_err(|_| invalid_line()))
+                .transpose()?;
+
+            Ok((recipient, base_amount, quote_amount, expire_date))
+        })
+        .collect()
+}
+
+/// Return `Order` addresses of batch file.
+pub fn read_cancel_batch(path: &str) -> Result<Vec<Pubkey>, error::Error> {
+    read_batch_file(path)?
+        .into_iter()
+        .map(|(line, fields)| {
+            if fields.len() != 1 {
+                return Err(error::Error::InvalidBatchLine(line));
+            }
+
+            Pubkey::from_str(&fields[0]).map_err(|_| error::Error::InvalidBatchLine(line))
+        })
+        .collect()
+}
 
 /// Return p2swap `Order` state.
 pub fn get_order(client: &RpcClient, order: &Pubkey) -> Result<p2swap::state::Order, error::Error> {
     let data = client.get_account_data(order)?;
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&data[8..])?;
+    let order =
+        p2swap::state::Order::try_deserialize(&mut data.as_slice()).map_err(ProgramError::from)?;
 
     Ok(order)
 }
 
-/// Return p2swap `Order` history for specific `funder` and `order_status`.
-pub fn get_orders_history(
-    client: &RpcClient,
+/// Return `getProgramAccounts` filters of `Order`'s for specific `funder`, `order_status`
+/// and client `reference`.
+fn orders_history_filters(
     funder: &Pubkey,
     order_status: Option<p2swap::state::OrderStatus>,
-) -> Result<Vec<(Pubkey, p2swap::state::Order)>, error::Error> {
+    reference: Option<[u8; 32]>,
+) -> Vec<RpcFilterType> {
     let mut filters = vec![
         RpcFilterType::DataSize(p2swap::state::Order::LEN as u64),
         RpcFilterType::Memcmp(Memcmp {
-            offset: 89,
+            offset: p2swap::state::Order::FUNDER_OFFSET,
             bytes: MemcmpEncodedBytes::Base58(bs58::encode(funder).into_string()),
             encoding: None,
         }),
@@ -41,6 +110,26 @@ pub fn get_orders_history(
         }));
     }
 
+    if let Some(reference) = reference {
+        filters.push(RpcFilterType::Memcmp(Memcmp {
+            offset: p2swap::state::Order::REFERENCE_OFFSET,
+            bytes: MemcmpEncodedBytes::Base58(bs58::encode(reference).into_string()),
+            encoding: None,
+        }));
+    }
+
+    filters
+}
+
+/// Return p2swap `Order` history for specific `funder`, `order_status` and client `reference`.
+pub fn get_orders_history(
+    client: &RpcClient,
+    funder: &Pubkey,
+    order_status: Option<p2swap::state::OrderStatus>,
+    reference: Option<[u8; 32]>,
+) -> Result<Vec<(Pubkey, p2swap::state::Order)>, error::Error> {
+    let filters = orders_history_filters(funder, order_status, reference);
+
     let accounts = client.get_program_accounts_with_config(
         &p2swap::id(),
         RpcProgramAccountsConfig {
@@ -62,7 +151,7 @@ pub fn get_orders_history(
         .map(|(account_pubkey, account)| {
             (
                 account_pubkey.clone(),
-                try_from_slice_unchecked::<p2swap::state::Order>(&account.data[8..]).unwrap(),
+                p2swap::state::Order::try_deserialize(&mut account.data.as_slice()).unwrap(),
             )
         })
         .collect())
@@ -86,7 +175,7 @@ pub fn print_order(
     };
 
     println!("pubkey: {}", order_pubkey);
-    println!("status: {:?}", order.status);
+    println!("status: {:?}", order.status().map_err(ProgramError::from)?);
     println!(
         "base_amount: {}",
         spl_token::amount_to_ui_amount(order.base_amount, base_decimals)
@@ -95,20 +184,464 @@ pub fn print_order(
         "quote_amount: {}",
         spl_token::amount_to_ui_amount(order.quote_amount, quote_decimals)
     );
+    println!(
+        "price_curve: {:?}",
+        order.price_curve().map_err(ProgramError::from)?
+    );
+    println!(
+        "floor_quote_amount: {}",
+        spl_token::amount_to_ui_amount(order.floor_quote_amount, quote_decimals)
+    );
+    println!(
+        "current_quote_amount: {}",
+        spl_token::amount_to_ui_amount(
+            order.current_quote_amount(Utc::now().timestamp()),
+            quote_decimals
+        )
+    );
     println!("base_mint: {}", order.base_mint);
     println!("quote_mint: {}", order.quote_mint);
     println!("funder: {}", order.funder);
+    if order.rent_payer != order.funder {
+        println!("rent_payer: {}", order.rent_payer);
+    }
+    println!("funder_token_account: {}", order.funder_token_account);
     println!("recipient: {}", order.recipient);
+    if order.is_hidden() {
+        println!(
+            "recipient_commitment: {}",
+            bs58::encode(order.recipient_commitment).into_string()
+        );
+    }
+    match order.gate() {
+        Some(p2swap::state::Gate::MinBalance { mint, min_amount }) => {
+            println!("gate_mint: {}", mint);
+            println!("gate_min_amount: {}", min_amount);
+        }
+        Some(p2swap::state::Gate::Collection { collection }) => {
+            println!("gate_collection: {}", collection);
+        }
+        None => {}
+    }
     println!("escrow: {}", order.escrow);
     println!("quote_token_account: {}", order.quote_token_account);
-    println!("start_date: {:?}", order.start_date);
-    println!("expire_date: {}", order.expire_date);
+    println!("start_date: {:?}", order.start_date());
+    println!("expire_date: {}", { order.expire_date });
+    println!("created_at: {}", { order.created_at });
+    println!("cancel_locked_until: {:?}", order.cancel_locked_until());
+    if order.is_htlc() {
+        println!("hashlock: {}", bs58::encode(order.hashlock).into_string());
+        if order.status().map_err(ProgramError::from)? == p2swap::state::OrderStatus::Completed {
+            println!("secret: {}", bs58::encode(order.preimage).into_string());
+        }
+    }
+    println!("order_book: {}", order.order_book);
+    if order.is_collection_offer() {
+        println!("quote_collection: {}", order.quote_collection);
+    }
+    if let Some(reference) = order.reference() {
+        println!("reference: {}", bs58::encode(reference).into_string());
+    }
+    println!("memo: {}", order.memo());
+    if order.is_arbitrated() {
+        println!("arbiter: {}", order.arbiter);
+        println!("dispute_window: {}", { order.dispute_window });
+        println!("delivery_claimed_at: {}", { order.delivery_claimed_at });
+        println!("disputed_at: {}", { order.disputed_at });
+    }
+    if order.has_referrer() {
+        println!("referrer: {}", order.referrer);
+        println!("referral_share_bps: {}", { order.referral_share_bps });
+    }
+
+    Ok(())
+}
+
+/// Return p2swap `OrderBook` state.
+pub fn get_order_book(
+    client: &RpcClient,
+    order_book: &Pubkey,
+) -> Result<p2swap::state::OrderBook, error::Error> {
+    let data = client.get_account_data(order_book)?;
+
+    let order_book = try_from_slice_unchecked::<p2swap::state::OrderBook>(&data[8..])?;
+
+    Ok(order_book)
+}
+
+/// Return p2swap `Protocol` state (`None` if it's not initialized, then fee is not charged).
+pub fn get_protocol(client: &RpcClient) -> Result<Option<p2swap::state::Protocol>, error::Error> {
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let account = match client
+        .get_account_with_commitment(&protocol, client.commitment())?
+        .value
+    {
+        Some(account) => account,
+        None => return Ok(None),
+    };
+
+    let protocol = try_from_slice_unchecked::<p2swap::state::Protocol>(&account.data[8..])?;
+
+    Ok(Some(protocol))
+}
+
+/// Return p2swap `Referrer` state.
+pub fn get_referrer(
+    client: &RpcClient,
+    referrer: &Pubkey,
+) -> Result<p2swap::state::Referrer, error::Error> {
+    let data = client.get_account_data(referrer)?;
+
+    let referrer = try_from_slice_unchecked::<p2swap::state::Referrer>(&data[8..])?;
+
+    Ok(referrer)
+}
+
+/// Print order book.
+pub fn print_order_book(
+    client: &RpcClient,
+    order_book_pubkey: &Pubkey,
+    order_book: &p2swap::state::OrderBook,
+) -> Result<(), error::Error> {
+    let base_decimals = if order_book.base_mint == System::id() {
+        9
+    } else {
+        get_mint(&client, &order_book.base_mint)?.decimals
+    };
+    let quote_decimals = if order_book.quote_mint == System::id() {
+        9
+    } else {
+        get_mint(&client, &order_book.quote_mint)?.decimals
+    };
+
+    println!("pubkey: {}", order_book_pubkey);
+    println!("base_mint: {}", order_book.base_mint);
+    println!("quote_mint: {}", order_book.quote_mint);
+
+    for entry in order_book.entries.iter() {
+        println!();
+        println!("order: {}", entry.order);
+        println!(
+            "base_amount: {}",
+            spl_token::amount_to_ui_amount(entry.base_amount, base_decimals)
+        );
+        println!(
+            "quote_amount: {}",
+            spl_token::amount_to_ui_amount(entry.quote_amount, quote_decimals)
+        );
+        println!("expire_date: {}", entry.expire_date);
+    }
 
     Ok(())
 }
 
+/// Return p2swap `Rfq` state.
+pub fn get_rfq(client: &RpcClient, rfq: &Pubkey) -> Result<p2swap::state::Rfq, error::Error> {
+    let data = client.get_account_data(rfq)?;
+
+    let rfq = try_from_slice_unchecked::<p2swap::state::Rfq>(&data[8..])?;
+
+    Ok(rfq)
+}
+
+/// Return open p2swap `Quote`'s for specific `rfq`.
+pub fn get_rfq_quotes(
+    client: &RpcClient,
+    rfq: &Pubkey,
+) -> Result<Vec<(Pubkey, p2swap::state::Quote)>, error::Error> {
+    let filters = vec![
+        RpcFilterType::DataSize(p2swap::state::Quote::LEN as u64),
+        RpcFilterType::Memcmp(Memcmp {
+            offset: 8,
+            bytes: MemcmpEncodedBytes::Base58(bs58::encode(rfq).into_string()),
+            encoding: None,
+        }),
+    ];
+
+    let accounts = client.get_program_accounts_with_config(
+        &p2swap::id(),
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: None,
+                commitment: None,
+            },
+            with_context: None,
+        },
+    )?;
+
+    Ok(accounts
+        .iter()
+        .map(|(account_pubkey, account)| {
+            (
+                account_pubkey.clone(),
+                try_from_slice_unchecked::<p2swap::state::Quote>(&account.data[8..]).unwrap(),
+            )
+        })
+        .collect())
+}
+
+/// Print rfq.
+pub fn print_rfq(
+    client: &RpcClient,
+    rfq_pubkey: &Pubkey,
+    rfq: &p2swap::state::Rfq,
+) -> Result<(), error::Error> {
+    let base_decimals = if rfq.is_base_native() {
+        9
+    } else {
+        get_mint(&client, &rfq.base_mint)?.decimals
+    };
+
+    println!("pubkey: {}", rfq_pubkey);
+    println!("status: {:?}", rfq.status);
+    println!(
+        "base_amount: {}",
+        spl_token::amount_to_ui_amount(rfq.base_amount, base_decimals)
+    );
+    println!("base_mint: {}", rfq.base_mint);
+    println!("funder: {}", rfq.funder);
+    println!("escrow: {}", rfq.escrow);
+    println!("expire_date: {}", rfq.expire_date);
+    println!("quotes_count: {}", rfq.quotes_count);
+    println!("accepted_quote: {}", rfq.accepted_quote);
+
+    Ok(())
+}
+
+/// Print rfq quote.
+pub fn print_quote(
+    client: &RpcClient,
+    quote_pubkey: &Pubkey,
+    quote: &p2swap::state::Quote,
+) -> Result<(), error::Error> {
+    let decimals = if quote.is_native() {
+        9
+    } else {
+        get_mint(&client, &quote.mint)?.decimals
+    };
+
+    println!("pubkey: {}", quote_pubkey);
+    println!("maker: {}", quote.maker);
+    println!("mint: {}", quote.mint);
+    println!(
+        "amount: {}",
+        spl_token::amount_to_ui_amount(quote.amount, decimals)
+    );
+    println!("expire_date: {}", quote.expire_date);
+    println!("deposit: {}", quote.deposit);
+
+    Ok(())
+}
+
+/// Return p2swap protocol-wide `Stats` state.
+pub fn get_stats(client: &RpcClient, stats: &Pubkey) -> Result<p2swap::state::Stats, error::Error> {
+    let data = client.get_account_data(stats)?;
+
+    let stats = try_from_slice_unchecked::<p2swap::state::Stats>(&data[8..])?;
+
+    Ok(stats)
+}
+
+/// Return p2swap mint pair `PairStats` state.
+pub fn get_pair_stats(
+    client: &RpcClient,
+    pair_stats: &Pubkey,
+) -> Result<p2swap::state::PairStats, error::Error> {
+    let data = client.get_account_data(pair_stats)?;
+
+    let pair_stats = try_from_slice_unchecked::<p2swap::state::PairStats>(&data[8..])?;
+
+    Ok(pair_stats)
+}
+
+/// Print protocol-wide stats.
+pub fn print_stats(stats_pubkey: &Pubkey, stats: &p2swap::state::Stats) {
+    println!("pubkey: {}", stats_pubkey);
+    println!("trades_count: {}", stats.trades_count);
+    println!("pairs_count: {}", stats.pairs_count);
+    println!(
+        "native_volume: {}",
+        stats.native_volume as f64 / 10f64.powi(9)
+    );
+}
+
+/// Print mint pair stats.
+pub fn print_pair_stats(
+    client: &RpcClient,
+    pair_stats_pubkey: &Pubkey,
+    pair_stats: &p2swap::state::PairStats,
+    twap_window: Option<i64>,
+) -> Result<(), error::Error> {
+    let base_decimals = if pair_stats.base_mint == System::id() {
+        9
+    } else {
+        get_mint(&client, &pair_stats.base_mint)?.decimals
+    };
+    let quote_decimals = if pair_stats.quote_mint == System::id() {
+        9
+    } else {
+        get_mint(&client, &pair_stats.quote_mint)?.decimals
+    };
+
+    let last_base_amount =
+        spl_token::amount_to_ui_amount(pair_stats.last_base_amount, base_decimals);
+    let last_quote_amount =
+        spl_token::amount_to_ui_amount(pair_stats.last_quote_amount, quote_decimals);
+
+    println!("pubkey: {}", pair_stats_pubkey);
+    println!("base_mint: {}", pair_stats.base_mint);
+    println!("quote_mint: {}", pair_stats.quote_mint);
+    println!("trades_count: {}", pair_stats.trades_count);
+    println!(
+        "base_volume: {}",
+        pair_stats.base_volume as f64 / 10f64.powi(base_decimals as i32)
+    );
+    println!(
+        "quote_volume: {}",
+        pair_stats.quote_volume as f64 / 10f64.powi(quote_decimals as i32)
+    );
+    println!("last_base_amount: {}", last_base_amount);
+    println!("last_quote_amount: {}", last_quote_amount);
+    if last_base_amount > 0.0 {
+        println!("last_price: {}", last_quote_amount / last_base_amount);
+    }
+    println!("last_traded_at: {}", pair_stats.last_traded_at);
+    println!("observations_count: {}", pair_stats.observations_count);
+    if let Some(twap_window) = twap_window {
+        // TWAP is scaled price of raw amounts, convert it to UI amounts price
+        let twap = pair_stats
+            .twap(Utc::now().timestamp(), twap_window)
+            .map(|twap| {
+                twap as f64 / p2swap::state::PRICE_SCALE as f64
+                    * 10f64.powi(base_decimals as i32 - quote_decimals as i32)
+            });
+        println!("twap: {:?}", twap);
+    }
+
+    Ok(())
+}
+
+/// Return p2swap `Profile` state.
+pub fn get_profile(
+    client: &RpcClient,
+    profile: &Pubkey,
+) -> Result<p2swap::state::Profile, error::Error> {
+    let data = client.get_account_data(profile)?;
+
+    let profile = try_from_slice_unchecked::<p2swap::state::Profile>(&data[8..])?;
+
+    Ok(profile)
+}
+
+/// Print wallet profile.
+pub fn print_profile(profile_pubkey: &Pubkey, profile: &p2swap::state::Profile) {
+    println!("pubkey: {}", profile_pubkey);
+    println!("wallet: {}", profile.wallet);
+    println!("orders_created: {}", profile.orders_created);
+    println!("orders_completed: {}", profile.orders_completed);
+    println!("orders_canceled: {}", profile.orders_canceled);
+    println!("orders_expired: {}", profile.orders_expired);
+
+    for volume in profile.volumes.iter() {
+        println!("volume: {} {}", volume.mint, volume.amount);
+    }
+}
+
+/// Return p2swap `StakingPool` state.
+pub fn get_staking_pool(client: &RpcClient) -> Result<p2swap::state::StakingPool, error::Error> {
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let data = client.get_account_data(&staking_pool)?;
+
+    let staking_pool = try_from_slice_unchecked::<p2swap::state::StakingPool>(&data[8..])?;
+
+    Ok(staking_pool)
+}
+
+/// Return p2swap `Stake` state.
+pub fn get_stake(client: &RpcClient, stake: &Pubkey) -> Result<p2swap::state::Stake, error::Error> {
+    let data = client.get_account_data(stake)?;
+
+    let stake = try_from_slice_unchecked::<p2swap::state::Stake>(&data[8..])?;
+
+    Ok(stake)
+}
+
+/// Print wallet stake with unclaimed rewards per mint.
+pub fn print_stake(
+    stake_pubkey: &Pubkey,
+    stake: &p2swap::state::Stake,
+    staking_pool: &p2swap::state::StakingPool,
+) {
+    let mut stake = stake.clone();
+    stake.settle(staking_pool);
+
+    println!("pubkey: {}", stake_pubkey);
+    println!("wallet: {}", stake.wallet);
+    println!("mint: {}", stake.mint);
+    println!("amount: {}", stake.amount);
+    println!("unstaking_amount: {}", stake.unstaking_amount);
+    println!("unstake_available_at: {}", stake.unstake_available_at);
+
+    for (reward, stake_reward) in staking_pool.rewards.iter().zip(stake.rewards.iter()) {
+        println!("reward: {} {}", reward.mint, stake_reward.pending);
+    }
+}
+
 /// Return `spl_token` `Mint` state.
 pub fn get_mint(client: &RpcClient, mint: &Pubkey) -> Result<spl_token::state::Mint, error::Error> {
     let data = client.get_account_data(mint)?;
     Ok(spl_token::state::Mint::unpack(&data)?)
 }
+
+/// Return `spl_token` `Account` state.
+pub fn get_token_account(
+    client: &RpcClient,
+    token_account: &Pubkey,
+) -> Result<spl_token::state::Account, error::Error> {
+    let data = client.get_account_data(token_account)?;
+    Ok(spl_token::state::Account::unpack(&data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::anchor_lang::Discriminator;
+
+    #[test]
+    fn orders_history_filters_match_order_layout() {
+        let funder = Pubkey::new_unique();
+        let reference = [7u8; 32];
+
+        let mut data = vec![0u8; p2swap::state::Order::LEN];
+        data[..8].copy_from_slice(&p2swap::state::Order::discriminator());
+        data[8] = p2swap::state::OrderStatus::Completed as u8;
+
+        let filters = orders_history_filters(
+            &funder,
+            Some(p2swap::state::OrderStatus::Completed),
+            Some(reference),
+        );
+
+        for filter in &filters {
+            match filter {
+                RpcFilterType::DataSize(size) => assert_eq!(*size as usize, data.len()),
+                RpcFilterType::Memcmp(memcmp) => {
+                    let bytes = memcmp.bytes().unwrap();
+                    data[memcmp.offset..memcmp.offset + bytes.len()].copy_from_slice(&bytes);
+                }
+            }
+        }
+
+        // Filtered bytes land on `funder` and `reference` fields of `Order`
+        let order = p2swap::state::Order::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!({ order.funder }, funder);
+        assert_eq!({ order.reference }, reference);
+        assert_eq!(
+            order.status().unwrap(),
+            p2swap::state::OrderStatus::Completed
+        );
+    }
+}
@@ -41,4 +41,302 @@ pub enum ErrorCode {
     /// 6009.
     #[msg("Recipient should not match funder")]
     RecipientMatchFunder,
+
+    /// 6010.
+    #[msg("Floor quote amount exceed quote amount")]
+    InvalidFloorQuoteAmount,
+
+    /// 6011.
+    #[msg("Commit end date should precede reveal end date")]
+    InvalidAuctionDates,
+
+    /// 6012.
+    #[msg("Auction is not started")]
+    AuctionIsNotStarted,
+
+    /// 6013.
+    #[msg("Bids commit phase is over")]
+    CommitPhaseIsOver,
+
+    /// 6014.
+    #[msg("Bids reveal phase is not active")]
+    RevealPhaseIsNotActive,
+
+    /// 6015.
+    #[msg("Bids reveal phase is not over")]
+    RevealPhaseIsNotOver,
+
+    /// 6016.
+    #[msg("Bid mismatch commitment")]
+    BidCommitmentMismatch,
+
+    /// 6017.
+    #[msg("Bid amount lower than minimum or exceed deposit")]
+    InvalidBidAmount,
+
+    /// 6018.
+    #[msg("Invalid bid status")]
+    InvalidBidStatus,
+
+    /// 6019.
+    #[msg("Invalid auction status")]
+    InvalidAuctionStatus,
+
+    /// 6020.
+    #[msg("Auction has no winner")]
+    AuctionHasNoWinner,
+
+    /// 6021.
+    #[msg("Auction has bids and can't be canceled")]
+    AuctionIsNotCancelable,
+
+    /// 6022.
+    #[msg("Bid is not refundable")]
+    BidIsNotRefundable,
+
+    /// 6023.
+    #[msg("Bidder should not match funder")]
+    BidderMatchFunder,
+
+    /// 6024.
+    #[msg("Bidder account mismatch wallet")]
+    BidderAccountWalletMismatch,
+
+    /// 6025.
+    #[msg("Bid lower than minimum or highest bid with increment")]
+    BidTooLow,
+
+    /// 6026.
+    #[msg("Auction is expired")]
+    AuctionIsExpired,
+
+    /// 6027.
+    #[msg("Auction is not expired")]
+    AuctionIsNotExpired,
+
+    /// 6028.
+    #[msg("Previous bidder account mismatch")]
+    PreviousBidderAccountMismatch,
+
+    /// 6029.
+    #[msg("Invalid rfq status")]
+    InvalidRfqStatus,
+
+    /// 6030.
+    #[msg("Rfq is expired")]
+    RfqIsExpired,
+
+    /// 6031.
+    #[msg("Quote is expired")]
+    QuoteIsExpired,
+
+    /// 6032.
+    #[msg("Quote is not refundable")]
+    QuoteIsNotRefundable,
+
+    /// 6033.
+    #[msg("Maker should not match funder")]
+    MakerMatchFunder,
+
+    /// 6034.
+    #[msg("Maker account mismatch wallet")]
+    MakerAccountWalletMismatch,
+
+    /// 6035.
+    #[msg("Ring swap should have from 3 to 5 legs with distinct participants")]
+    InvalidRingSwapLegs,
+
+    /// 6036.
+    #[msg("Invalid ring swap status")]
+    InvalidRingSwapStatus,
+
+    /// 6037.
+    #[msg("Ring swap is expired")]
+    RingSwapIsExpired,
+
+    /// 6038.
+    #[msg("Ring swap leg mismatch")]
+    RingSwapLegMismatch,
+
+    /// 6039.
+    #[msg("Ring swap leg is already funded")]
+    RingSwapLegIsFunded,
+
+    /// 6040.
+    #[msg("Ring swap is not funded")]
+    RingSwapIsNotFunded,
+
+    /// 6041.
+    #[msg("Ring swap leg is not refundable")]
+    RingSwapLegIsNotRefundable,
+
+    /// 6042.
+    #[msg("Participant account mismatch wallet")]
+    ParticipantAccountWalletMismatch,
+
+    /// 6043.
+    #[msg("Orders mints mismatch")]
+    OrdersMintMismatch,
+
+    /// 6044.
+    #[msg("Orders prices do not cross")]
+    OrdersDoNotCross,
+
+    /// 6045.
+    #[msg("Order can't be matched with counter order")]
+    OrderIsNotMatchable,
+
+    /// 6046.
+    #[msg("Matcher account mismatch wallet")]
+    MatcherAccountWalletMismatch,
+
+    /// 6047.
+    #[msg("Invalid protocol fee")]
+    InvalidProtocolFee,
+
+    /// 6048.
+    #[msg("Invalid referral share")]
+    InvalidReferralShare,
+
+    /// 6049.
+    #[msg("Treasury account mismatch protocol treasury")]
+    TreasuryAccountMismatch,
+
+    /// 6050.
+    #[msg("Referrer mismatch")]
+    ReferrerMismatch,
+
+    /// 6051.
+    #[msg("Referrer account mismatch wallet")]
+    ReferrerAccountWalletMismatch,
+
+    /// 6052.
+    #[msg("Invalid price curve")]
+    InvalidPriceCurve,
+
+    /// 6053.
+    #[msg("Collection offer should have non-native quote mint and funder wallet as quote account")]
+    InvalidCollectionOffer,
+
+    /// 6054.
+    #[msg("Invalid token metadata")]
+    InvalidTokenMetadata,
+
+    /// 6055.
+    #[msg("Mint is not verified member of collection")]
+    NftIsNotCollectionMember,
+
+    /// 6056.
+    #[msg("Order can't be canceled until cancel lock date")]
+    CancelIsLocked,
+
+    /// 6057.
+    #[msg("Cancel lock date should be in the future and not after expire date")]
+    InvalidCancelLockDate,
+
+    /// 6058.
+    #[msg("Hash-time-locked order requires cancel lock date (timelock)")]
+    InvalidHtlcOrder,
+
+    /// 6059.
+    #[msg("Preimage mismatch order hashlock")]
+    InvalidPreimage,
+
+    /// 6060.
+    #[msg(
+        "Arbitrated order requires named recipient, distinct arbiter and positive dispute window"
+    )]
+    InvalidArbitratedOrder,
+
+    /// 6061.
+    #[msg("Arbitrated order can be settled by release or arbiter decision only")]
+    OrderIsArbitrated,
+
+    /// 6062.
+    #[msg("Order is not arbitrated")]
+    OrderIsNotArbitrated,
+
+    /// 6063.
+    #[msg("Dispute window is closed")]
+    DisputeWindowIsClosed,
+
+    /// 6064.
+    #[msg("Dispute window is open")]
+    DisputeWindowIsOpen,
+
+    /// 6065.
+    #[msg("Release authority should be funder or recipient")]
+    InvalidReleaseAuthority,
+
+    /// 6066.
+    #[msg("Memo is too long")]
+    MemoIsTooLong,
+
+    /// 6067.
+    #[msg("Batch doesn't match remaining accounts")]
+    InvalidBatch,
+
+    /// 6068.
+    #[msg("Fee tiers should be sorted and shouldn't exceed protocol fee")]
+    InvalidFeeTiers,
+
+    /// 6069.
+    #[msg("Discount token account doesn't match wallet or discount mint")]
+    DiscountAccountMismatch,
+
+    /// 6070.
+    #[msg("Stake mint doesn't match protocol discount mint")]
+    StakeMintMismatch,
+
+    /// 6071.
+    #[msg("Stake amount is insufficient")]
+    InsufficientStake,
+
+    /// 6072.
+    #[msg("Unstaked amount is in cooldown")]
+    StakeIsInCooldown,
+
+    /// 6073.
+    #[msg("Staking pool has no stake to distribute rewards")]
+    StakingPoolIsEmpty,
+
+    /// 6074.
+    #[msg("Staking pool reward mints limit is reached")]
+    TooManyRewardMints,
+
+    /// 6075.
+    #[msg("Reward mint isn't distributed by staking pool")]
+    InvalidRewardMint,
+
+    /// 6076.
+    #[msg("Staker account mismatch wallet")]
+    StakerAccountWalletMismatch,
+
+    /// 6077.
+    #[msg("Depositor account mismatch wallet")]
+    DepositorAccountWalletMismatch,
+
+    /// 6078.
+    #[msg("Hidden order should have System recipient")]
+    InvalidHiddenOrder,
+
+    /// 6079.
+    #[msg("Recipient mismatch order commitment")]
+    RecipientCommitmentMismatch,
+
+    /// 6080.
+    #[msg("Close authority should be funder or rent payer")]
+    InvalidCloseAuthority,
+
+    /// 6081.
+    #[msg("Token gate should have mint and positive minimum amount or collection")]
+    InvalidGate,
+
+    /// 6082.
+    #[msg("Executing wallet doesn't pass order token gate")]
+    GateIsNotPassed,
+
+    /// 6083.
+    #[msg("Order book is full, expired orders should be pruned")]
+    OrderBookIsFull,
 }
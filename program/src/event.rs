@@ -0,0 +1,86 @@
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+/// `Order` was created.
+#[event]
+pub struct OrderCreated {
+    pub order: Pubkey,
+    pub funder: Pubkey,
+    pub recipient: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub reference: [u8; 32],
+    pub memo: String,
+}
+
+/// `Order` was executed by `recipient` or matched with counter `Order`
+/// (`recipient` is counter `Order` funder).
+#[event]
+pub struct OrderExecuted {
+    pub order: Pubkey,
+    pub recipient: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub reference: [u8; 32],
+}
+
+/// `Order` was canceled by `funder`.
+#[event]
+pub struct OrderCanceled {
+    pub order: Pubkey,
+    pub reference: [u8; 32],
+}
+
+/// `Order` was declined by `recipient`.
+#[event]
+pub struct OrderDeclined {
+    pub order: Pubkey,
+    pub reference: [u8; 32],
+}
+
+/// Finished `Order` account was closed, rent was refunded to `rent_payer`.
+#[event]
+pub struct OrderClosed {
+    pub order: Pubkey,
+    pub rent_payer: Pubkey,
+    pub reference: [u8; 32],
+}
+
+/// `recipient` claimed delivery of arbitrated `Order` off-chain leg.
+#[event]
+pub struct DeliveryClaimed {
+    pub order: Pubkey,
+    pub recipient: Pubkey,
+    pub claimed_at: UnixTimestamp,
+    pub reference: [u8; 32],
+}
+
+/// `funder` disputed claimed delivery, arbiter should resolve it.
+#[event]
+pub struct OrderDisputed {
+    pub order: Pubkey,
+    pub funder: Pubkey,
+    pub arbiter: Pubkey,
+    pub disputed_at: UnixTimestamp,
+    pub reference: [u8; 32],
+}
+
+/// `escrow` of arbitrated `Order` was released to `recipient` by `authority`
+/// (`funder` or `recipient` after dispute window).
+#[event]
+pub struct OrderReleased {
+    pub order: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub reference: [u8; 32],
+}
+
+/// Arbiter decided who gets `escrow` of disputed `Order`.
+#[event]
+pub struct DisputeResolved {
+    pub order: Pubkey,
+    pub arbiter: Pubkey,
+    pub released: bool,
+    pub reference: [u8; 32],
+}
@@ -0,0 +1,451 @@
+//! Typed helpers to invoke `p2swap` instructions from other programs (CPI).
+//!
+//! `Order`, `funder` and `recipient` can be PDA's of the calling program,
+//! in this case their seeds should be passed in `signer_seeds`.
+//! PDA, that pays rent or sends native `SOL`'s, should be owned by system program.
+
+use crate::{state, utils};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        clock::UnixTimestamp,
+        instruction::{AccountMeta, Instruction},
+        program::{get_return_data, invoke, invoke_signed},
+    },
+    InstructionData,
+};
+
+/// `CreateOrder` accounts.
+pub struct CreateOrder<'info> {
+    pub order: AccountInfo<'info>,
+    pub funder: AccountInfo<'info>,
+    /// Pays `Order` rent (should be `funder` if creation isn't sponsored).
+    pub rent_payer: AccountInfo<'info>,
+    pub recipient: AccountInfo<'info>,
+    pub funder_token_account: AccountInfo<'info>,
+    pub escrow: AccountInfo<'info>,
+    pub quote_token_account: AccountInfo<'info>,
+    pub escrow_mint: AccountInfo<'info>,
+    pub quote_mint: AccountInfo<'info>,
+    pub order_book: AccountInfo<'info>,
+    pub funder_profile: AccountInfo<'info>,
+    pub protocol: AccountInfo<'info>,
+    pub rent_sysvar: AccountInfo<'info>,
+    pub clock_sysvar: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    /// `Referrer` of `escrow_mint`.
+    pub referrer: Option<AccountInfo<'info>>,
+}
+
+/// `CreateOrder` arguments (bump seeds are derived by helper).
+pub struct CreateOrderArgs {
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub start_date: Option<UnixTimestamp>,
+    pub expire_date: UnixTimestamp,
+    pub price_curve: state::PriceCurve,
+    pub floor_quote_amount: u64,
+    pub referral_share_bps: u16,
+    pub collection_offer: bool,
+    pub cancel_locked_until: Option<UnixTimestamp>,
+    pub hashlock: Option<[u8; 32]>,
+    pub arbitration: Option<state::ArbitrationArgs>,
+    pub memo: Option<String>,
+    pub reference: Option<[u8; 32]>,
+    pub recipient_commitment: Option<[u8; 32]>,
+    pub gate: Option<state::Gate>,
+}
+
+/// `CancelOrder` accounts.
+pub struct CancelOrder<'info> {
+    pub order: AccountInfo<'info>,
+    pub funder: AccountInfo<'info>,
+    pub rent_payer: AccountInfo<'info>,
+    pub funder_token_account: AccountInfo<'info>,
+    pub escrow: AccountInfo<'info>,
+    pub order_book: AccountInfo<'info>,
+    pub funder_profile: AccountInfo<'info>,
+    pub clock_sysvar: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// `DeclineOrder` accounts.
+pub struct DeclineOrder<'info> {
+    pub order: AccountInfo<'info>,
+    pub funder: AccountInfo<'info>,
+    pub rent_payer: AccountInfo<'info>,
+    pub recipient: AccountInfo<'info>,
+    pub funder_token_account: AccountInfo<'info>,
+    pub escrow: AccountInfo<'info>,
+    pub order_book: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// `ExecuteOrder` accounts.
+pub struct ExecuteOrder<'info> {
+    pub order: AccountInfo<'info>,
+    pub funder: AccountInfo<'info>,
+    pub rent_payer: AccountInfo<'info>,
+    pub recipient: AccountInfo<'info>,
+    pub recipient_token_account: AccountInfo<'info>,
+    pub recipient_receive_token_account: AccountInfo<'info>,
+    pub escrow: AccountInfo<'info>,
+    pub quote_token_account: AccountInfo<'info>,
+    pub order_book: AccountInfo<'info>,
+    pub funder_profile: AccountInfo<'info>,
+    pub recipient_profile: AccountInfo<'info>,
+    pub stats: AccountInfo<'info>,
+    pub pair_stats: AccountInfo<'info>,
+    pub protocol: AccountInfo<'info>,
+    pub treasury_base_token_account: AccountInfo<'info>,
+    pub treasury_quote_token_account: AccountInfo<'info>,
+    pub clock_sysvar: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    /// `recipient` token account of `Order::gate_mint` and NFT metadata, if gate is collection
+    /// (required if `Order::is_gated`).
+    pub gate: Option<(AccountInfo<'info>, Option<AccountInfo<'info>>)>,
+    /// NFT metadata and `funder` NFT token account (required if `Order::is_collection_offer`).
+    pub collection_nft: Option<(AccountInfo<'info>, AccountInfo<'info>)>,
+    /// `Order` `Referrer` and its token account (required if `Order` has referrer).
+    pub maker_referrer: Option<(AccountInfo<'info>, AccountInfo<'info>)>,
+    /// `Referrer` of quote mint and its token account (required if `taker_referral_share_bps` > 0).
+    pub taker_referrer: Option<(AccountInfo<'info>, AccountInfo<'info>)>,
+    /// `recipient` token account of `Protocol::discount_mint` (optional fee discount).
+    pub discount_token_account: Option<AccountInfo<'info>>,
+}
+
+/// Invoke `create_order` instruction.
+pub fn create_order<'info>(
+    p2swap_program: &AccountInfo<'info>,
+    accounts: CreateOrder<'info>,
+    args: CreateOrderArgs,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let (_, escrow_bump) =
+        utils::find_order_escrow_address(accounts.funder.key, accounts.order.key);
+
+    let mut account_metas = crate::accounts::CreateOrder {
+        order: accounts.order.key(),
+        funder: accounts.funder.key(),
+        rent_payer: accounts.rent_payer.key(),
+        recipient: accounts.recipient.key(),
+        funder_token_account: accounts.funder_token_account.key(),
+        escrow: accounts.escrow.key(),
+        quote_token_account: accounts.quote_token_account.key(),
+        escrow_mint: accounts.escrow_mint.key(),
+        quote_mint: accounts.quote_mint.key(),
+        order_book: accounts.order_book.key(),
+        funder_profile: accounts.funder_profile.key(),
+        protocol: accounts.protocol.key(),
+        rent_sysvar: accounts.rent_sysvar.key(),
+        clock_sysvar: accounts.clock_sysvar.key(),
+        token_program: accounts.token_program.key(),
+        system_program: accounts.system_program.key(),
+    }
+    .to_account_metas(None);
+
+    let mut account_infos = vec![
+        accounts.order,
+        accounts.funder,
+        accounts.rent_payer,
+        accounts.recipient,
+        accounts.funder_token_account,
+        accounts.escrow,
+        accounts.quote_token_account,
+        accounts.escrow_mint,
+        accounts.quote_mint,
+        accounts.order_book,
+        accounts.funder_profile,
+        accounts.protocol,
+        accounts.rent_sysvar,
+        accounts.clock_sysvar,
+        accounts.token_program,
+        accounts.system_program,
+        p2swap_program.clone(),
+    ];
+
+    if let Some(referrer) = accounts.referrer {
+        account_metas.push(AccountMeta::new_readonly(referrer.key(), false));
+        account_infos.push(referrer);
+    }
+
+    let instruction = Instruction {
+        program_id: crate::id(),
+        accounts: account_metas,
+        data: crate::instruction::CreateOrder {
+            escrow_bump,
+            base_amount: args.base_amount,
+            quote_amount: args.quote_amount,
+            start_date: args.start_date,
+            expire_date: args.expire_date,
+            price_curve: args.price_curve,
+            floor_quote_amount: args.floor_quote_amount,
+            referral_share_bps: args.referral_share_bps,
+            collection_offer: args.collection_offer,
+            cancel_locked_until: args.cancel_locked_until,
+            hashlock: args.hashlock,
+            arbitration: args.arbitration,
+            memo: args.memo,
+            reference: args.reference,
+            recipient_commitment: args.recipient_commitment,
+            gate: args.gate,
+        }
+        .data(),
+    };
+
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+
+    Ok(())
+}
+
+/// Invoke `cancel_order` instruction.
+pub fn cancel_order<'info>(
+    p2swap_program: &AccountInfo<'info>,
+    accounts: CancelOrder<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let (_, escrow_bump) =
+        utils::find_order_escrow_address(accounts.funder.key, accounts.order.key);
+
+    let instruction = Instruction {
+        program_id: crate::id(),
+        accounts: crate::accounts::CancelOrder {
+            order: accounts.order.key(),
+            funder: accounts.funder.key(),
+            rent_payer: accounts.rent_payer.key(),
+            funder_token_account: accounts.funder_token_account.key(),
+            escrow: accounts.escrow.key(),
+            order_book: accounts.order_book.key(),
+            funder_profile: accounts.funder_profile.key(),
+            clock_sysvar: accounts.clock_sysvar.key(),
+            token_program: accounts.token_program.key(),
+            system_program: accounts.system_program.key(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::CancelOrder { escrow_bump }.data(),
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.order,
+            accounts.funder,
+            accounts.rent_payer,
+            accounts.funder_token_account,
+            accounts.escrow,
+            accounts.order_book,
+            accounts.funder_profile,
+            accounts.clock_sysvar,
+            accounts.token_program,
+            accounts.system_program,
+            p2swap_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Invoke `decline_order` instruction.
+pub fn decline_order<'info>(
+    p2swap_program: &AccountInfo<'info>,
+    accounts: DeclineOrder<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let (_, escrow_bump) =
+        utils::find_order_escrow_address(accounts.funder.key, accounts.order.key);
+
+    let instruction = Instruction {
+        program_id: crate::id(),
+        accounts: crate::accounts::DeclineOrder {
+            order: accounts.order.key(),
+            funder: accounts.funder.key(),
+            rent_payer: accounts.rent_payer.key(),
+            recipient: accounts.recipient.key(),
+            funder_token_account: accounts.funder_token_account.key(),
+            escrow: accounts.escrow.key(),
+            order_book: accounts.order_book.key(),
+            token_program: accounts.token_program.key(),
+            system_program: accounts.system_program.key(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::DeclineOrder { escrow_bump }.data(),
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.order,
+            accounts.funder,
+            accounts.rent_payer,
+            accounts.recipient,
+            accounts.funder_token_account,
+            accounts.escrow,
+            accounts.order_book,
+            accounts.token_program,
+            accounts.system_program,
+            p2swap_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Invoke `execute_order` instruction.
+pub fn execute_order<'info>(
+    p2swap_program: &AccountInfo<'info>,
+    accounts: ExecuteOrder<'info>,
+    taker_referral_share_bps: u16,
+    preimage: Option<[u8; 32]>,
+    recipient_salt: Option<[u8; 32]>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let (_, escrow_bump) =
+        utils::find_order_escrow_address(accounts.funder.key, accounts.order.key);
+
+    let mut account_metas = crate::accounts::ExecuteOrder {
+        order: accounts.order.key(),
+        funder: accounts.funder.key(),
+        rent_payer: accounts.rent_payer.key(),
+        recipient: accounts.recipient.key(),
+        recipient_token_account: accounts.recipient_token_account.key(),
+        recipient_receive_token_account: accounts.recipient_receive_token_account.key(),
+        escrow: accounts.escrow.key(),
+        quote_token_account: accounts.quote_token_account.key(),
+        order_book: accounts.order_book.key(),
+        funder_profile: accounts.funder_profile.key(),
+        recipient_profile: accounts.recipient_profile.key(),
+        stats: accounts.stats.key(),
+        pair_stats: accounts.pair_stats.key(),
+        protocol: accounts.protocol.key(),
+        treasury_base_token_account: accounts.treasury_base_token_account.key(),
+        treasury_quote_token_account: accounts.treasury_quote_token_account.key(),
+        clock_sysvar: accounts.clock_sysvar.key(),
+        token_program: accounts.token_program.key(),
+        system_program: accounts.system_program.key(),
+    }
+    .to_account_metas(None);
+
+    let mut account_infos = vec![
+        accounts.order,
+        accounts.funder,
+        accounts.rent_payer,
+        accounts.recipient,
+        accounts.recipient_token_account,
+        accounts.recipient_receive_token_account,
+        accounts.escrow,
+        accounts.quote_token_account,
+        accounts.order_book,
+        accounts.funder_profile,
+        accounts.recipient_profile,
+        accounts.stats,
+        accounts.pair_stats,
+        accounts.protocol,
+        accounts.treasury_base_token_account,
+        accounts.treasury_quote_token_account,
+        accounts.clock_sysvar,
+        accounts.token_program,
+        accounts.system_program,
+        p2swap_program.clone(),
+    ];
+
+    if let Some((gate_token_account, gate_metadata)) = accounts.gate {
+        account_metas.push(AccountMeta::new_readonly(gate_token_account.key(), false));
+        account_infos.push(gate_token_account);
+
+        if let Some(gate_metadata) = gate_metadata {
+            account_metas.push(AccountMeta::new_readonly(gate_metadata.key(), false));
+            account_infos.push(gate_metadata);
+        }
+    }
+
+    if let Some((metadata, funder_nft_token_account)) = accounts.collection_nft {
+        account_metas.push(AccountMeta::new_readonly(metadata.key(), false));
+        account_metas.push(AccountMeta::new(funder_nft_token_account.key(), false));
+        account_infos.push(metadata);
+        account_infos.push(funder_nft_token_account);
+    }
+
+    for (referrer, referrer_token_account) in [accounts.maker_referrer, accounts.taker_referrer]
+        .into_iter()
+        .flatten()
+    {
+        account_metas.push(AccountMeta::new(referrer.key(), false));
+        account_metas.push(AccountMeta::new(referrer_token_account.key(), false));
+        account_infos.push(referrer);
+        account_infos.push(referrer_token_account);
+    }
+
+    if let Some(discount_token_account) = accounts.discount_token_account {
+        account_metas.push(AccountMeta::new_readonly(
+            discount_token_account.key(),
+            false,
+        ));
+        account_infos.push(discount_token_account);
+    }
+
+    let instruction = Instruction {
+        program_id: crate::id(),
+        accounts: account_metas,
+        data: crate::instruction::ExecuteOrder {
+            escrow_bump,
+            taker_referral_share_bps,
+            preimage,
+            recipient_salt,
+        }
+        .data(),
+    };
+
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+
+    Ok(())
+}
+
+/// `GetTwap` accounts.
+pub struct GetTwap<'info> {
+    pub pair_stats: AccountInfo<'info>,
+    pub clock_sysvar: AccountInfo<'info>,
+}
+
+/// Invoke `get_twap` instruction and return time-weighted average price of mint pair
+/// over `window` seconds (scaled by `state::PRICE_SCALE`).
+pub fn get_twap<'info>(
+    p2swap_program: &AccountInfo<'info>,
+    accounts: GetTwap<'info>,
+    window: i64,
+) -> Result<Option<u128>> {
+    let instruction = Instruction {
+        program_id: crate::id(),
+        accounts: crate::accounts::GetTwap {
+            pair_stats: accounts.pair_stats.key(),
+            clock_sysvar: accounts.clock_sysvar.key(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::GetTwap { window }.data(),
+    };
+
+    invoke(
+        &instruction,
+        &[
+            accounts.pair_stats,
+            accounts.clock_sysvar,
+            p2swap_program.clone(),
+        ],
+    )?;
+
+    let (program_id, mut data) = get_return_data().ok_or(ProgramError::InvalidAccountData)?;
+
+    if program_id != crate::id() {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+
+    // Trailing zero bytes of return data are truncated
+    data.resize(1 + 16, 0);
+
+    Ok(Option::<u128>::deserialize(&mut data.as_slice())?)
+}
@@ -1,10 +1,13 @@
 pub mod error;
+pub mod event;
+pub mod interface;
 mod processor;
 pub mod state;
 pub mod utils;
 
 use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
 use anchor_spl::token::Token;
+use state::{ArbitrationArgs, BatchOrderArgs, FeeTier, Gate, PriceCurve, RingSwapLegArgs};
 
 declare_id!("p2sSQ51hNP1yiQKtZ81sDzWBM6tkaS2ZtMrgnbBhE4v");
 
@@ -12,20 +15,45 @@ declare_id!("p2sSQ51hNP1yiQKtZ81sDzWBM6tkaS2ZtMrgnbBhE4v");
 mod p2swap {
     use super::*;
 
-    pub fn create_order(
-        ctx: Context<CreateOrder>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateOrder<'info>>,
         escrow_bump: u8,
         base_amount: u64,
         quote_amount: u64,
         start_date: Option<UnixTimestamp>,
         expire_date: UnixTimestamp,
+        price_curve: PriceCurve,
+        floor_quote_amount: u64,
+        referral_share_bps: u16,
+        collection_offer: bool,
+        cancel_locked_until: Option<UnixTimestamp>,
+        hashlock: Option<[u8; 32]>,
+        arbitration: Option<ArbitrationArgs>,
+        memo: Option<String>,
+        reference: Option<[u8; 32]>,
+        recipient_commitment: Option<[u8; 32]>,
+        gate: Option<Gate>,
     ) -> Result<()> {
         ctx.accounts.process(
             escrow_bump,
+            *ctx.bumps.get("funder_profile").unwrap(),
             base_amount,
             quote_amount,
             start_date,
             expire_date,
+            price_curve,
+            floor_quote_amount,
+            referral_share_bps,
+            collection_offer,
+            cancel_locked_until,
+            hashlock,
+            arbitration,
+            memo,
+            reference,
+            recipient_commitment,
+            gate,
+            ctx.remaining_accounts,
         )
     }
 
@@ -33,9 +61,321 @@ mod p2swap {
         ctx.accounts.process(escrow_bump)
     }
 
-    pub fn execute_order(ctx: Context<ExecuteOrder>, escrow_bump: u8) -> Result<()> {
+    pub fn close_order(ctx: Context<CloseOrder>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn batch_create_orders<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchCreateOrders<'info>>,
+        orders: Vec<BatchOrderArgs>,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            *ctx.bumps.get("funder_profile").unwrap(),
+            orders,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn batch_cancel_orders<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchCancelOrders<'info>>,
+        escrow_bumps: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.process(escrow_bumps, ctx.remaining_accounts)
+    }
+
+    pub fn prune_order_book<'info>(
+        ctx: Context<'_, '_, '_, 'info, PruneOrderBook<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn decline_order(ctx: Context<DeclineOrder>, escrow_bump: u8) -> Result<()> {
+        ctx.accounts.process(escrow_bump)
+    }
+
+    pub fn claim_delivery(ctx: Context<ClaimDelivery>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn dispute_order(ctx: Context<DisputeOrder>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn release_order(ctx: Context<ReleaseOrder>, escrow_bump: u8) -> Result<()> {
+        ctx.accounts.process(escrow_bump)
+    }
+
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        escrow_bump: u8,
+        release: bool,
+    ) -> Result<()> {
+        ctx.accounts.process(escrow_bump, release)
+    }
+
+    pub fn execute_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteOrder<'info>>,
+        escrow_bump: u8,
+        taker_referral_share_bps: u16,
+        preimage: Option<[u8; 32]>,
+        recipient_salt: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            escrow_bump,
+            *ctx.bumps.get("recipient_profile").unwrap(),
+            *ctx.bumps.get("stats").unwrap(),
+            *ctx.bumps.get("pair_stats").unwrap(),
+            taker_referral_share_bps,
+            preimage,
+            recipient_salt,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn match_orders<'info>(
+        ctx: Context<'_, '_, '_, 'info, MatchOrders<'info>>,
+        escrow_a_bump: u8,
+        escrow_b_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            escrow_a_bump,
+            escrow_b_bump,
+            *ctx.bumps.get("stats").unwrap(),
+            *ctx.bumps.get("pair_stats_a").unwrap(),
+            *ctx.bumps.get("pair_stats_b").unwrap(),
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap>, window: i64) -> Result<()> {
+        ctx.accounts.process(window)
+    }
+
+    pub fn init_protocol(
+        ctx: Context<InitProtocol>,
+        fee_bps: u16,
+        max_referral_share_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            *ctx.bumps.get("protocol").unwrap(),
+            fee_bps,
+            max_referral_share_bps,
+        )
+    }
+
+    pub fn update_protocol(
+        ctx: Context<UpdateProtocol>,
+        fee_bps: u16,
+        max_referral_share_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.process(fee_bps, max_referral_share_bps)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        ctx.accounts.process(fee_tiers)
+    }
+
+    pub fn init_staking_pool(
+        ctx: Context<InitStakingPool>,
+        stake_vault_bump: u8,
+        cooldown: i64,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            *ctx.bumps.get("staking_pool").unwrap(),
+            stake_vault_bump,
+            cooldown,
+        )
+    }
+
+    pub fn stake_tokens(
+        ctx: Context<StakeTokens>,
+        stake_vault_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(*ctx.bumps.get("stake").unwrap(), stake_vault_bump, amount)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, stake_vault_bump: u8, amount: u64) -> Result<()> {
+        ctx.accounts.process(stake_vault_bump, amount)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, stake_vault_bump: u8) -> Result<()> {
+        ctx.accounts.process(stake_vault_bump)
+    }
+
+    pub fn deposit_rewards(
+        ctx: Context<DepositRewards>,
+        reward_vault_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.process(reward_vault_bump, amount)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, reward_vault_bump: u8) -> Result<()> {
+        ctx.accounts.process(reward_vault_bump)
+    }
+
+    pub fn create_referrer(ctx: Context<CreateReferrer>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        escrow_bump: u8,
+        base_amount: u64,
+        min_bid_amount: u64,
+        start_date: Option<UnixTimestamp>,
+        commit_end_date: UnixTimestamp,
+        reveal_end_date: UnixTimestamp,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            escrow_bump,
+            base_amount,
+            min_bid_amount,
+            start_date,
+            commit_end_date,
+            reveal_end_date,
+        )
+    }
+
+    pub fn commit_bid(
+        ctx: Context<CommitBid>,
+        deposit_bump: u8,
+        commitment: [u8; 32],
+        deposit_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .process(deposit_bump, commitment, deposit_amount)
+    }
+
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        ctx.accounts.process(amount, salt)
+    }
+
+    pub fn settle_auction(
+        ctx: Context<SettleAuction>,
+        escrow_bump: u8,
+        deposit_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(escrow_bump, deposit_bump)
+    }
+
+    pub fn refund_bid(ctx: Context<RefundBid>, deposit_bump: u8) -> Result<()> {
+        ctx.accounts.process(deposit_bump)
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>, escrow_bump: u8) -> Result<()> {
+        ctx.accounts.process(escrow_bump)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_english_auction(
+        ctx: Context<CreateEnglishAuction>,
+        escrow_bump: u8,
+        bid_escrow_bump: u8,
+        base_amount: u64,
+        min_bid_amount: u64,
+        min_bid_increment: u64,
+        start_date: Option<UnixTimestamp>,
+        expire_date: UnixTimestamp,
+        extension_window: i64,
+    ) -> Result<()> {
+        ctx.accounts.process(
+            escrow_bump,
+            bid_escrow_bump,
+            base_amount,
+            min_bid_amount,
+            min_bid_increment,
+            start_date,
+            expire_date,
+            extension_window,
+        )
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    pub fn refund_english_bid(ctx: Context<RefundEnglishBid>, bid_escrow_bump: u8) -> Result<()> {
+        ctx.accounts.process(bid_escrow_bump)
+    }
+
+    pub fn settle_english_auction(
+        ctx: Context<SettleEnglishAuction>,
+        escrow_bump: u8,
+        bid_escrow_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(escrow_bump, bid_escrow_bump)
+    }
+
+    pub fn cancel_english_auction(
+        ctx: Context<CancelEnglishAuction>,
+        escrow_bump: u8,
+        bid_escrow_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(escrow_bump, bid_escrow_bump)
+    }
+
+    pub fn create_rfq(
+        ctx: Context<CreateRfq>,
+        escrow_bump: u8,
+        base_amount: u64,
+        expire_date: UnixTimestamp,
+    ) -> Result<()> {
+        ctx.accounts.process(escrow_bump, base_amount, expire_date)
+    }
+
+    pub fn submit_quote(
+        ctx: Context<SubmitQuote>,
+        deposit_bump: u8,
+        amount: u64,
+        expire_date: UnixTimestamp,
+    ) -> Result<()> {
+        ctx.accounts.process(deposit_bump, amount, expire_date)
+    }
+
+    pub fn accept_quote(
+        ctx: Context<AcceptQuote>,
+        escrow_bump: u8,
+        deposit_bump: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(escrow_bump, deposit_bump)
+    }
+
+    pub fn refund_quote(ctx: Context<RefundQuote>, deposit_bump: u8) -> Result<()> {
+        ctx.accounts.process(deposit_bump)
+    }
+
+    pub fn cancel_rfq(ctx: Context<CancelRfq>, escrow_bump: u8) -> Result<()> {
         ctx.accounts.process(escrow_bump)
     }
+
+    pub fn create_ring_swap(
+        ctx: Context<CreateRingSwap>,
+        legs: Vec<RingSwapLegArgs>,
+        expire_date: UnixTimestamp,
+    ) -> Result<()> {
+        ctx.accounts.process(legs, expire_date)
+    }
+
+    pub fn deposit_ring_swap_leg(
+        ctx: Context<DepositRingSwapLeg>,
+        deposit_bump: u8,
+        leg_index: u8,
+    ) -> Result<()> {
+        ctx.accounts.process(deposit_bump, leg_index)
+    }
+
+    pub fn settle_ring_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleRingSwap<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn refund_ring_swap_leg(ctx: Context<RefundRingSwapLeg>, leg_index: u8) -> Result<()> {
+        ctx.accounts.process(leg_index)
+    }
 }
 
 /// Perform p2p swap `Order` creation and initialization.
@@ -47,16 +387,54 @@ mod p2swap {
 /// `start_date` - the date from which payments will be accepted.
 ///
 /// `expire_date` - the date from which `Order` will be expired.
+///
+/// `price_curve` - how quantity of tokens, that `recipient` will give, changes over time.
+///
+/// `floor_quote_amount` - lowest quantity of tokens, that `recipient` will give (ignored if `PriceCurve::Fixed`).
+///
+/// `referral_share_bps` - share of protocol fee on `funder` side, that will be paid to referrer
+/// (up to `Protocol::max_referral_share_bps`).
+///
+/// `collection_offer` - `quote_mint` is verified collection, any NFT of which `funder` accepts
+/// (`quote_token_account` should be `funder` wallet, NFT token account is passed on execution).
+///
+/// `cancel_locked_until` - the date until which `funder` can't cancel `Order`.
+///
+/// `hashlock` - SHA-256 hash of secret, that `recipient` should reveal to execute `Order`
+/// (hash-time-locked `Order`, `cancel_locked_until` is required and acts as timelock).
+///
+/// `arbitration` - arbiter and dispute window of `Order` with off-chain leg
+/// (settled by `release_order` or `resolve_dispute` instead of `execute_order`).
+///
+/// `memo` - UTF-8 note up to `ORDER_MEMO_LEN` bytes.
+///
+/// `reference` - client reference (e.g. off-chain deal ID), included in events.
+///
+/// `recipient_commitment` - `utils::hash_recipient` of recipient and salt, that hides recipient
+/// until execution (`recipient` should be `System::id()`).
+///
+/// `gate` - token gate, that executing wallet should pass. Open (`recipient` is `System::id()`)
+/// token-gated `Order` can be executed by any wallet, that passes gate.
+///
+/// Optional remaining accounts: [`Referrer` of `escrow_mint`].
 #[derive(Accounts)]
-#[instruction(escrow_bump: u8, base_amount: u64, quote_amount: u64, start_date: Option<UnixTimestamp>, expire_date: UnixTimestamp)]
+#[instruction(escrow_bump: u8, base_amount: u64, quote_amount: u64, start_date: Option<UnixTimestamp>, expire_date: UnixTimestamp, price_curve: PriceCurve, floor_quote_amount: u64, referral_share_bps: u16, collection_offer: bool, cancel_locked_until: Option<UnixTimestamp>, hashlock: Option<[u8; 32]>, arbitration: Option<ArbitrationArgs>, memo: Option<String>, reference: Option<[u8; 32]>, recipient_commitment: Option<[u8; 32]>, gate: Option<Gate>)]
 pub struct CreateOrder<'info> {
-    #[account(init, space=state::Order::LEN, payer=funder)]
-    order: Box<Account<'info, state::Order>>,
+    /// New account, can be PDA of calling program (signed over CPI).
+    #[account(init, space=state::Order::LEN, payer=rent_payer)]
+    order: AccountLoader<'info, state::Order>,
 
     /// Funder represent `Order` initiator(creator).
+    /// Can be PDA of calling program (system-owned, if it sends native `SOL`'s).
     #[account(mut)]
     funder: Signer<'info>,
 
+    /// Pays rent of `order`, `escrow` and created PDA's, receives `order` and `escrow` rent
+    /// back when they are closed. Should be `funder` if creation isn't sponsored.
+    /// Can be PDA of calling program (system-owned, because it pays rent).
+    #[account(mut)]
+    rent_payer: Signer<'info>,
+
     /// Recipient represent `Order` participant(who will accept offer).
     recipient: UncheckedAccount<'info>,
 
@@ -84,6 +462,24 @@ pub struct CreateOrder<'info> {
     /// If quote tokens are native `SOL`'s, then this field should eq to `System::id()`.
     quote_mint: UncheckedAccount<'info>,
 
+    /// Index of active `Order`'s for mint pair.
+    ///
+    /// PDA: [ORDER_BOOK_PREFIX, escrow_mint_pubkey, quote_mint_pubkey].
+    #[account(init_if_needed, space=state::OrderBook::LEN, payer=rent_payer, seeds = [utils::ORDER_BOOK_PREFIX.as_bytes(), escrow_mint.key().as_ref(), quote_mint.key().as_ref()], bump)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    /// Trading statistics of `funder`.
+    ///
+    /// PDA: [PROFILE_PREFIX, funder_pubkey].
+    #[account(init_if_needed, space=state::Profile::LEN, payer=rent_payer, seeds = [utils::PROFILE_PREFIX.as_bytes(), funder.key().as_ref()], bump)]
+    funder_profile: Box<Account<'info, state::Profile>>,
+
+    /// `Protocol` configuration, that caps `referral_share_bps`.
+    ///
+    /// PDA: [PROTOCOL_PREFIX].
+    #[account(seeds = [utils::PROTOCOL_PREFIX.as_bytes()], bump)]
+    protocol: UncheckedAccount<'info>,
+
     rent_sysvar: Sysvar<'info, Rent>,
     clock_sysvar: Sysvar<'info, Clock>,
     token_program: Program<'info, Token>,
@@ -94,13 +490,17 @@ pub struct CreateOrder<'info> {
 #[derive(Accounts)]
 #[instruction(escrow_bump: u8)]
 pub struct CancelOrder<'info> {
-    #[account(mut, has_one = funder, has_one = escrow)]
-    order: Box<Account<'info, state::Order>>,
+    #[account(mut, has_one = funder, has_one = rent_payer, has_one = escrow, has_one = order_book)]
+    order: AccountLoader<'info, state::Order>,
 
     /// Funder represent `Order` initiator(creator).
     #[account(mut)]
     funder: Signer<'info>,
 
+    /// Receives `escrow` rent (`Order::rent_payer`).
+    #[account(mut)]
+    rent_payer: UncheckedAccount<'info>,
+
     /// `funder` token account (will receive `escrow` tokens).
     /// Should be `funder` wallet if `Order::is_base_native`.
     /// Otherwise `spl_token` account should be passed.
@@ -113,48 +513,1448 @@ pub struct CancelOrder<'info> {
     #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), order.key().as_ref()], bump=escrow_bump)]
     escrow: UncheckedAccount<'info>,
 
+    /// Index of active `Order`'s for mint pair.
+    #[account(mut)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    /// Trading statistics of `funder`.
+    ///
+    /// PDA: [PROFILE_PREFIX, funder_pubkey].
+    #[account(mut, seeds = [utils::PROFILE_PREFIX.as_bytes(), funder.key().as_ref()], bump = funder_profile.bump)]
+    funder_profile: Box<Account<'info, state::Profile>>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
 }
 
-/// Perform p2p swap `Order` execute.
+/// Close finished (canceled, completed or declined) `Order`, rent is refunded to `rent_payer`.
+/// Can be performed by `funder` or `rent_payer`.
 #[derive(Accounts)]
-#[instruction(escrow_bump: u8)]
-pub struct ExecuteOrder<'info> {
-    #[account(mut, has_one = recipient, has_one = escrow, has_one = quote_token_account, has_one = funder)]
-    order: Box<Account<'info, state::Order>>,
+pub struct CloseOrder<'info> {
+    #[account(mut, close = rent_payer, has_one = funder, has_one = rent_payer)]
+    order: AccountLoader<'info, state::Order>,
+
+    /// `funder` or `rent_payer`.
+    authority: Signer<'info>,
 
     /// Funder represent `Order` initiator(creator).
-    #[account(mut)]
     funder: UncheckedAccount<'info>,
 
-    /// Recipient represent `Order` participant(who will accept offer).
+    /// Receives `order` rent (`Order::rent_payer`).
     #[account(mut)]
-    recipient: Signer<'info>,
+    rent_payer: UncheckedAccount<'info>,
+}
 
-    /// `recipient` token account (tokens input).
-    /// Should be `recipient` wallet if `Order::is_quote_native`.
-    /// Otherwise `spl_token` account should be passed.
+/// Perform creation of several p2p swap `Order`'s of one mint pair and `funder`.
+///
+/// `orders` - parameters of each `Order` (checked like in `create_order`).
+/// Batch `Order`'s have no referrer, hashlock and arbitration and aren't collection offers.
+///
+/// Remaining accounts for each `Order` (in `orders` order):
+/// [`order` (new account, signer, mut), `escrow` (mut), `recipient`].
+#[derive(Accounts)]
+#[instruction(orders: Vec<BatchOrderArgs>)]
+pub struct BatchCreateOrders<'info> {
+    /// Funder represent `Order`'s initiator(creator).
     #[account(mut)]
-    recipient_token_account: UncheckedAccount<'info>,
+    funder: Signer<'info>,
 
-    /// `recipient` token account for receiving from `escrow` (tokens output).
-    /// Should be `recipient` wallet if `Order::is_base_native`.
+    /// `funder` token account.
+    /// Should be `funder` wallet if base tokens are native `SOL`'s.
     /// Otherwise `spl_token` account should be passed.
     #[account(mut)]
-    recipient_receive_token_account: UncheckedAccount<'info>,
+    funder_token_account: UncheckedAccount<'info>,
 
-    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    /// Will directly accept `recipient`'s tokens and send them to `funder` (`funder`'s token account).
+    #[account(mut)]
+    quote_token_account: UncheckedAccount<'info>,
+
+    /// Mint of `escrow`'s.
+    /// If base tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    escrow_mint: UncheckedAccount<'info>,
+
+    /// Mint of `quote_token_account`.
+    /// If quote tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    quote_mint: UncheckedAccount<'info>,
+
+    /// Index of active `Order`'s for mint pair.
     ///
-    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, order_pubkey].
-    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), order.funder.as_ref(), order.key().as_ref()], bump=escrow_bump)]
-    escrow: UncheckedAccount<'info>,
+    /// PDA: [ORDER_BOOK_PREFIX, escrow_mint_pubkey, quote_mint_pubkey].
+    #[account(init_if_needed, space=state::OrderBook::LEN, payer=funder, seeds = [utils::ORDER_BOOK_PREFIX.as_bytes(), escrow_mint.key().as_ref(), quote_mint.key().as_ref()], bump)]
+    order_book: Box<Account<'info, state::OrderBook>>,
 
-    /// Will directly accept `recipient`'s tokens and send them to `funder` (`funder`'s token account).
-    /// Should be `funder` wallet if `Order::is_quote_native`.
+    /// Trading statistics of `funder`.
+    ///
+    /// PDA: [PROFILE_PREFIX, funder_pubkey].
+    #[account(init_if_needed, space=state::Profile::LEN, payer=funder, seeds = [utils::PROFILE_PREFIX.as_bytes(), funder.key().as_ref()], bump)]
+    funder_profile: Box<Account<'info, state::Profile>>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform cancel of several p2p swap `Order`'s of one mint pair and `funder`.
+///
+/// `escrow_bumps` - bump seed of each `Order` `escrow`.
+///
+/// Remaining accounts for each `Order` (in `escrow_bumps` order):
+/// [`order` (mut), `escrow` (mut), `Order::rent_payer` (mut)].
+#[derive(Accounts)]
+#[instruction(escrow_bumps: Vec<u8>)]
+pub struct BatchCancelOrders<'info> {
+    /// Funder represent `Order`'s initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// `funder` token account (will receive `escrow`'s tokens).
+    /// Should be `funder` wallet if base tokens are native `SOL`'s.
     /// Otherwise `spl_token` account should be passed.
     #[account(mut)]
-    quote_token_account: UncheckedAccount<'info>,
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// Index of active `Order`'s for mint pair.
+    #[account(mut)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    /// Trading statistics of `funder`.
+    ///
+    /// PDA: [PROFILE_PREFIX, funder_pubkey].
+    #[account(mut, seeds = [utils::PROFILE_PREFIX.as_bytes(), funder.key().as_ref()], bump = funder_profile.bump)]
+    funder_profile: Box<Account<'info, state::Profile>>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Remove expired and finished `Order`'s from mint pair `OrderBook` (can be called by anyone).
+///
+/// Remaining accounts:
+/// [`order`] for each indexed `Order`, that is not in `Created` status or is closed.
+/// Expired `Order`'s are removed without accounts.
+#[derive(Accounts)]
+pub struct PruneOrderBook<'info> {
+    /// Index of active `Order`'s for mint pair.
+    #[account(mut)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+}
+
+/// Perform p2p swap `Order` decline by `recipient`.
+/// `escrow` tokens are returned to `funder` token account recorded on `Order`.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8)]
+pub struct DeclineOrder<'info> {
+    #[account(mut, has_one = funder, has_one = rent_payer, has_one = recipient, has_one = funder_token_account, has_one = escrow, has_one = order_book)]
+    order: AccountLoader<'info, state::Order>,
+
+    /// Funder represent `Order` initiator(creator).
+    #[account(mut)]
+    funder: UncheckedAccount<'info>,
+
+    /// Receives `escrow` rent (`Order::rent_payer`).
+    #[account(mut)]
+    rent_payer: UncheckedAccount<'info>,
+
+    /// Recipient represent `Order` participant(who declines offer).
+    /// Can be PDA of calling program (signed over CPI).
+    recipient: Signer<'info>,
+
+    /// `funder` token account (will receive `escrow` tokens).
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, order_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), order.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Index of active `Order`'s for mint pair.
+    #[account(mut)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Claim delivery of arbitrated `Order` off-chain leg by `recipient`.
+#[derive(Accounts)]
+pub struct ClaimDelivery<'info> {
+    #[account(mut, has_one = recipient)]
+    order: AccountLoader<'info, state::Order>,
+
+    /// Recipient represent `Order` participant(who delivers off-chain leg).
+    recipient: Signer<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+}
+
+/// Dispute claimed delivery of arbitrated `Order` by `funder`.
+#[derive(Accounts)]
+pub struct DisputeOrder<'info> {
+    #[account(mut, has_one = funder)]
+    order: AccountLoader<'info, state::Order>,
+
+    /// Funder represent `Order` initiator(creator).
+    funder: Signer<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+}
+
+/// Release `escrow` of arbitrated `Order` to `recipient`.
+/// Can be performed by `funder` at any moment or by `recipient` after
+/// undisputed dispute window.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8)]
+pub struct ReleaseOrder<'info> {
+    #[account(mut, has_one = funder, has_one = rent_payer, has_one = recipient, has_one = escrow, has_one = order_book)]
+    order: AccountLoader<'info, state::Order>,
+
+    /// `funder` or `recipient`.
+    authority: Signer<'info>,
+
+    /// Funder represent `Order` initiator(creator).
+    #[account(mut)]
+    funder: UncheckedAccount<'info>,
+
+    /// Receives `escrow` rent (`Order::rent_payer`).
+    #[account(mut)]
+    rent_payer: UncheckedAccount<'info>,
+
+    /// Recipient represent `Order` participant(who delivers off-chain leg).
+    recipient: UncheckedAccount<'info>,
+
+    /// `recipient` token account for receiving from `escrow`.
+    /// Should be `recipient` wallet if `Order::is_base_native`.
+    #[account(mut)]
+    recipient_receive_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, order_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), order.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Index of active `Order`'s for mint pair.
+    #[account(mut)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Resolve dispute of arbitrated `Order` by arbiter.
+///
+/// `release` - send `escrow` tokens to `recipient`, otherwise return them to `funder`.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, release: bool)]
+pub struct ResolveDispute<'info> {
+    #[account(mut, has_one = funder, has_one = rent_payer, has_one = recipient, has_one = funder_token_account, has_one = escrow, has_one = arbiter, has_one = order_book)]
+    order: AccountLoader<'info, state::Order>,
+
+    /// Third party, that decides who gets `escrow`.
+    arbiter: Signer<'info>,
+
+    /// Funder represent `Order` initiator(creator).
+    #[account(mut)]
+    funder: UncheckedAccount<'info>,
+
+    /// Receives `escrow` rent (`Order::rent_payer`).
+    #[account(mut)]
+    rent_payer: UncheckedAccount<'info>,
+
+    /// Recipient represent `Order` participant(who delivers off-chain leg).
+    recipient: UncheckedAccount<'info>,
+
+    /// `funder` token account (will receive `escrow` tokens if not `release`).
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// `recipient` token account (will receive `escrow` tokens if `release`).
+    /// Should be `recipient` wallet if `Order::is_base_native`.
+    #[account(mut)]
+    recipient_receive_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, order_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), order.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Index of active `Order`'s for mint pair.
+    #[account(mut)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform p2p swap `Order` execute.
+///
+/// `taker_referral_share_bps` - share of protocol fee on `recipient` side, that will be paid to referrer.
+///
+/// `preimage` - secret of hash-time-locked `Order` (required if `Order::is_htlc`).
+///
+/// `recipient_salt` - salt of recipient commitment (required if `Order::is_hidden`).
+///
+/// Remaining accounts:
+/// [`recipient` token account of `Order::gate_mint`, NFT metadata if gate is collection]
+/// if `Order::is_gated`, then
+/// [NFT metadata, `funder` NFT token account (mut)] if `Order::is_collection_offer`, then
+/// [`Referrer` (mut), referrer token account (mut)] if `Order` has referrer, then
+/// [`Referrer` of quote mint (mut), referrer token account (mut)] if `taker_referral_share_bps` > 0, then
+/// [`recipient` token account of `Protocol::discount_mint`] (optional, reduces fee on `recipient` side
+/// according to `Protocol::fee_tiers`).
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, taker_referral_share_bps: u16, preimage: Option<[u8; 32]>, recipient_salt: Option<[u8; 32]>)]
+pub struct ExecuteOrder<'info> {
+    #[account(mut, has_one = escrow, has_one = quote_token_account, has_one = funder, has_one = rent_payer, has_one = order_book)]
+    order: AccountLoader<'info, state::Order>,
+
+    /// Funder represent `Order` initiator(creator).
+    #[account(mut)]
+    funder: UncheckedAccount<'info>,
+
+    /// Receives `escrow` rent (`Order::rent_payer`).
+    #[account(mut)]
+    rent_payer: UncheckedAccount<'info>,
+
+    /// Recipient represent `Order` participant(who will accept offer).
+    /// Can be PDA of calling program (signed over CPI).
+    #[account(mut)]
+    recipient: Signer<'info>,
+
+    /// `recipient` token account (tokens input).
+    /// Should be `recipient` wallet if `Order::is_quote_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    recipient_token_account: UncheckedAccount<'info>,
+
+    /// `recipient` token account for receiving from `escrow` (tokens output).
+    /// Should be `recipient` wallet if `Order::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    recipient_receive_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, order_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), order.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Will directly accept `recipient`'s tokens and send them to `funder` (`funder`'s token account).
+    /// Should be `funder` wallet if `Order::is_quote_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    quote_token_account: UncheckedAccount<'info>,
+
+    /// Index of active `Order`'s for mint pair.
+    #[account(mut)]
+    order_book: Box<Account<'info, state::OrderBook>>,
+
+    /// Trading statistics of `funder`.
+    ///
+    /// PDA: [PROFILE_PREFIX, funder_pubkey].
+    #[account(mut, seeds = [utils::PROFILE_PREFIX.as_bytes(), funder.key().as_ref()], bump = funder_profile.bump)]
+    funder_profile: Box<Account<'info, state::Profile>>,
+
+    /// Trading statistics of `recipient`.
+    ///
+    /// PDA: [PROFILE_PREFIX, recipient_pubkey].
+    #[account(init_if_needed, space=state::Profile::LEN, payer=recipient, seeds = [utils::PROFILE_PREFIX.as_bytes(), recipient.key().as_ref()], bump)]
+    recipient_profile: Box<Account<'info, state::Profile>>,
+
+    /// Protocol-wide trading statistics.
+    ///
+    /// PDA: [STATS_PREFIX].
+    #[account(init_if_needed, space=state::Stats::LEN, payer=recipient, seeds = [utils::STATS_PREFIX.as_bytes()], bump)]
+    stats: Box<Account<'info, state::Stats>>,
+
+    /// Trading statistics of `Order` mint pair.
+    ///
+    /// PDA: [PAIR_STATS_PREFIX, order_book_pubkey].
+    #[account(init_if_needed, space=state::PairStats::LEN, payer=recipient, seeds = [utils::PAIR_STATS_PREFIX.as_bytes(), order_book.key().as_ref()], bump)]
+    pair_stats: Box<Account<'info, state::PairStats>>,
+
+    /// `Protocol` configuration, fee is not charged until it's initialized.
+    ///
+    /// PDA: [PROTOCOL_PREFIX].
+    #[account(seeds = [utils::PROTOCOL_PREFIX.as_bytes()], bump)]
+    protocol: UncheckedAccount<'info>,
+
+    /// `Protocol` treasury token account for base tokens fee.
+    /// Should be treasury wallet if `Order::is_base_native`.
+    /// Not checked if base tokens fee is zero (e.g. `Protocol` is not initialized).
+    #[account(mut)]
+    treasury_base_token_account: UncheckedAccount<'info>,
+
+    /// `Protocol` treasury token account for quote tokens fee.
+    /// Should be treasury wallet if `Order::is_quote_native`.
+    /// Not checked if quote tokens fee is zero (e.g. `Protocol` is not initialized).
+    #[account(mut)]
+    treasury_quote_token_account: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform matching of two crossing `Order`'s (can be called by anyone).
+///
+/// `order_a` base tokens are sent to `order_b` funder and vice versa,
+/// each funder receives at least its `Order` current quote amount (less protocol fee).
+/// Surplus of both sides is sent to `matcher`.
+///
+/// Remaining accounts:
+/// [`Referrer` (mut), referrer token account (mut)] if `order_a` has referrer, then
+/// [`Referrer` (mut), referrer token account (mut)] if `order_b` has referrer.
+#[derive(Accounts)]
+#[instruction(escrow_a_bump: u8, escrow_b_bump: u8)]
+pub struct MatchOrders<'info> {
+    #[account(mut, constraint = order_a.key() != order_b.key())]
+    order_a: AccountLoader<'info, state::Order>,
+
+    /// `order_a` initiator(creator).
+    #[account(mut, address = order_a.load()?.funder)]
+    funder_a: UncheckedAccount<'info>,
+
+    /// Receives `escrow_a` rent.
+    #[account(mut, address = order_a.load()?.rent_payer)]
+    rent_payer_a: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_a_pubkey, order_a_pubkey].
+    #[account(mut, address = order_a.load()?.escrow, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder_a.key().as_ref(), order_a.key().as_ref()], bump=escrow_a_bump)]
+    escrow_a: UncheckedAccount<'info>,
+
+    /// Will receive `escrow_b` tokens for `funder_a`.
+    #[account(mut, address = order_a.load()?.quote_token_account)]
+    quote_token_account_a: UncheckedAccount<'info>,
+
+    /// `order_a` mint pair `OrderBook`.
+    #[account(mut, address = order_a.load()?.order_book)]
+    order_book_a: Box<Account<'info, state::OrderBook>>,
+
+    #[account(mut)]
+    order_b: AccountLoader<'info, state::Order>,
+
+    /// `order_b` initiator(creator).
+    #[account(mut, address = order_b.load()?.funder)]
+    funder_b: UncheckedAccount<'info>,
+
+    /// Receives `escrow_b` rent.
+    #[account(mut, address = order_b.load()?.rent_payer)]
+    rent_payer_b: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_b_pubkey, order_b_pubkey].
+    #[account(mut, address = order_b.load()?.escrow, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder_b.key().as_ref(), order_b.key().as_ref()], bump=escrow_b_bump)]
+    escrow_b: UncheckedAccount<'info>,
+
+    /// Will receive `escrow_a` tokens for `funder_b`.
+    #[account(mut, address = order_b.load()?.quote_token_account)]
+    quote_token_account_b: UncheckedAccount<'info>,
+
+    /// `order_b` mint pair `OrderBook`.
+    #[account(mut, address = order_b.load()?.order_book)]
+    order_book_b: Box<Account<'info, state::OrderBook>>,
+
+    /// Matcher, that will receive surplus.
+    #[account(mut)]
+    matcher: Signer<'info>,
+
+    /// `matcher` token account for `order_a` base tokens surplus.
+    /// Should be `matcher` wallet if `order_a` base tokens are native `SOL`'s.
+    #[account(mut)]
+    matcher_token_account_a: UncheckedAccount<'info>,
+
+    /// `matcher` token account for `order_b` base tokens surplus.
+    /// Should be `matcher` wallet if `order_b` base tokens are native `SOL`'s.
+    #[account(mut)]
+    matcher_token_account_b: UncheckedAccount<'info>,
+
+    /// Trading statistics of `funder_a`.
+    ///
+    /// PDA: [PROFILE_PREFIX, funder_a_pubkey].
+    #[account(mut, seeds = [utils::PROFILE_PREFIX.as_bytes(), funder_a.key().as_ref()], bump = funder_a_profile.bump)]
+    funder_a_profile: Box<Account<'info, state::Profile>>,
+
+    /// Trading statistics of `funder_b`.
+    ///
+    /// PDA: [PROFILE_PREFIX, funder_b_pubkey].
+    #[account(mut, seeds = [utils::PROFILE_PREFIX.as_bytes(), funder_b.key().as_ref()], bump = funder_b_profile.bump)]
+    funder_b_profile: Box<Account<'info, state::Profile>>,
+
+    /// Protocol-wide trading statistics.
+    ///
+    /// PDA: [STATS_PREFIX].
+    #[account(init_if_needed, space=state::Stats::LEN, payer=matcher, seeds = [utils::STATS_PREFIX.as_bytes()], bump)]
+    stats: Box<Account<'info, state::Stats>>,
+
+    /// Trading statistics of `order_a` mint pair.
+    ///
+    /// PDA: [PAIR_STATS_PREFIX, order_book_a_pubkey].
+    #[account(init_if_needed, space=state::PairStats::LEN, payer=matcher, seeds = [utils::PAIR_STATS_PREFIX.as_bytes(), order_book_a.key().as_ref()], bump)]
+    pair_stats_a: Box<Account<'info, state::PairStats>>,
+
+    /// Trading statistics of `order_b` mint pair.
+    ///
+    /// PDA: [PAIR_STATS_PREFIX, order_book_b_pubkey].
+    #[account(init_if_needed, space=state::PairStats::LEN, payer=matcher, seeds = [utils::PAIR_STATS_PREFIX.as_bytes(), order_book_b.key().as_ref()], bump)]
+    pair_stats_b: Box<Account<'info, state::PairStats>>,
+
+    /// `Protocol` configuration, fee is not charged until it's initialized.
+    ///
+    /// PDA: [PROTOCOL_PREFIX].
+    #[account(seeds = [utils::PROTOCOL_PREFIX.as_bytes()], bump)]
+    protocol: UncheckedAccount<'info>,
+
+    /// `Protocol` treasury token account for `order_a` base tokens fee.
+    /// Should be treasury wallet if `order_a` base tokens are native `SOL`'s.
+    /// Not checked if fee is zero (e.g. `Protocol` is not initialized).
+    #[account(mut)]
+    treasury_token_account_a: UncheckedAccount<'info>,
+
+    /// `Protocol` treasury token account for `order_b` base tokens fee.
+    /// Should be treasury wallet if `order_b` base tokens are native `SOL`'s.
+    /// Not checked if fee is zero (e.g. `Protocol` is not initialized).
+    #[account(mut)]
+    treasury_token_account_b: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Return time-weighted average price of mint pair over `window` seconds (can be called by anyone).
+///
+/// Price is set as return data (`Option<u128>`, scaled by `PRICE_SCALE`),
+/// so other programs can read it after CPI.
+#[derive(Accounts)]
+#[instruction(window: i64)]
+pub struct GetTwap<'info> {
+    /// PDA: [PAIR_STATS_PREFIX, order_book_pubkey].
+    pair_stats: Box<Account<'info, state::PairStats>>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+}
+
+/// Perform `Protocol` configuration initialization.
+///
+/// `fee_bps` - fee, charged from both `Order` sides on execution.
+///
+/// `max_referral_share_bps` - highest share of protocol fee, that can be paid to referrer.
+#[derive(Accounts)]
+#[instruction(fee_bps: u16, max_referral_share_bps: u16)]
+pub struct InitProtocol<'info> {
+    /// PDA: [PROTOCOL_PREFIX].
+    #[account(init, space=state::Protocol::LEN, payer=admin, seeds = [utils::PROTOCOL_PREFIX.as_bytes()], bump)]
+    protocol: Box<Account<'info, state::Protocol>>,
+
+    /// Protocol admin, that can update configuration.
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    /// Wallet, that will receive protocol fees.
+    treasury: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+/// Perform `Protocol` configuration update.
+#[derive(Accounts)]
+#[instruction(fee_bps: u16, max_referral_share_bps: u16)]
+pub struct UpdateProtocol<'info> {
+    #[account(mut, has_one = admin)]
+    protocol: Box<Account<'info, state::Protocol>>,
+
+    admin: Signer<'info>,
+
+    /// New protocol admin (pass `admin` to keep current one).
+    new_admin: UncheckedAccount<'info>,
+
+    /// Wallet, that will receive protocol fees.
+    treasury: UncheckedAccount<'info>,
+}
+
+/// Perform `Protocol` fee discount tiers update.
+///
+/// `fee_tiers` - tiers sorted by `min_amount`, with fee not higher than `Protocol::fee_bps`
+/// (pass empty tiers to disable discounts).
+#[derive(Accounts)]
+#[instruction(fee_tiers: Vec<FeeTier>)]
+pub struct SetFeeTiers<'info> {
+    #[account(mut, has_one = admin)]
+    protocol: Box<Account<'info, state::Protocol>>,
+
+    admin: Signer<'info>,
+
+    /// Mint of p2swap token, that gives fee discount to holders.
+    discount_mint: UncheckedAccount<'info>,
+}
+
+/// Perform `StakingPool` initialization.
+///
+/// `cooldown` - delay between `unstake` and `withdraw_stake` (seconds, 0 - without cooldown).
+#[derive(Accounts)]
+#[instruction(stake_vault_bump: u8, cooldown: i64)]
+pub struct InitStakingPool<'info> {
+    /// PDA: [STAKING_POOL_PREFIX].
+    #[account(init, space=state::StakingPool::LEN, payer=admin, seeds = [utils::STAKING_POOL_PREFIX.as_bytes()], bump)]
+    staking_pool: Box<Account<'info, state::StakingPool>>,
+
+    #[account(has_one = admin)]
+    protocol: Box<Account<'info, state::Protocol>>,
+
+    /// Protocol admin.
+    #[account(mut)]
+    admin: Signer<'info>,
+
+    /// Mint of p2swap token (should eq to `Protocol::discount_mint`).
+    stake_mint: UncheckedAccount<'info>,
+
+    /// Will hold staked tokens (PDA, uninitialized).
+    ///
+    /// PDA: [STAKE_VAULT_PREFIX].
+    #[account(mut, seeds = [utils::STAKE_VAULT_PREFIX.as_bytes()], bump=stake_vault_bump)]
+    stake_vault: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform p2swap tokens stake.
+#[derive(Accounts)]
+#[instruction(stake_vault_bump: u8, amount: u64)]
+pub struct StakeTokens<'info> {
+    #[account(mut, seeds = [utils::STAKING_POOL_PREFIX.as_bytes()], bump = staking_pool.bump)]
+    staking_pool: Box<Account<'info, state::StakingPool>>,
+
+    /// PDA: [STAKE_PREFIX, wallet_pubkey].
+    #[account(init_if_needed, space=state::Stake::LEN, payer=wallet, seeds = [utils::STAKE_PREFIX.as_bytes(), wallet.key().as_ref()], bump)]
+    stake: Box<Account<'info, state::Stake>>,
+
+    /// Staker wallet.
+    #[account(mut)]
+    wallet: Signer<'info>,
+
+    /// `wallet` token account of `StakingPool::stake_mint`.
+    #[account(mut)]
+    wallet_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [STAKE_VAULT_PREFIX].
+    #[account(mut, seeds = [utils::STAKE_VAULT_PREFIX.as_bytes()], bump=stake_vault_bump)]
+    stake_vault: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform p2swap tokens unstake.
+/// Tokens are returned immediately if `StakingPool::cooldown` is 0,
+/// otherwise they can be withdrawn with `withdraw_stake` after cooldown.
+#[derive(Accounts)]
+#[instruction(stake_vault_bump: u8, amount: u64)]
+pub struct Unstake<'info> {
+    #[account(mut, seeds = [utils::STAKING_POOL_PREFIX.as_bytes()], bump = staking_pool.bump)]
+    staking_pool: Box<Account<'info, state::StakingPool>>,
+
+    #[account(mut, has_one = wallet, seeds = [utils::STAKE_PREFIX.as_bytes(), wallet.key().as_ref()], bump = stake.bump)]
+    stake: Box<Account<'info, state::Stake>>,
+
+    /// Staker wallet.
+    wallet: Signer<'info>,
+
+    /// `wallet` token account of `StakingPool::stake_mint`.
+    #[account(mut)]
+    wallet_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [STAKE_VAULT_PREFIX].
+    #[account(mut, seeds = [utils::STAKE_VAULT_PREFIX.as_bytes()], bump=stake_vault_bump)]
+    stake_vault: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+}
+
+/// Perform withdraw of unstaked p2swap tokens after cooldown.
+#[derive(Accounts)]
+#[instruction(stake_vault_bump: u8)]
+pub struct WithdrawStake<'info> {
+    #[account(mut, has_one = wallet, seeds = [utils::STAKE_PREFIX.as_bytes(), wallet.key().as_ref()], bump = stake.bump)]
+    stake: Box<Account<'info, state::Stake>>,
+
+    /// Staker wallet.
+    wallet: Signer<'info>,
+
+    /// `wallet` token account of `StakingPool::stake_mint`.
+    #[account(mut)]
+    wallet_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [STAKE_VAULT_PREFIX].
+    #[account(mut, seeds = [utils::STAKE_VAULT_PREFIX.as_bytes()], bump=stake_vault_bump)]
+    stake_vault: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+}
+
+/// Perform deposit of collected fees, that will be distributed between stakers pro rata to stake.
+#[derive(Accounts)]
+#[instruction(reward_vault_bump: u8, amount: u64)]
+pub struct DepositRewards<'info> {
+    #[account(mut, seeds = [utils::STAKING_POOL_PREFIX.as_bytes()], bump = staking_pool.bump)]
+    staking_pool: Box<Account<'info, state::StakingPool>>,
+
+    /// Rewards depositor (e.g. protocol treasury).
+    #[account(mut)]
+    depositor: Signer<'info>,
+
+    /// `depositor` token account.
+    /// Should be `depositor` wallet if rewards are native `SOL`'s.
+    #[account(mut)]
+    depositor_token_account: UncheckedAccount<'info>,
+
+    /// Mint of rewards.
+    /// If rewards are native `SOL`'s, then this field should eq to `System::id()`.
+    reward_mint: UncheckedAccount<'info>,
+
+    /// Will hold rewards in `reward_mint` (PDA, created on first deposit).
+    ///
+    /// PDA: [REWARD_VAULT_PREFIX, reward_mint_pubkey].
+    #[account(mut, seeds = [utils::REWARD_VAULT_PREFIX.as_bytes(), reward_mint.key().as_ref()], bump=reward_vault_bump)]
+    reward_vault: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform claim of `Stake` rewards in specific mint.
+#[derive(Accounts)]
+#[instruction(reward_vault_bump: u8)]
+pub struct ClaimRewards<'info> {
+    #[account(seeds = [utils::STAKING_POOL_PREFIX.as_bytes()], bump = staking_pool.bump)]
+    staking_pool: Box<Account<'info, state::StakingPool>>,
+
+    #[account(mut, has_one = wallet, seeds = [utils::STAKE_PREFIX.as_bytes(), wallet.key().as_ref()], bump = stake.bump)]
+    stake: Box<Account<'info, state::Stake>>,
+
+    /// Staker wallet.
+    #[account(mut)]
+    wallet: Signer<'info>,
+
+    /// `wallet` token account (will receive rewards).
+    /// Should be `wallet` if rewards are native `SOL`'s.
+    #[account(mut)]
+    wallet_token_account: UncheckedAccount<'info>,
+
+    /// Mint of rewards.
+    /// If rewards are native `SOL`'s, then this field should eq to `System::id()`.
+    reward_mint: UncheckedAccount<'info>,
+
+    /// PDA: [REWARD_VAULT_PREFIX, reward_mint_pubkey].
+    #[account(mut, seeds = [utils::REWARD_VAULT_PREFIX.as_bytes(), reward_mint.key().as_ref()], bump=reward_vault_bump)]
+    reward_vault: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+}
+
+/// Perform `Referrer` creation for payouts in specific mint.
+#[derive(Accounts)]
+pub struct CreateReferrer<'info> {
+    /// PDA: [REFERRER_PREFIX, wallet_pubkey, mint_pubkey].
+    #[account(init, space=state::Referrer::LEN, payer=wallet, seeds = [utils::REFERRER_PREFIX.as_bytes(), wallet.key().as_ref(), mint.key().as_ref()], bump)]
+    referrer: Box<Account<'info, state::Referrer>>,
+
+    /// Referrer wallet.
+    #[account(mut)]
+    wallet: Signer<'info>,
+
+    /// `wallet` token account, that will receive payouts.
+    /// Should be `wallet` if payouts are native `SOL`'s.
+    token_account: UncheckedAccount<'info>,
+
+    /// Mint of payouts.
+    /// If payouts are native `SOL`'s, then this field should eq to `System::id()`.
+    mint: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+/// Perform sealed-bid `Auction` creation and initialization.
+///
+/// `base_amount` - quantity of tokens, that `funder` will give to the winner.
+///
+/// `min_bid_amount` - lowest quantity of tokens, that will be accepted as bid.
+///
+/// `start_date` - the date from which bids will be accepted.
+///
+/// `commit_end_date` - the date from which bids will be revealed.
+///
+/// `reveal_end_date` - the date from which `Auction` can be settled.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, base_amount: u64, min_bid_amount: u64, start_date: Option<UnixTimestamp>, commit_end_date: UnixTimestamp, reveal_end_date: UnixTimestamp)]
+pub struct CreateAuction<'info> {
+    #[account(init, space=state::Auction::LEN, payer=funder)]
+    auction: Box<Account<'info, state::Auction>>,
+
+    /// Funder represent `Auction` initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// `funder` token account.
+    /// Should be `funder` wallet if `Auction::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), auction.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Will accept winning bid and send it to `funder` (`funder`'s token account).
+    /// Should be `funder` wallet if `Auction::is_quote_native`.
+    /// Otherwise `spl_token` account should be passed.
+    quote_token_account: UncheckedAccount<'info>,
+
+    /// Mint of `escrow`.
+    /// If base tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    escrow_mint: UncheckedAccount<'info>,
+
+    /// Mint of bids.
+    /// If bids are native `SOL`'s, then this field should eq to `System::id()`.
+    quote_mint: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform sealed `Bid` commit (with deposit).
+///
+/// `commitment` - sha256(auction_pubkey, bidder_pubkey, amount (LE), salt), see `utils::hash_bid`.
+///
+/// `deposit_amount` - quantity of tokens, that will be locked in `deposit` (should cover the bid).
+#[derive(Accounts)]
+#[instruction(deposit_bump: u8, commitment: [u8; 32], deposit_amount: u64)]
+pub struct CommitBid<'info> {
+    #[account(mut)]
+    auction: Box<Account<'info, state::Auction>>,
+
+    /// PDA: [AUCTION_BID_PREFIX, auction_pubkey, bidder_pubkey].
+    #[account(init, space=state::Bid::LEN, payer=bidder, seeds = [utils::AUCTION_BID_PREFIX.as_bytes(), auction.key().as_ref(), bidder.key().as_ref()], bump)]
+    bid: Box<Account<'info, state::Bid>>,
+
+    /// Bidder represent `Auction` participant.
+    #[account(mut)]
+    bidder: Signer<'info>,
+
+    /// `bidder` token account (deposit input, refunds output).
+    /// Should be `bidder` wallet if `Auction::is_quote_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    bidder_token_account: UncheckedAccount<'info>,
+
+    /// `bidder` token account for receiving from `escrow` in case of win (settlement accepts any other).
+    /// Should be `bidder` wallet if `Auction::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    receive_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `bidder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, bidder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), bidder.key().as_ref(), auction.key().as_ref()], bump=deposit_bump)]
+    deposit: UncheckedAccount<'info>,
+
+    /// Mint of `deposit`.
+    #[account(address = auction.quote_mint)]
+    quote_mint: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform sealed `Bid` reveal.
+///
+/// `amount` - bid quantity of tokens.
+///
+/// `salt` - secret, that was used for `commitment`.
+#[derive(Accounts)]
+#[instruction(amount: u64, salt: [u8; 32])]
+pub struct RevealBid<'info> {
+    #[account(mut)]
+    auction: Box<Account<'info, state::Auction>>,
+
+    #[account(mut, has_one = auction, has_one = bidder)]
+    bid: Box<Account<'info, state::Bid>>,
+
+    bidder: Signer<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+}
+
+/// Perform `Auction` settlement to the highest revealed `Bid` (can be called by anyone).
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, deposit_bump: u8)]
+pub struct SettleAuction<'info> {
+    #[account(mut, has_one = funder, has_one = escrow)]
+    auction: Box<Account<'info, state::Auction>>,
+
+    /// Funder represent `Auction` initiator(creator).
+    #[account(mut)]
+    funder: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), auction.funder.as_ref(), auction.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Will accept winning bid and send it to `funder` (any `funder`'s `quote_mint` token account).
+    /// Should be `funder` wallet if `Auction::is_quote_native`.
+    #[account(mut)]
+    quote_token_account: UncheckedAccount<'info>,
+
+    /// Winning `Bid`.
+    #[account(mut, close = bidder, has_one = auction, has_one = bidder, has_one = deposit, has_one = refund_token_account, constraint = bid.bidder == auction.winner)]
+    bid: Box<Account<'info, state::Bid>>,
+
+    /// Winner.
+    #[account(mut)]
+    bidder: UncheckedAccount<'info>,
+
+    /// `bidder` token account, that will receive `deposit` remainder.
+    #[account(mut)]
+    refund_token_account: UncheckedAccount<'info>,
+
+    /// Any `bidder`'s `base_mint` token account, that will receive `escrow` tokens.
+    /// Should be `bidder` wallet if `Auction::is_base_native`.
+    #[account(mut)]
+    receive_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, bidder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), bidder.key().as_ref(), auction.key().as_ref()], bump=deposit_bump)]
+    deposit: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform losing (or not revealed) `Bid` refund (can be called by anyone).
+#[derive(Accounts)]
+#[instruction(deposit_bump: u8)]
+pub struct RefundBid<'info> {
+    auction: Box<Account<'info, state::Auction>>,
+
+    #[account(mut, close = bidder, has_one = auction, has_one = bidder, has_one = deposit, has_one = refund_token_account)]
+    bid: Box<Account<'info, state::Bid>>,
+
+    #[account(mut)]
+    bidder: UncheckedAccount<'info>,
+
+    /// `bidder` token account, that will receive `deposit` tokens.
+    #[account(mut)]
+    refund_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, bidder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), bidder.key().as_ref(), auction.key().as_ref()], bump=deposit_bump)]
+    deposit: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `Auction` cancel (allowed if there are no bids or no valid revealed bids).
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8)]
+pub struct CancelAuction<'info> {
+    #[account(mut, has_one = funder, has_one = escrow)]
+    auction: Box<Account<'info, state::Auction>>,
+
+    /// Funder represent `Auction` initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// `funder` token account (will receive `escrow` tokens).
+    /// Should be `funder` wallet if `Auction::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), auction.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `EnglishAuction` creation and initialization.
+///
+/// `base_amount` - quantity of tokens, that `funder` will give to the winner.
+///
+/// `min_bid_amount` - lowest quantity of tokens, that will be accepted as first bid.
+///
+/// `min_bid_increment` - lowest difference between new bid and the highest one.
+///
+/// `start_date` - the date from which bids will be accepted.
+///
+/// `expire_date` - the date from which bids will not be accepted.
+///
+/// `extension_window` - bids placed within this period (seconds) before `expire_date` extend it.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, bid_escrow_bump: u8, base_amount: u64, min_bid_amount: u64, min_bid_increment: u64, start_date: Option<UnixTimestamp>, expire_date: UnixTimestamp, extension_window: i64)]
+pub struct CreateEnglishAuction<'info> {
+    #[account(init, space=state::EnglishAuction::LEN, payer=funder)]
+    auction: Box<Account<'info, state::EnglishAuction>>,
+
+    /// Funder represent `EnglishAuction` initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// `funder` token account.
+    /// Should be `funder` wallet if `EnglishAuction::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), auction.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Will hold the highest bid(PDA, uninitialized).
+    ///
+    /// PDA: [AUCTION_BID_ESCROW_PREFIX, auction_pubkey].
+    #[account(mut, seeds = [utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(), auction.key().as_ref()], bump=bid_escrow_bump)]
+    bid_escrow: UncheckedAccount<'info>,
+
+    /// Will accept winning bid and send it to `funder` (`funder`'s token account).
+    /// Should be `funder` wallet if `EnglishAuction::is_quote_native`.
+    /// Otherwise `spl_token` account should be passed.
+    quote_token_account: UncheckedAccount<'info>,
+
+    /// Mint of `escrow`.
+    /// If base tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    escrow_mint: UncheckedAccount<'info>,
+
+    /// Mint of bids.
+    /// If bids are native `SOL`'s, then this field should eq to `System::id()`.
+    quote_mint: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `EnglishAuction` bid, outbid bids are refunded by `RefundEnglishBid`.
+///
+/// `amount` - bid quantity of tokens.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct PlaceBid<'info> {
+    #[account(mut, has_one = bid_escrow)]
+    auction: Box<Account<'info, state::EnglishAuction>>,
+
+    /// Bidder represent `EnglishAuction` participant.
+    #[account(mut)]
+    bidder: Signer<'info>,
+
+    /// `bidder` token account (bid input, refund output).
+    /// Should be `bidder` wallet if `EnglishAuction::is_quote_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    bidder_token_account: UncheckedAccount<'info>,
+
+    /// `bidder` token account for receiving from `escrow` in case of win (settlement accepts any other).
+    /// Should be `bidder` wallet if `EnglishAuction::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    receive_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [AUCTION_BID_ESCROW_PREFIX, auction_pubkey].
+    #[account(mut, seeds = [utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(), auction.key().as_ref()], bump)]
+    bid_escrow: UncheckedAccount<'info>,
+
+    /// PDA: [ENGLISH_BID_PREFIX, auction_pubkey, bidder_pubkey].
+    #[account(init_if_needed, space=state::EnglishBid::LEN, payer=bidder, seeds = [utils::ENGLISH_BID_PREFIX.as_bytes(), auction.key().as_ref(), bidder.key().as_ref()], bump)]
+    bid: Box<Account<'info, state::EnglishBid>>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `EnglishBid` refund (can be called by anyone).
+///
+/// Allowed for outbid bidders and, after settlement, for the winner's excess.
+#[derive(Accounts)]
+#[instruction(bid_escrow_bump: u8)]
+pub struct RefundEnglishBid<'info> {
+    #[account(has_one = funder, has_one = bid_escrow)]
+    auction: Box<Account<'info, state::EnglishAuction>>,
+
+    /// Receives drained `bid_escrow` rent after settlement.
+    #[account(mut)]
+    funder: UncheckedAccount<'info>,
+
+    #[account(mut, close = bidder, has_one = auction, has_one = bidder)]
+    bid: Box<Account<'info, state::EnglishBid>>,
+
+    #[account(mut)]
+    bidder: UncheckedAccount<'info>,
+
+    /// `bidder` token account, that will receive refund.
+    /// Should be `bidder` wallet if `EnglishAuction::is_quote_native`.
+    /// Otherwise any `spl_token` account of `bidder` can be passed.
+    #[account(mut)]
+    refund_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [AUCTION_BID_ESCROW_PREFIX, auction_pubkey].
+    #[account(mut, seeds = [utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(), auction.key().as_ref()], bump=bid_escrow_bump)]
+    bid_escrow: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `EnglishAuction` settlement to the highest bid (can be called by anyone).
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, bid_escrow_bump: u8)]
+pub struct SettleEnglishAuction<'info> {
+    #[account(mut, has_one = funder, has_one = escrow, has_one = bid_escrow)]
+    auction: Box<Account<'info, state::EnglishAuction>>,
+
+    /// Funder represent `EnglishAuction` initiator(creator).
+    #[account(mut)]
+    funder: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), auction.funder.as_ref(), auction.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// PDA: [AUCTION_BID_ESCROW_PREFIX, auction_pubkey].
+    #[account(mut, seeds = [utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(), auction.key().as_ref()], bump=bid_escrow_bump)]
+    bid_escrow: UncheckedAccount<'info>,
+
+    /// Will accept winning bid and send it to `funder` (any `funder`'s `quote_mint` token account).
+    /// Should be `funder` wallet if `EnglishAuction::is_quote_native`.
+    #[account(mut)]
+    quote_token_account: UncheckedAccount<'info>,
+
+    /// Any `highest_bidder`'s `base_mint` token account, that will receive `escrow` tokens.
+    /// Should be `highest_bidder` wallet if `EnglishAuction::is_base_native`.
+    #[account(mut)]
+    receive_token_account: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `EnglishAuction` cancel (allowed if there are no bids).
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, bid_escrow_bump: u8)]
+pub struct CancelEnglishAuction<'info> {
+    #[account(mut, has_one = funder, has_one = escrow, has_one = bid_escrow)]
+    auction: Box<Account<'info, state::EnglishAuction>>,
+
+    /// Funder represent `EnglishAuction` initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// `funder` token account (will receive `escrow` tokens).
+    /// Should be `funder` wallet if `EnglishAuction::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, auction_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), auction.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// PDA: [AUCTION_BID_ESCROW_PREFIX, auction_pubkey].
+    #[account(mut, seeds = [utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(), auction.key().as_ref()], bump=bid_escrow_bump)]
+    bid_escrow: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `Rfq` (request for quotes) creation and initialization.
+///
+/// `base_amount` - quantity of tokens, that `funder` will give to the accepted `Quote` maker.
+///
+/// `expire_date` - the date from which `Rfq` will be expired.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, base_amount: u64, expire_date: UnixTimestamp)]
+pub struct CreateRfq<'info> {
+    #[account(init, space=state::Rfq::LEN, payer=funder)]
+    rfq: Box<Account<'info, state::Rfq>>,
+
+    /// Funder represent `Rfq` initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// `funder` token account.
+    /// Should be `funder` wallet if `Rfq::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `funder`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, rfq_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), rfq.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    /// Mint of `escrow`.
+    /// If base tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    escrow_mint: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `Quote` submission (with deposit) for `Rfq`.
+///
+/// `amount` - quantity of tokens, that `maker` will give to `funder`.
+///
+/// `expire_date` - the date from which `Quote` can't be accepted.
+#[derive(Accounts)]
+#[instruction(deposit_bump: u8, amount: u64, expire_date: UnixTimestamp)]
+pub struct SubmitQuote<'info> {
+    #[account(mut)]
+    rfq: Box<Account<'info, state::Rfq>>,
+
+    /// PDA: [RFQ_QUOTE_PREFIX, rfq_pubkey, maker_pubkey].
+    #[account(init, space=state::Quote::LEN, payer=maker, seeds = [utils::RFQ_QUOTE_PREFIX.as_bytes(), rfq.key().as_ref(), maker.key().as_ref()], bump)]
+    quote: Box<Account<'info, state::Quote>>,
+
+    /// Maker represent `Rfq` participant.
+    #[account(mut)]
+    maker: Signer<'info>,
+
+    /// `maker` token account (deposit input, refund output).
+    /// Should be `maker` wallet if `quote_mint` is native.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    maker_token_account: UncheckedAccount<'info>,
+
+    /// `maker` token account for receiving from `escrow` in case of acceptance.
+    /// Should be `maker` wallet if `Rfq::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    receive_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `maker`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, maker_pubkey, rfq_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), maker.key().as_ref(), rfq.key().as_ref()], bump=deposit_bump)]
+    deposit: UncheckedAccount<'info>,
+
+    /// Mint of `deposit`.
+    /// If quote tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    quote_mint: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `Quote` acceptance and `Rfq` settlement.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8, deposit_bump: u8)]
+pub struct AcceptQuote<'info> {
+    #[account(mut, has_one = funder, has_one = escrow)]
+    rfq: Box<Account<'info, state::Rfq>>,
+
+    /// Funder represent `Rfq` initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// Will accept `maker`'s tokens (`funder`'s token account).
+    /// Should be `funder` wallet if `Quote::is_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    quote_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, rfq_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), rfq.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    #[account(mut, close = maker, has_one = rfq, has_one = maker, has_one = deposit, has_one = receive_token_account)]
+    quote: Box<Account<'info, state::Quote>>,
+
+    #[account(mut)]
+    maker: UncheckedAccount<'info>,
+
+    /// `maker` token account, that will receive `escrow` tokens.
+    #[account(mut)]
+    receive_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, maker_pubkey, rfq_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), maker.key().as_ref(), rfq.key().as_ref()], bump=deposit_bump)]
+    deposit: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `Quote` refund.
+/// Can be called by anyone if `Rfq` is not active or `Quote` is expired, otherwise `maker` should sign.
+#[derive(Accounts)]
+#[instruction(deposit_bump: u8)]
+pub struct RefundQuote<'info> {
+    #[account(mut)]
+    rfq: Box<Account<'info, state::Rfq>>,
+
+    #[account(mut, close = maker, has_one = rfq, has_one = maker, has_one = deposit, has_one = refund_token_account)]
+    quote: Box<Account<'info, state::Quote>>,
+
+    #[account(mut)]
+    maker: UncheckedAccount<'info>,
+
+    /// `maker` token account, that will receive `deposit` tokens.
+    #[account(mut)]
+    refund_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, maker_pubkey, rfq_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), maker.key().as_ref(), rfq.key().as_ref()], bump=deposit_bump)]
+    deposit: UncheckedAccount<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `Rfq` cancel.
+#[derive(Accounts)]
+#[instruction(escrow_bump: u8)]
+pub struct CancelRfq<'info> {
+    #[account(mut, has_one = funder, has_one = escrow)]
+    rfq: Box<Account<'info, state::Rfq>>,
+
+    /// Funder represent `Rfq` initiator(creator).
+    #[account(mut)]
+    funder: Signer<'info>,
+
+    /// `funder` token account (will receive `escrow` tokens).
+    /// Should be `funder` wallet if `Rfq::is_base_native`.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    funder_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, funder_pubkey, rfq_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), funder.key().as_ref(), rfq.key().as_ref()], bump=escrow_bump)]
+    escrow: UncheckedAccount<'info>,
+
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `RingSwap` creation and initialization.
+///
+/// `legs` - ring legs, leg `i` tokens will be received by participant of leg `i + 1`
+/// (tokens of the last leg will be received by participant of the first leg).
+///
+/// `expire_date` - the date from which `RingSwap` will be expired.
+#[derive(Accounts)]
+#[instruction(legs: Vec<RingSwapLegArgs>, expire_date: UnixTimestamp)]
+pub struct CreateRingSwap<'info> {
+    #[account(init, space=state::RingSwap::LEN, payer=creator)]
+    ring_swap: Box<Account<'info, state::RingSwap>>,
+
+    /// Creator of `RingSwap` (not necessarily participant).
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `RingSwap` leg deposit.
+///
+/// `leg_index` - index of `participant` leg.
+#[derive(Accounts)]
+#[instruction(deposit_bump: u8, leg_index: u8)]
+pub struct DepositRingSwapLeg<'info> {
+    #[account(mut)]
+    ring_swap: Box<Account<'info, state::RingSwap>>,
+
+    /// Participant of `RingSwap` leg.
+    #[account(mut)]
+    participant: Signer<'info>,
+
+    /// `participant` token account (deposit input, refund output).
+    /// Should be `participant` wallet if leg tokens are native `SOL`'s.
+    /// Otherwise `spl_token` account should be passed.
+    #[account(mut)]
+    participant_token_account: UncheckedAccount<'info>,
+
+    /// Will hold `participant`'s tokens to provide guarantee(PDA, uninitialized).
+    ///
+    /// PDA: [ORDER_ESCROW_PREFIX, participant_pubkey, ring_swap_pubkey].
+    #[account(mut, seeds = [utils::ORDER_ESCROW_PREFIX.as_bytes(), participant.key().as_ref(), ring_swap.key().as_ref()], bump=deposit_bump)]
+    deposit: UncheckedAccount<'info>,
+
+    /// Mint of leg tokens.
+    mint: UncheckedAccount<'info>,
+
+    rent_sysvar: Sysvar<'info, Rent>,
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `RingSwap` settlement (can be called by anyone when all legs are funded).
+///
+/// Remaining accounts for each leg (in legs order):
+/// [`deposit` (mut), `participant` (mut), `receive_token_account` (mut)].
+#[derive(Accounts)]
+pub struct SettleRingSwap<'info> {
+    #[account(mut)]
+    ring_swap: Box<Account<'info, state::RingSwap>>,
+
+    clock_sysvar: Sysvar<'info, Clock>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Perform `RingSwap` leg refund.
+/// Can be called by anyone after `expire_date`, otherwise `participant` should sign.
+#[derive(Accounts)]
+#[instruction(leg_index: u8)]
+pub struct RefundRingSwapLeg<'info> {
+    #[account(mut)]
+    ring_swap: Box<Account<'info, state::RingSwap>>,
+
+    #[account(mut)]
+    participant: UncheckedAccount<'info>,
+
+    /// `participant` token account, that will receive `deposit` tokens.
+    #[account(mut)]
+    refund_token_account: UncheckedAccount<'info>,
+
+    /// PDA: [ORDER_ESCROW_PREFIX, participant_pubkey, ring_swap_pubkey].
+    #[account(mut)]
+    deposit: UncheckedAccount<'info>,
 
     clock_sysvar: Sysvar<'info, Clock>,
     token_program: Program<'info, Token>,
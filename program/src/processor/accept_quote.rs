@@ -0,0 +1,82 @@
+use crate::{error, state, utils, AcceptQuote};
+use anchor_lang::prelude::*;
+
+impl<'info> AcceptQuote<'info> {
+    pub fn process(&mut self, escrow_bump: u8, deposit_bump: u8) -> Result<()> {
+        if self.rfq.status != state::RfqStatus::Created {
+            return Err(error::ErrorCode::InvalidRfqStatus.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp >= self.rfq.expire_date {
+            return Err(error::ErrorCode::RfqIsExpired.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp >= self.quote.expire_date {
+            return Err(error::ErrorCode::QuoteIsExpired.into());
+        }
+
+        if self.quote.is_native() && self.quote_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        }
+
+        self.rfq.status = state::RfqStatus::Settled;
+        self.rfq.accepted_quote = self.quote.key();
+        self.rfq.quotes_count -= 1;
+
+        let rfq_key = self.rfq.key();
+
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.rfq.funder.as_ref(),
+            rfq_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        let deposit_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.quote.maker.as_ref(),
+            rfq_key.as_ref(),
+            &[deposit_bump],
+        ];
+
+        // Transfer funds from `deposit` to `funder` (`quote_token_account`).
+        utils::transfer_from_escrow(
+            &self.deposit.to_account_info(),
+            &self.quote_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.quote.amount,
+            self.quote.is_native(),
+            deposit_seeds,
+        )?;
+
+        // Delete `deposit` account
+        utils::close_escrow(
+            &self.deposit.to_account_info(),
+            &self.maker.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.quote.is_native(),
+            deposit_seeds,
+        )?;
+
+        // Transfer funds from `escrow` to `maker` (`receive_token_account`).
+        utils::transfer_from_escrow(
+            &self.escrow.to_account_info(),
+            &self.receive_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.rfq.base_amount,
+            self.rfq.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        // Delete `escrow` account
+        utils::close_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.rfq.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use super::cancel_order::{check_cancel, return_escrow};
+use crate::{error, event, state, utils, BatchCancelOrders};
+use anchor_lang::prelude::*;
+
+impl<'info> BatchCancelOrders<'info> {
+    pub fn process(
+        &mut self,
+        escrow_bumps: Vec<u8>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        if escrow_bumps.is_empty() || remaining_accounts.len() != escrow_bumps.len() * 3 {
+            return Err(error::ErrorCode::InvalidBatch.into());
+        }
+
+        let now = self.clock_sysvar.unix_timestamp;
+
+        for (escrow_bump, accounts) in escrow_bumps.into_iter().zip(remaining_accounts.chunks(3)) {
+            let order_info = &accounts[0];
+            let escrow = &accounts[1];
+            let rent_payer = &accounts[2];
+
+            let order_loader = AccountLoader::<state::Order>::try_from(order_info)?;
+            let mut order = order_loader.load_mut()?;
+
+            if order.funder != self.funder.key()
+                || order.escrow != escrow.key()
+                || order.rent_payer != rent_payer.key()
+                || order.order_book != self.order_book.key()
+            {
+                return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+            }
+
+            check_cancel(&order, now)?;
+
+            order.status = state::OrderStatus::Canceled as u8;
+            self.order_book.remove(order_info.key);
+
+            // `Order` canceled after `expire_date` is counted as expired
+            if now > order.expire_date {
+                self.funder_profile.orders_expired += 1;
+            } else {
+                self.funder_profile.orders_canceled += 1;
+            }
+
+            if order.is_base_native() && self.funder_token_account.key != self.funder.key {
+                return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+            }
+
+            let escrow_seeds: &[&[u8]] = &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.funder.key.as_ref(),
+                order_info.key.as_ref(),
+                &[escrow_bump],
+            ];
+
+            return_escrow(
+                escrow,
+                &self.funder.to_account_info(),
+                rent_payer,
+                &self.funder_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                order.is_base_native(),
+                escrow_seeds,
+            )?;
+
+            emit!(event::OrderCanceled {
+                order: order_info.key(),
+                reference: order.reference,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use super::create_order::{check_optional_dates, check_order, set_memo};
+use crate::{error, event, state, utils, BatchCreateOrders};
+use anchor_lang::prelude::*;
+
+impl<'info> BatchCreateOrders<'info> {
+    pub fn process(
+        &mut self,
+        funder_profile_bump: u8,
+        orders: Vec<state::BatchOrderArgs>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        if orders.is_empty() || remaining_accounts.len() != orders.len() * 3 {
+            return Err(error::ErrorCode::InvalidBatch.into());
+        }
+
+        let is_base_native = self.escrow_mint.key() == System::id();
+        let is_quote_native = self.quote_mint.key() == System::id();
+
+        if is_base_native && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+        }
+
+        // Check if quote token account is valid in native `SOL` context
+        if is_quote_native && self.quote_token_account.key() != self.funder.key() {
+            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        }
+
+        let now = self.clock_sysvar.unix_timestamp;
+
+        self.order_book.base_mint = self.escrow_mint.key();
+        self.order_book.quote_mint = self.quote_mint.key();
+
+        // Track `funder` trading statistics
+        self.funder_profile.wallet = self.funder.key();
+        self.funder_profile.bump = funder_profile_bump;
+        self.funder_profile.orders_created += orders.len() as u64;
+
+        for (args, accounts) in orders.into_iter().zip(remaining_accounts.chunks(3)) {
+            let order_info = &accounts[0];
+            let escrow = &accounts[1];
+            let recipient = &accounts[2];
+
+            if !order_info.is_signer || !order_info.is_writable {
+                return Err(error::ErrorCode::InvalidBatch.into());
+            }
+
+            if self.funder.key == recipient.key {
+                return Err(error::ErrorCode::RecipientMatchFunder.into());
+            }
+
+            let escrow_seeds: &[&[u8]] = &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.funder.key.as_ref(),
+                order_info.key.as_ref(),
+                &[args.escrow_bump],
+            ];
+
+            if Pubkey::create_program_address(escrow_seeds, &crate::id())
+                .map_err(|_| anchor_lang::error::ErrorCode::ConstraintSeeds)?
+                != escrow.key()
+            {
+                return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+            }
+
+            // Create `Order` account, signed by its keypair
+            utils::sys_create_account(
+                &self.funder.to_account_info(),
+                order_info,
+                self.rent_sysvar.minimum_balance(state::Order::LEN),
+                state::Order::LEN,
+                &crate::id(),
+                &[],
+            )?;
+
+            let order_loader =
+                AccountLoader::<state::Order>::try_from_unchecked(&crate::id(), order_info)?;
+
+            {
+                // Other fields are zeroed by account creation
+                let mut order = order_loader.load_init()?;
+
+                order.status = state::OrderStatus::Created as u8;
+
+                order.base_amount = args.base_amount;
+                order.quote_amount = args.quote_amount;
+
+                order.base_mint = self.escrow_mint.key();
+                order.quote_mint = self.quote_mint.key();
+
+                order.funder = self.funder.key();
+                order.recipient = recipient.key();
+                order.rent_payer = self.funder.key();
+
+                order.funder_token_account = self.funder_token_account.key();
+                order.escrow = escrow.key();
+                order.quote_token_account = self.quote_token_account.key();
+
+                check_optional_dates(args.start_date, args.cancel_locked_until)?;
+
+                order.start_date = args.start_date.unwrap_or(0);
+                order.expire_date = args.expire_date;
+
+                order.price_curve = args.price_curve as u8;
+                order.floor_quote_amount = if args.price_curve == state::PriceCurve::Fixed {
+                    args.quote_amount
+                } else {
+                    args.floor_quote_amount
+                };
+
+                order.created_at = now;
+                order.cancel_locked_until = args.cancel_locked_until.unwrap_or(0);
+
+                let memo = args.memo.unwrap_or_default();
+                set_memo(&mut order, &memo)?;
+                order.reference = args.reference.unwrap_or([0; 32]);
+
+                // Index `Order` in mint pair `OrderBook`
+                order.order_book = self.order_book.key();
+                self.order_book
+                    .insert_or_evict(order.to_order_book_entry(order_info.key), now);
+
+                check_order(&order, now)?;
+
+                // Create escrow account according to `funder` mint and lock base amount
+                utils::create_escrow(
+                    &self.funder.to_account_info(),
+                    &self.funder.to_account_info(),
+                    &self.funder_token_account.to_account_info(),
+                    escrow,
+                    &self.escrow_mint.to_account_info(),
+                    &self.rent_sysvar,
+                    &self.token_program.to_account_info(),
+                    args.base_amount,
+                    is_base_native,
+                    escrow_seeds,
+                )?;
+
+                emit!(event::OrderCreated {
+                    order: order_info.key(),
+                    funder: order.funder,
+                    recipient: order.recipient,
+                    base_mint: order.base_mint,
+                    quote_mint: order.quote_mint,
+                    base_amount: order.base_amount,
+                    quote_amount: order.quote_amount,
+                    reference: order.reference,
+                    memo,
+                });
+            }
+
+            // Write `Order` discriminator
+            order_loader.exit(&crate::id())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{error, state, utils, CancelAuction};
+use anchor_lang::prelude::*;
+
+impl<'info> CancelAuction<'info> {
+    pub fn process(&mut self, escrow_bump: u8) -> Result<()> {
+        if self.auction.status != state::AuctionStatus::Created {
+            return Err(error::ErrorCode::InvalidAuctionStatus.into());
+        }
+
+        // `Auction` with bids can be canceled only if none of them was revealed
+        if self.auction.bids_count > 0
+            && (self.clock_sysvar.unix_timestamp < self.auction.reveal_end_date
+                || self.auction.has_winner())
+        {
+            return Err(error::ErrorCode::AuctionIsNotCancelable.into());
+        }
+
+        self.auction.status = state::AuctionStatus::Canceled;
+
+        if self.auction.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+        }
+
+        let auction_key = self.auction.key();
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.funder.key.as_ref(),
+            auction_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        // Transfer `funder` tokens from `escrow`
+        // (native `SOL`'s will be returned while `escrow` deletion)
+        if !self.auction.is_base_native() {
+            let escrow_amount = utils::escrow_amount(&self.escrow.to_account_info(), false)?;
+
+            utils::transfer_from_escrow(
+                &self.escrow.to_account_info(),
+                &self.funder_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                escrow_amount,
+                false,
+                escrow_seeds,
+            )?;
+        }
+
+        // Delete `escrow` account
+        utils::close_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{error, state, utils, CancelEnglishAuction};
+use anchor_lang::prelude::*;
+
+impl<'info> CancelEnglishAuction<'info> {
+    pub fn process(&mut self, escrow_bump: u8, bid_escrow_bump: u8) -> Result<()> {
+        if self.auction.status != state::AuctionStatus::Created {
+            return Err(error::ErrorCode::InvalidAuctionStatus.into());
+        }
+
+        if self.auction.has_bids() {
+            return Err(error::ErrorCode::AuctionIsNotCancelable.into());
+        }
+
+        self.auction.status = state::AuctionStatus::Canceled;
+
+        if self.auction.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+        }
+
+        let auction_key = self.auction.key();
+
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.funder.key.as_ref(),
+            auction_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        // Transfer `funder` tokens from `escrow`
+        // (native `SOL`'s will be returned while `escrow` deletion)
+        if !self.auction.is_base_native() {
+            let escrow_amount = utils::escrow_amount(&self.escrow.to_account_info(), false)?;
+
+            utils::transfer_from_escrow(
+                &self.escrow.to_account_info(),
+                &self.funder_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                escrow_amount,
+                false,
+                escrow_seeds,
+            )?;
+        }
+
+        // Delete `escrow` account
+        utils::close_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        // Delete empty `bid_escrow` account
+        utils::close_escrow(
+            &self.bid_escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_quote_native(),
+            &[
+                utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(),
+                auction_key.as_ref(),
+                &[bid_escrow_bump],
+            ],
+        )?;
+
+        Ok(())
+    }
+}
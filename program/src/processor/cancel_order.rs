@@ -1,60 +1,115 @@
-use crate::{error, state, utils, CancelOrder};
-use anchor_lang::prelude::*;
-use anchor_spl::token;
+use crate::{error, event, state, utils, CancelOrder};
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+/// Check, that `Order` can be canceled by `funder` at `now`.
+pub fn check_cancel(order: &state::Order, now: UnixTimestamp) -> Result<()> {
+    // Arbitrated `Order` can be canceled if delivery wasn't claimed before
+    // `expire_date` or dispute wasn't resolved by arbiter in time
+    match order.status()? {
+        state::OrderStatus::Created => {
+            if order.is_arbitrated() && now <= order.expire_date {
+                return Err(error::ErrorCode::CancelIsLocked.into());
+            }
+        }
+        state::OrderStatus::Disputed => {
+            if now < order.arbitration_end_date() {
+                return Err(error::ErrorCode::DisputeWindowIsOpen.into());
+            }
+        }
+        _ => return Err(error::ErrorCode::InvalidOrderStatus.into()),
+    }
+
+    if let Some(cancel_locked_until) = order.cancel_locked_until() {
+        if now < cancel_locked_until {
+            return Err(error::ErrorCode::CancelIsLocked.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Return `escrow` tokens to `funder_token_account` and delete `escrow`
+/// (rent is refunded to `rent_payer`).
+pub fn return_escrow<'a>(
+    escrow: &AccountInfo<'a>,
+    funder: &AccountInfo<'a>,
+    rent_payer: &AccountInfo<'a>,
+    funder_token_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    is_native: bool,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    // Transfer `funder` tokens from `escrow`
+    // (native `SOL`'s will be returned while `escrow` deletion)
+    if !is_native {
+        let escrow_amount = utils::escrow_amount(escrow, false)?;
+
+        utils::transfer_from_escrow(
+            escrow,
+            funder_token_account,
+            token_program,
+            escrow_amount,
+            false,
+            escrow_seeds,
+        )?;
+    }
+
+    // Delete `escrow` account
+    utils::close_order_escrow(
+        escrow,
+        funder,
+        rent_payer,
+        token_program,
+        is_native,
+        escrow_seeds,
+    )
+}
 
 impl<'info> CancelOrder<'info> {
     pub fn process(&mut self, escrow_bump: u8) -> Result<()> {
-        if self.order.status != state::OrderStatus::Created {
-            return Err(error::ErrorCode::InvalidOrderStatus.into());
-        }
+        let mut order = self.order.load_mut()?;
 
-        self.order.status = state::OrderStatus::Canceled;
+        let now = self.clock_sysvar.unix_timestamp;
 
-        // Delete `escrow` account
-        if self.order.is_base_native() {
-            if self.funder_token_account.key != self.funder.key {
-                return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
-            }
+        check_cancel(&order, now)?;
 
-            utils::delete_account(
-                &self.escrow.to_account_info(),
-                &self.funder.to_account_info(),
-            )?;
+        order.status = state::OrderStatus::Canceled as u8;
+        self.order_book.remove(&self.order.key());
+
+        // `Order` canceled after `expire_date` is counted as expired
+        if now > order.expire_date {
+            self.funder_profile.orders_expired += 1;
         } else {
-            // Transfer `funder` tokens from `escrow`
-            let escrow_token_account = token::TokenAccount::try_deserialize_unchecked(
-                &mut self.escrow.data.borrow().as_ref(),
-            )?;
-
-            let order_key = self.order.key();
-
-            let signer_seeds: &[&[&[u8]]] = &[&[
-                utils::ORDER_ESCROW_PREFIX.as_bytes(),
-                self.funder.key.as_ref(),
-                order_key.as_ref(),
-                &[escrow_bump],
-            ]];
-
-            let cpi_program = self.token_program.to_account_info();
-            let cpi_accounts = token::Transfer {
-                from: self.escrow.to_account_info(),
-                to: self.funder_token_account.to_account_info(),
-                authority: self.escrow.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-            token::transfer(cpi_ctx, escrow_token_account.amount)?;
-
-            // Close `escrow` account
-            let cpi_program = self.token_program.to_account_info();
-            let cpi_accounts = token::CloseAccount {
-                account: self.escrow.to_account_info(),
-                destination: self.funder.to_account_info(),
-                authority: self.escrow.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-            token::close_account(cpi_ctx)?;
+            self.funder_profile.orders_canceled += 1;
+        }
+
+        if order.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
         }
 
+        let order_key = self.order.key();
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.funder.key.as_ref(),
+            order_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        return_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.rent_payer.to_account_info(),
+            &self.funder_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        emit!(event::OrderCanceled {
+            order: order_key,
+            reference: order.reference,
+        });
+
         Ok(())
     }
 }
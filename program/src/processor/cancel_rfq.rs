@@ -0,0 +1,50 @@
+use crate::{error, state, utils, CancelRfq};
+use anchor_lang::prelude::*;
+
+impl<'info> CancelRfq<'info> {
+    pub fn process(&mut self, escrow_bump: u8) -> Result<()> {
+        if self.rfq.status != state::RfqStatus::Created {
+            return Err(error::ErrorCode::InvalidRfqStatus.into());
+        }
+
+        self.rfq.status = state::RfqStatus::Canceled;
+
+        if self.rfq.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+        }
+
+        let rfq_key = self.rfq.key();
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.funder.key.as_ref(),
+            rfq_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        // Transfer `funder` tokens from `escrow`
+        // (native `SOL`'s will be returned while `escrow` deletion)
+        if !self.rfq.is_base_native() {
+            let escrow_amount = utils::escrow_amount(&self.escrow.to_account_info(), false)?;
+
+            utils::transfer_from_escrow(
+                &self.escrow.to_account_info(),
+                &self.funder_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                escrow_amount,
+                false,
+                escrow_seeds,
+            )?;
+        }
+
+        // Delete `escrow` account
+        utils::close_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.rfq.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{error, event, state, ClaimDelivery};
+use anchor_lang::prelude::*;
+
+impl<'info> ClaimDelivery<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let mut order = self.order.load_mut()?;
+
+        if !order.is_arbitrated() {
+            return Err(error::ErrorCode::OrderIsNotArbitrated.into());
+        }
+
+        if order.status()? != state::OrderStatus::Created {
+            return Err(error::ErrorCode::InvalidOrderStatus.into());
+        }
+
+        let now = self.clock_sysvar.unix_timestamp;
+
+        if now > order.expire_date {
+            return Err(error::ErrorCode::OrderIsExpired.into());
+        }
+
+        if let Some(start_date) = order.start_date() {
+            if start_date > now {
+                return Err(error::ErrorCode::OrderIsNotStarted.into());
+            }
+        }
+
+        order.status = state::OrderStatus::DeliveryClaimed as u8;
+        order.delivery_claimed_at = now;
+
+        emit!(event::DeliveryClaimed {
+            order: self.order.key(),
+            recipient: self.recipient.key(),
+            claimed_at: now,
+            reference: order.reference,
+        });
+
+        Ok(())
+    }
+}
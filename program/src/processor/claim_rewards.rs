@@ -0,0 +1,38 @@
+use crate::{error, utils, ClaimRewards};
+use anchor_lang::prelude::*;
+
+impl<'info> ClaimRewards<'info> {
+    pub fn process(&mut self, reward_vault_bump: u8) -> Result<()> {
+        let index = self
+            .staking_pool
+            .reward_index(self.reward_mint.key)
+            .ok_or(error::ErrorCode::InvalidRewardMint)?;
+
+        self.stake.settle(&self.staking_pool);
+
+        let amount = self.stake.rewards[index].pending;
+        self.stake.rewards[index].pending = 0;
+
+        let is_native = self.reward_mint.key() == System::id();
+
+        if is_native && self.wallet_token_account.key != self.wallet.key {
+            return Err(error::ErrorCode::StakerAccountWalletMismatch.into());
+        }
+
+        let reward_mint_key = self.reward_mint.key();
+        let reward_vault_seeds: &[&[u8]] = &[
+            utils::REWARD_VAULT_PREFIX.as_bytes(),
+            reward_mint_key.as_ref(),
+            &[reward_vault_bump],
+        ];
+
+        utils::transfer_from_escrow(
+            &self.reward_vault.to_account_info(),
+            &self.wallet_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount,
+            is_native,
+            reward_vault_seeds,
+        )
+    }
+}
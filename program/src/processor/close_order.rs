@@ -0,0 +1,28 @@
+use crate::{error, event, state, CloseOrder};
+use anchor_lang::prelude::*;
+
+impl<'info> CloseOrder<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        if self.authority.key != self.funder.key && self.authority.key != self.rent_payer.key {
+            return Err(error::ErrorCode::InvalidCloseAuthority.into());
+        }
+
+        let order = self.order.load()?;
+
+        // Only finished `Order` (`escrow` is already deleted) can be closed
+        match order.status()? {
+            state::OrderStatus::Canceled
+            | state::OrderStatus::Completed
+            | state::OrderStatus::Declined => {}
+            _ => return Err(error::ErrorCode::InvalidOrderStatus.into()),
+        }
+
+        emit!(event::OrderClosed {
+            order: self.order.key(),
+            rent_payer: order.rent_payer,
+            reference: order.reference,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{error, state, utils, CommitBid};
+use anchor_lang::prelude::*;
+
+impl<'info> CommitBid<'info> {
+    pub fn process(
+        &mut self,
+        deposit_bump: u8,
+        commitment: [u8; 32],
+        deposit_amount: u64,
+    ) -> Result<()> {
+        if self.auction.status != state::AuctionStatus::Created {
+            return Err(error::ErrorCode::InvalidAuctionStatus.into());
+        }
+
+        if let Some(start_date) = self.auction.start_date {
+            if start_date > self.clock_sysvar.unix_timestamp {
+                return Err(error::ErrorCode::AuctionIsNotStarted.into());
+            }
+        }
+
+        if self.clock_sysvar.unix_timestamp >= self.auction.commit_end_date {
+            return Err(error::ErrorCode::CommitPhaseIsOver.into());
+        }
+
+        if self.bidder.key() == self.auction.funder {
+            return Err(error::ErrorCode::BidderMatchFunder.into());
+        }
+
+        if deposit_amount < self.auction.min_bid_amount {
+            return Err(error::ErrorCode::InvalidBidAmount.into());
+        }
+
+        if self.auction.is_quote_native() && self.bidder_token_account.key != self.bidder.key {
+            return Err(error::ErrorCode::BidderAccountWalletMismatch.into());
+        }
+
+        if !utils::is_token_account_of(
+            &self.receive_token_account.to_account_info(),
+            self.bidder.key,
+            &self.auction.base_mint,
+        )? {
+            return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        }
+
+        self.bid.status = state::BidStatus::Committed;
+        self.bid.auction = self.auction.key();
+        self.bid.bidder = self.bidder.key();
+        self.bid.deposit = self.deposit.key();
+        self.bid.refund_token_account = self.bidder_token_account.key();
+        self.bid.receive_token_account = self.receive_token_account.key();
+        self.bid.deposit_amount = deposit_amount;
+        self.bid.commitment = commitment;
+        self.bid.amount = 0;
+
+        self.auction.bids_count += 1;
+
+        // Create deposit account according to bids mint and lock deposit amount
+        utils::create_escrow(
+            &self.bidder.to_account_info(),
+            &self.bidder.to_account_info(),
+            &self.bidder_token_account.to_account_info(),
+            &self.deposit.to_account_info(),
+            &self.quote_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            deposit_amount,
+            self.auction.is_quote_native(),
+            &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.bidder.key.as_ref(),
+                self.auction.key().as_ref(),
+                &[deposit_bump],
+            ],
+        )?;
+
+        Ok(())
+    }
+}
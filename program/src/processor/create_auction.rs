@@ -0,0 +1,86 @@
+use crate::{error, state, utils, CreateAuction};
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+impl<'info> CreateAuction<'info> {
+    pub fn process(
+        &mut self,
+        escrow_bump: u8,
+        base_amount: u64,
+        min_bid_amount: u64,
+        start_date: Option<UnixTimestamp>,
+        commit_end_date: UnixTimestamp,
+        reveal_end_date: UnixTimestamp,
+    ) -> Result<()> {
+        self.auction.status = state::AuctionStatus::Created;
+
+        self.auction.base_amount = base_amount;
+        self.auction.min_bid_amount = min_bid_amount;
+
+        self.auction.base_mint = *self.escrow_mint.key;
+        self.auction.quote_mint = *self.quote_mint.key;
+
+        self.auction.funder = *self.funder.key;
+
+        self.auction.escrow = self.escrow.key();
+        self.auction.quote_token_account = *self.quote_token_account.key;
+
+        self.auction.start_date = start_date;
+        self.auction.commit_end_date = commit_end_date;
+        self.auction.reveal_end_date = reveal_end_date;
+
+        self.auction.bids_count = 0;
+        self.auction.highest_bid_amount = 0;
+        self.auction.winner = Pubkey::default();
+
+        if self.auction.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+        }
+
+        // Create escrow account according to `funder` mint and lock base amount
+        utils::create_escrow(
+            &self.funder.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.funder_token_account.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.escrow_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            base_amount,
+            self.auction.is_base_native(),
+            &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.funder.key.as_ref(),
+                self.auction.key().as_ref(),
+                &[escrow_bump],
+            ],
+        )?;
+
+        // Check if quote token account is valid in native `SOL` context
+        if self.auction.is_quote_native() && self.quote_token_account.key() != self.funder.key() {
+            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        }
+
+        // Check commit end date
+        if self.clock_sysvar.unix_timestamp >= self.auction.commit_end_date {
+            return Err(error::ErrorCode::ExpireDateInThePast.into());
+        }
+
+        // Check reveal end date
+        if self.auction.commit_end_date >= self.auction.reveal_end_date {
+            return Err(error::ErrorCode::InvalidAuctionDates.into());
+        }
+
+        // Check start date
+        if let Some(start_date) = self.auction.start_date {
+            if self.clock_sysvar.unix_timestamp > start_date {
+                return Err(error::ErrorCode::StartDateInThePast.into());
+            }
+
+            if start_date >= self.auction.commit_end_date {
+                return Err(error::ErrorCode::InvalidAuctionDates.into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{error, state, utils, CreateEnglishAuction};
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+impl<'info> CreateEnglishAuction<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn process(
+        &mut self,
+        escrow_bump: u8,
+        bid_escrow_bump: u8,
+        base_amount: u64,
+        min_bid_amount: u64,
+        min_bid_increment: u64,
+        start_date: Option<UnixTimestamp>,
+        expire_date: UnixTimestamp,
+        extension_window: i64,
+    ) -> Result<()> {
+        self.auction.status = state::AuctionStatus::Created;
+
+        self.auction.base_amount = base_amount;
+        self.auction.min_bid_amount = min_bid_amount;
+        self.auction.min_bid_increment = min_bid_increment;
+
+        self.auction.base_mint = *self.escrow_mint.key;
+        self.auction.quote_mint = *self.quote_mint.key;
+
+        self.auction.funder = *self.funder.key;
+
+        self.auction.escrow = self.escrow.key();
+        self.auction.bid_escrow = self.bid_escrow.key();
+        self.auction.quote_token_account = *self.quote_token_account.key;
+
+        self.auction.start_date = start_date;
+        self.auction.expire_date = expire_date;
+        self.auction.extension_window = extension_window.max(0);
+
+        self.auction.highest_bid_amount = 0;
+        self.auction.highest_bidder = Pubkey::default();
+        self.auction.highest_bidder_receive_account = Pubkey::default();
+
+        if self.auction.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+        }
+
+        // Create escrow account according to `funder` mint and lock base amount
+        utils::create_escrow(
+            &self.funder.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.funder_token_account.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.escrow_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            base_amount,
+            self.auction.is_base_native(),
+            &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.funder.key.as_ref(),
+                self.auction.key().as_ref(),
+                &[escrow_bump],
+            ],
+        )?;
+
+        // Create empty bids escrow account according to bids mint
+        utils::init_escrow(
+            &self.funder.to_account_info(),
+            &self.bid_escrow.to_account_info(),
+            &self.quote_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            self.auction.is_quote_native(),
+            &[
+                utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(),
+                self.auction.key().as_ref(),
+                &[bid_escrow_bump],
+            ],
+        )?;
+
+        // Check if quote token account is valid in native `SOL` context
+        if self.auction.is_quote_native() && self.quote_token_account.key() != self.funder.key() {
+            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        }
+
+        // Check expire date
+        if self.clock_sysvar.unix_timestamp >= self.auction.expire_date {
+            return Err(error::ErrorCode::ExpireDateInThePast.into());
+        }
+
+        // Check start date
+        if let Some(start_date) = self.auction.start_date {
+            if self.clock_sysvar.unix_timestamp > start_date {
+                return Err(error::ErrorCode::StartDateInThePast.into());
+            }
+
+            if start_date >= self.auction.expire_date {
+                return Err(error::ErrorCode::ExpireDateInThePast.into());
+            }
+        }
+
+        Ok(())
+    }
+}
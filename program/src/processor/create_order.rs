@@ -1,125 +1,274 @@
-use crate::{error, id, state, utils, CreateOrder};
+use crate::{error, event, state, utils, CreateOrder};
 use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
-use anchor_spl::token;
+
+/// Check optional `Order` dates before storing, as zero date is stored as unset.
+pub fn check_optional_dates(
+    start_date: Option<UnixTimestamp>,
+    cancel_locked_until: Option<UnixTimestamp>,
+) -> Result<()> {
+    if matches!(start_date, Some(start_date) if start_date <= 0) {
+        return Err(error::ErrorCode::StartDateInThePast.into());
+    }
+
+    if matches!(cancel_locked_until, Some(cancel_locked_until) if cancel_locked_until <= 0) {
+        return Err(error::ErrorCode::InvalidCancelLockDate.into());
+    }
+
+    Ok(())
+}
+
+/// Check `Order` dates and parameters combination at creation moment `now`.
+pub fn check_order(order: &state::Order, now: UnixTimestamp) -> Result<()> {
+    // Check expire date
+    if now >= order.expire_date {
+        return Err(error::ErrorCode::ExpireDateInThePast.into());
+    }
+
+    // Check start date
+    if let Some(start_date) = order.start_date() {
+        if now > start_date {
+            return Err(error::ErrorCode::StartDateInThePast.into());
+        }
+
+        if start_date >= order.expire_date {
+            return Err(error::ErrorCode::ExpireDateInThePast.into());
+        }
+    }
+
+    // Check cancel lock date
+    if let Some(cancel_locked_until) = order.cancel_locked_until() {
+        if now >= cancel_locked_until || cancel_locked_until > order.expire_date {
+            return Err(error::ErrorCode::InvalidCancelLockDate.into());
+        }
+    }
+
+    // Hash-time-locked `Order` is reclaimable by `funder` after timelock only
+    if order.is_htlc() && order.cancel_locked_until().is_none() {
+        return Err(error::ErrorCode::InvalidHtlcOrder.into());
+    }
+
+    // Hidden `Order` stores only `recipient` commitment
+    if order.is_hidden() && order.recipient != System::id() {
+        return Err(error::ErrorCode::InvalidHiddenOrder.into());
+    }
+
+    // Arbitrated `Order` is settled between named parties by release or arbiter
+    if order.is_arbitrated()
+        && (order.arbiter == order.funder
+            || order.arbiter == order.recipient
+            || order.recipient == System::id()
+            || order.dispute_window <= 0
+            || order.is_htlc()
+            || order.is_collection_offer()
+            || order.is_gated())
+    {
+        return Err(error::ErrorCode::InvalidArbitratedOrder.into());
+    }
+
+    // Check price curve
+    if order.floor_quote_amount > order.quote_amount {
+        return Err(error::ErrorCode::InvalidFloorQuoteAmount.into());
+    }
+
+    Ok(())
+}
+
+/// Write UTF-8 `memo` to `Order`.
+pub fn set_memo(order: &mut state::Order, memo: &str) -> Result<()> {
+    if memo.len() > state::ORDER_MEMO_LEN {
+        return Err(error::ErrorCode::MemoIsTooLong.into());
+    }
+
+    order.memo = [0; state::ORDER_MEMO_LEN];
+    order.memo[..memo.len()].copy_from_slice(memo.as_bytes());
+
+    Ok(())
+}
 
 impl<'info> CreateOrder<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn process(
         &mut self,
         escrow_bump: u8,
+        funder_profile_bump: u8,
         base_amount: u64,
         quote_amount: u64,
         start_date: Option<UnixTimestamp>,
         expire_date: UnixTimestamp,
+        price_curve: state::PriceCurve,
+        floor_quote_amount: u64,
+        referral_share_bps: u16,
+        collection_offer: bool,
+        cancel_locked_until: Option<UnixTimestamp>,
+        hashlock: Option<[u8; 32]>,
+        arbitration: Option<state::ArbitrationArgs>,
+        memo: Option<String>,
+        reference: Option<[u8; 32]>,
+        recipient_commitment: Option<[u8; 32]>,
+        gate: Option<state::Gate>,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
-        self.order.status = state::OrderStatus::Created;
+        let mut order = self.order.load_init()?;
 
-        self.order.base_amount = base_amount;
-        self.order.quote_amount = quote_amount;
+        order.status = state::OrderStatus::Created as u8;
 
-        self.order.base_mint = self.escrow_mint.key.clone();
-        self.order.quote_mint = self.quote_mint.key.clone();
+        order.base_amount = base_amount;
+        order.quote_amount = quote_amount;
 
-        self.order.funder = self.funder.key.clone();
-        self.order.recipient = self.recipient.key.clone();
+        order.base_mint = *self.escrow_mint.key;
+        order.quote_mint = *self.quote_mint.key;
 
-        self.order.escrow = self.escrow.key().clone();
-        self.order.quote_token_account = self.quote_token_account.key.clone();
+        order.funder = *self.funder.key;
+        order.recipient = *self.recipient.key;
+        order.rent_payer = *self.rent_payer.key;
 
-        self.order.start_date = start_date;
-        self.order.expire_date = expire_date;
+        order.funder_token_account = *self.funder_token_account.key;
+        order.escrow = self.escrow.key();
+        order.quote_token_account = *self.quote_token_account.key;
 
-        if self.funder.key == self.recipient.key {
-            return Err(error::ErrorCode::RecipientMatchFunder.into());
-        }
+        check_optional_dates(start_date, cancel_locked_until)?;
 
-        // Create escrow account according to `funder` mint
-        if self.order.is_base_native() {
-            if self.funder_token_account.key != self.funder.key {
-                return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
-            }
+        order.start_date = start_date.unwrap_or(0);
+        order.expire_date = expire_date;
 
-            // Create native escrow account to hold `funder`'s native `SOL`'s
-            utils::sys_create_account(
-                &self.funder.to_account_info(),
-                &self.escrow.to_account_info(),
-                self.rent_sysvar
-                    .minimum_balance(utils::ORDER_ESCROW_NATIVE_SIZE),
-                utils::ORDER_ESCROW_NATIVE_SIZE,
-                &id(),
-                &[
-                    utils::ORDER_ESCROW_PREFIX.as_bytes(),
-                    self.funder.key.as_ref(),
-                    self.order.key().as_ref(),
-                    &[escrow_bump],
-                ],
-            )?;
-
-            // Transfer native `SOL`'s to escrow
-            utils::sys_transfer(
-                &self.funder_token_account.to_account_info(),
-                &self.escrow.to_account_info(),
-                base_amount,
-                &[],
-            )?;
+        order.price_curve = price_curve as u8;
+        order.floor_quote_amount = if price_curve == state::PriceCurve::Fixed {
+            quote_amount
         } else {
-            // Create `spl` escrow account to hold `funder`'s `spl_token`'s
-            utils::sys_create_account(
-                &self.funder.to_account_info(),
-                &self.escrow.to_account_info(),
-                self.rent_sysvar.minimum_balance(token::TokenAccount::LEN),
-                token::TokenAccount::LEN,
-                &token::Token::id(),
-                &[
-                    utils::ORDER_ESCROW_PREFIX.as_bytes(),
-                    self.funder.key.as_ref(),
-                    self.order.key().as_ref(),
-                    &[escrow_bump],
-                ],
-            )?;
-
-            // Initialize escrow `spl_token` account
-            let cpi_program = self.token_program.to_account_info();
-            let cpi_accounts = token::InitializeAccount {
-                account: self.escrow.to_account_info(),
-                mint: self.escrow_mint.to_account_info(),
-                authority: self.escrow.to_account_info(),
-                rent: self.rent_sysvar.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &[]);
-            token::initialize_account(cpi_ctx)?;
-
-            // Transfer base amount to `spl_token` escrow
-            let cpi_program = self.token_program.to_account_info();
-            let cpi_accounts = token::Transfer {
-                from: self.funder_token_account.to_account_info(),
-                to: self.escrow.to_account_info(),
-                authority: self.funder.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &[]);
-            token::transfer(cpi_ctx, base_amount)?;
+            floor_quote_amount
+        };
+
+        order.created_at = self.clock_sysvar.unix_timestamp;
+        order.cancel_locked_until = cancel_locked_until.unwrap_or(0);
+        order.hashlock = hashlock.unwrap_or([0; 32]);
+        order.preimage = [0; 32];
+
+        if let Some(arbitration) = arbitration {
+            order.arbiter = arbitration.arbiter;
+            order.dispute_window = arbitration.dispute_window;
+        } else {
+            order.arbiter = Pubkey::default();
+            order.dispute_window = 0;
         }
+        order.delivery_claimed_at = 0;
+        order.disputed_at = 0;
 
-        // Check if quote token account is valid in native `SOL` context
-        if self.order.is_quote_native() && self.quote_token_account.key() != self.funder.key() {
-            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        let memo = memo.unwrap_or_default();
+        set_memo(&mut order, &memo)?;
+        order.reference = reference.unwrap_or([0; 32]);
+        order.recipient_commitment = recipient_commitment.unwrap_or([0; 32]);
+
+        // Executing wallet should hold gate tokens (`System::id()` is `Pubkey::default()`)
+        let (gate_mint, gate_min_amount) = match gate {
+            Some(state::Gate::MinBalance { mint, min_amount }) => (mint, min_amount),
+            Some(state::Gate::Collection { collection }) => (collection, 0),
+            None => (Pubkey::default(), 0),
+        };
+
+        if gate.is_some() && gate_mint == Pubkey::default() {
+            return Err(error::ErrorCode::InvalidGate.into());
         }
 
-        // Check expire date
-        if self.clock_sysvar.unix_timestamp >= self.order.expire_date {
-            return Err(error::ErrorCode::ExpireDateInThePast.into());
+        if let Some(state::Gate::MinBalance { min_amount: 0, .. }) = gate {
+            return Err(error::ErrorCode::InvalidGate.into());
         }
 
-        // Check start date
-        if let Some(start_date) = self.order.start_date {
-            if self.clock_sysvar.unix_timestamp > start_date {
-                return Err(error::ErrorCode::StartDateInThePast.into());
+        order.gate_mint = gate_mint;
+        order.gate_min_amount = gate_min_amount;
+
+        // Set `funder` side referrer
+        if let Some(referrer) = remaining_accounts.first() {
+            let referrer = Account::<state::Referrer>::try_from(referrer)?;
+
+            if referrer.mint != order.base_mint {
+                return Err(error::ErrorCode::ReferrerMismatch.into());
             }
 
-            if start_date >= self.order.expire_date {
-                return Err(error::ErrorCode::ExpireDateInThePast.into());
+            order.referrer = referrer.key();
+            order.referral_share_bps = referral_share_bps;
+        } else {
+            order.referrer = Pubkey::default();
+            order.referral_share_bps = 0;
+        }
+
+        let protocol = state::Protocol::load_or_default(&self.protocol)?;
+
+        if referral_share_bps > protocol.max_referral_share_bps {
+            return Err(error::ErrorCode::InvalidReferralShare.into());
+        }
+
+        // Quote mint is collection mint, NFT token account of `funder` is unknown
+        // until execution
+        if collection_offer {
+            if order.is_quote_native() || self.quote_token_account.key() != self.funder.key() {
+                return Err(error::ErrorCode::InvalidCollectionOffer.into());
             }
+
+            order.quote_collection = order.quote_mint;
+        } else {
+            order.quote_collection = Pubkey::default();
+        }
+
+        // Index `Order` in mint pair `OrderBook`
+        order.order_book = self.order_book.key();
+        self.order_book.base_mint = order.base_mint;
+        self.order_book.quote_mint = order.quote_mint;
+        self.order_book.insert_or_evict(
+            order.to_order_book_entry(&self.order.key()),
+            self.clock_sysvar.unix_timestamp,
+        );
+
+        if self.funder.key == self.recipient.key {
+            return Err(error::ErrorCode::RecipientMatchFunder.into());
+        }
+
+        if order.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
         }
 
+        // Track `funder` trading statistics
+        self.funder_profile.wallet = self.funder.key();
+        self.funder_profile.bump = funder_profile_bump;
+        self.funder_profile.orders_created += 1;
+
+        // Create escrow account according to `funder` mint and lock base amount
+        utils::create_escrow(
+            &self.rent_payer.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.funder_token_account.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.escrow_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            base_amount,
+            order.is_base_native(),
+            &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.funder.key.as_ref(),
+                self.order.key().as_ref(),
+                &[escrow_bump],
+            ],
+        )?;
+
+        // Check if quote token account is valid in native `SOL` context
+        if order.is_quote_native() && self.quote_token_account.key() != self.funder.key() {
+            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        }
+
+        check_order(&order, self.clock_sysvar.unix_timestamp)?;
+
+        emit!(event::OrderCreated {
+            order: self.order.key(),
+            funder: order.funder,
+            recipient: order.recipient,
+            base_mint: order.base_mint,
+            quote_mint: order.quote_mint,
+            base_amount: order.base_amount,
+            quote_amount: order.quote_amount,
+            reference: order.reference,
+            memo,
+        });
+
         Ok(())
     }
 }
@@ -0,0 +1,18 @@
+use crate::{error, utils, CreateReferrer};
+use anchor_lang::prelude::*;
+
+impl<'info> CreateReferrer<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        self.referrer.wallet = self.wallet.key();
+        self.referrer.mint = self.mint.key();
+        self.referrer.token_account = self.token_account.key();
+        self.referrer.total_amount = 0;
+        self.referrer.payouts_count = 0;
+
+        if !utils::is_token_account_of(&self.token_account, self.wallet.key, self.mint.key)? {
+            return Err(error::ErrorCode::ReferrerAccountWalletMismatch.into());
+        }
+
+        Ok(())
+    }
+}
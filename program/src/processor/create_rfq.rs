@@ -0,0 +1,50 @@
+use crate::{error, state, utils, CreateRfq};
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+impl<'info> CreateRfq<'info> {
+    pub fn process(
+        &mut self,
+        escrow_bump: u8,
+        base_amount: u64,
+        expire_date: UnixTimestamp,
+    ) -> Result<()> {
+        self.rfq.status = state::RfqStatus::Created;
+        self.rfq.base_amount = base_amount;
+        self.rfq.base_mint = *self.escrow_mint.key;
+        self.rfq.funder = *self.funder.key;
+        self.rfq.escrow = self.escrow.key();
+        self.rfq.expire_date = expire_date;
+        self.rfq.quotes_count = 0;
+        self.rfq.accepted_quote = Pubkey::default();
+
+        if self.rfq.is_base_native() && self.funder_token_account.key != self.funder.key {
+            return Err(error::ErrorCode::FunderAccountWalletMismatch.into());
+        }
+
+        // Create escrow account according to `funder` mint and lock base amount
+        utils::create_escrow(
+            &self.funder.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.funder_token_account.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.escrow_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            base_amount,
+            self.rfq.is_base_native(),
+            &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.funder.key.as_ref(),
+                self.rfq.key().as_ref(),
+                &[escrow_bump],
+            ],
+        )?;
+
+        // Check expire date
+        if self.clock_sysvar.unix_timestamp >= self.rfq.expire_date {
+            return Err(error::ErrorCode::ExpireDateInThePast.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{error, state, CreateRingSwap};
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+impl<'info> CreateRingSwap<'info> {
+    pub fn process(
+        &mut self,
+        legs: Vec<state::RingSwapLegArgs>,
+        expire_date: UnixTimestamp,
+    ) -> Result<()> {
+        if legs.len() < state::RING_SWAP_MIN_LEGS || legs.len() > state::RING_SWAP_MAX_LEGS {
+            return Err(error::ErrorCode::InvalidRingSwapLegs.into());
+        }
+
+        // Check participants uniqueness
+        for (i, leg) in legs.iter().enumerate() {
+            if legs[..i].iter().any(|l| l.participant == leg.participant) {
+                return Err(error::ErrorCode::InvalidRingSwapLegs.into());
+            }
+        }
+
+        // Check if receive token accounts are valid in native `SOL` context
+        for (i, leg) in legs.iter().enumerate() {
+            let next_leg = &legs[(i + 1) % legs.len()];
+
+            if leg.mint == System::id() && leg.receive_token_account != next_leg.participant {
+                return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+            }
+        }
+
+        // Check expire date
+        if self.clock_sysvar.unix_timestamp >= expire_date {
+            return Err(error::ErrorCode::ExpireDateInThePast.into());
+        }
+
+        self.ring_swap.status = state::RingSwapStatus::Created;
+        self.ring_swap.creator = self.creator.key();
+        self.ring_swap.expire_date = expire_date;
+        self.ring_swap.legs = legs
+            .into_iter()
+            .map(|leg| state::RingSwapLeg {
+                participant: leg.participant,
+                mint: leg.mint,
+                amount: leg.amount,
+                receive_token_account: leg.receive_token_account,
+                refund_token_account: Pubkey::default(),
+                deposit: Pubkey::default(),
+                deposit_bump: 0,
+                is_funded: false,
+            })
+            .collect();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{error, event, state, utils, DeclineOrder};
+use anchor_lang::prelude::*;
+
+impl<'info> DeclineOrder<'info> {
+    pub fn process(&mut self, escrow_bump: u8) -> Result<()> {
+        let mut order = self.order.load_mut()?;
+
+        if order.status()? != state::OrderStatus::Created {
+            return Err(error::ErrorCode::InvalidOrderStatus.into());
+        }
+
+        order.status = state::OrderStatus::Declined as u8;
+        self.order_book.remove(&self.order.key());
+
+        let order_key = self.order.key();
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.funder.key.as_ref(),
+            order_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        // Transfer `funder` tokens from `escrow` to recorded `funder_token_account`
+        // (native `SOL`'s will be returned while `escrow` deletion)
+        if !order.is_base_native() {
+            let escrow_amount = utils::escrow_amount(&self.escrow.to_account_info(), false)?;
+
+            utils::transfer_from_escrow(
+                &self.escrow.to_account_info(),
+                &self.funder_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                escrow_amount,
+                false,
+                escrow_seeds,
+            )?;
+        }
+
+        // Delete `escrow` account
+        utils::close_order_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.rent_payer.to_account_info(),
+            &self.token_program.to_account_info(),
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        emit!(event::OrderDeclined {
+            order: order_key,
+            reference: order.reference,
+        });
+
+        Ok(())
+    }
+}
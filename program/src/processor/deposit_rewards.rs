@@ -0,0 +1,54 @@
+use crate::{error, utils, DepositRewards};
+use anchor_lang::prelude::*;
+
+impl<'info> DepositRewards<'info> {
+    pub fn process(&mut self, reward_vault_bump: u8, amount: u64) -> Result<()> {
+        // Rewards without stakers can't be claimed by anyone
+        if self.staking_pool.total_staked == 0 {
+            return Err(error::ErrorCode::StakingPoolIsEmpty.into());
+        }
+
+        if self
+            .staking_pool
+            .distribute(self.reward_mint.key, amount)
+            .is_none()
+        {
+            return Err(error::ErrorCode::TooManyRewardMints.into());
+        }
+
+        let is_native = self.reward_mint.key() == System::id();
+
+        if is_native && self.depositor_token_account.key != self.depositor.key {
+            return Err(error::ErrorCode::DepositorAccountWalletMismatch.into());
+        }
+
+        let reward_mint_key = self.reward_mint.key();
+        let reward_vault_seeds: &[&[u8]] = &[
+            utils::REWARD_VAULT_PREFIX.as_bytes(),
+            reward_mint_key.as_ref(),
+            &[reward_vault_bump],
+        ];
+
+        // Create vault on first deposit of `reward_mint`
+        if self.reward_vault.data_is_empty() {
+            utils::init_escrow(
+                &self.depositor.to_account_info(),
+                &self.reward_vault.to_account_info(),
+                &self.reward_mint.to_account_info(),
+                &self.rent_sysvar,
+                &self.token_program.to_account_info(),
+                is_native,
+                reward_vault_seeds,
+            )?;
+        }
+
+        utils::transfer(
+            &self.depositor_token_account.to_account_info(),
+            &self.reward_vault.to_account_info(),
+            &self.depositor.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount,
+            is_native,
+        )
+    }
+}
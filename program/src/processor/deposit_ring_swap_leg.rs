@@ -0,0 +1,60 @@
+use crate::{error, state, utils, DepositRingSwapLeg};
+use anchor_lang::prelude::*;
+
+impl<'info> DepositRingSwapLeg<'info> {
+    pub fn process(&mut self, deposit_bump: u8, leg_index: u8) -> Result<()> {
+        if self.ring_swap.status != state::RingSwapStatus::Created {
+            return Err(error::ErrorCode::InvalidRingSwapStatus.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp >= self.ring_swap.expire_date {
+            return Err(error::ErrorCode::RingSwapIsExpired.into());
+        }
+
+        let leg = self
+            .ring_swap
+            .legs
+            .get(leg_index as usize)
+            .cloned()
+            .ok_or(error::ErrorCode::RingSwapLegMismatch)?;
+
+        if leg.participant != self.participant.key() || leg.mint != self.mint.key() {
+            return Err(error::ErrorCode::RingSwapLegMismatch.into());
+        }
+
+        if leg.is_funded {
+            return Err(error::ErrorCode::RingSwapLegIsFunded.into());
+        }
+
+        if leg.is_native() && self.participant_token_account.key != self.participant.key {
+            return Err(error::ErrorCode::ParticipantAccountWalletMismatch.into());
+        }
+
+        // Create deposit account according to leg mint and lock leg amount
+        utils::create_escrow(
+            &self.participant.to_account_info(),
+            &self.participant.to_account_info(),
+            &self.participant_token_account.to_account_info(),
+            &self.deposit.to_account_info(),
+            &self.mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            leg.amount,
+            leg.is_native(),
+            &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.participant.key.as_ref(),
+                self.ring_swap.key().as_ref(),
+                &[deposit_bump],
+            ],
+        )?;
+
+        let leg = &mut self.ring_swap.legs[leg_index as usize];
+        leg.refund_token_account = self.participant_token_account.key();
+        leg.deposit = self.deposit.key();
+        leg.deposit_bump = deposit_bump;
+        leg.is_funded = true;
+
+        Ok(())
+    }
+}
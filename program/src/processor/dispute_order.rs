@@ -0,0 +1,31 @@
+use crate::{error, event, state, DisputeOrder};
+use anchor_lang::prelude::*;
+
+impl<'info> DisputeOrder<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let mut order = self.order.load_mut()?;
+
+        if order.status()? != state::OrderStatus::DeliveryClaimed {
+            return Err(error::ErrorCode::InvalidOrderStatus.into());
+        }
+
+        let now = self.clock_sysvar.unix_timestamp;
+
+        if now >= order.dispute_end_date() {
+            return Err(error::ErrorCode::DisputeWindowIsClosed.into());
+        }
+
+        order.status = state::OrderStatus::Disputed as u8;
+        order.disputed_at = now;
+
+        emit!(event::OrderDisputed {
+            order: self.order.key(),
+            funder: self.funder.key(),
+            arbiter: order.arbiter,
+            disputed_at: now,
+            reference: order.reference,
+        });
+
+        Ok(())
+    }
+}
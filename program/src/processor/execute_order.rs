@@ -1,109 +1,446 @@
-use crate::{error, state, utils, ExecuteOrder};
-use anchor_lang::prelude::*;
-use anchor_spl::token;
+use crate::{error, event, state, utils, ExecuteOrder};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{account_info::next_account_info, clock::UnixTimestamp},
+};
+
+/// Load `Referrer` and its payouts token account from remaining accounts.
+fn next_referrer<'a, 'info>(
+    remaining_accounts: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+    mint: &Pubkey,
+) -> Result<(Account<'info, state::Referrer>, &'a AccountInfo<'info>)>
+where
+    'info: 'a,
+{
+    let referrer = Account::<state::Referrer>::try_from(next_account_info(remaining_accounts)?)?;
+    let referrer_token_account = next_account_info(remaining_accounts)?;
+
+    if referrer.mint != *mint {
+        return Err(error::ErrorCode::ReferrerMismatch.into());
+    }
+
+    if referrer_token_account.key() != referrer.token_account {
+        return Err(error::ErrorCode::ReferrerAccountWalletMismatch.into());
+    }
+
+    Ok((referrer, referrer_token_account))
+}
+
+/// `Referrer`, its payouts token account and referral fee.
+pub type ReferrerPayout<'a, 'info> = (Account<'info, state::Referrer>, &'a AccountInfo<'info>, u64);
+
+/// Load `Order` maker referrer from remaining accounts (if `Order` has referrer)
+/// and calculate its share of `base_fee`.
+pub fn next_maker_referrer<'a, 'info>(
+    order: &state::Order,
+    protocol: &state::Protocol,
+    base_fee: u64,
+    remaining_accounts: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+) -> Result<Option<ReferrerPayout<'a, 'info>>>
+where
+    'info: 'a,
+{
+    if !order.has_referrer() {
+        return Ok(None);
+    }
+
+    let (referrer, referrer_token_account) = next_referrer(remaining_accounts, &order.base_mint)?;
+
+    if referrer.key() != order.referrer {
+        return Err(error::ErrorCode::ReferrerMismatch.into());
+    }
+
+    let referral_fee = protocol.referral_fee(base_fee, order.referral_share_bps);
+
+    Ok(Some((referrer, referrer_token_account, referral_fee)))
+}
+
+/// Record paid referral fee on `Referrer` account.
+fn record_referrer_payout(mut referrer: Account<state::Referrer>, referral_fee: u64) -> Result<()> {
+    referrer.total_amount += referral_fee;
+    referrer.payouts_count += 1;
+    referrer.exit(&crate::id())
+}
+
+/// Transfer base tokens `fee` from `escrow` to treasury and maker referrer.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_escrow_fee<'a, 'info>(
+    escrow: &AccountInfo<'info>,
+    treasury_token_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    protocol: &state::Protocol,
+    mint: &Pubkey,
+    fee: u64,
+    maker_referrer: Option<ReferrerPayout<'a, 'info>>,
+    is_native: bool,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    let referral_fee = maker_referrer.as_ref().map_or(0, |r| r.2);
+
+    if fee > referral_fee {
+        if !utils::is_token_account_of(treasury_token_account, &protocol.treasury, mint)? {
+            return Err(error::ErrorCode::TreasuryAccountMismatch.into());
+        }
+
+        utils::transfer_from_escrow(
+            escrow,
+            treasury_token_account,
+            token_program,
+            fee - referral_fee,
+            is_native,
+            escrow_seeds,
+        )?;
+    }
+
+    if let Some((referrer, referrer_token_account, referral_fee)) = maker_referrer {
+        if referral_fee > 0 {
+            utils::transfer_from_escrow(
+                escrow,
+                referrer_token_account,
+                token_program,
+                referral_fee,
+                is_native,
+                escrow_seeds,
+            )?;
+        }
+
+        record_referrer_payout(referrer, referral_fee)?;
+    }
+
+    Ok(())
+}
+
+/// Track executed `Order` in `funder` profile, protocol-wide and mint pair statistics.
+#[allow(clippy::too_many_arguments)]
+pub fn record_trade(
+    order: &state::Order,
+    quote_amount: u64,
+    funder_profile: &mut state::Profile,
+    stats: &mut state::Stats,
+    stats_bump: u8,
+    pair_stats: &mut state::PairStats,
+    pair_stats_bump: u8,
+    now: UnixTimestamp,
+) {
+    funder_profile.orders_completed += 1;
+    funder_profile.add_volume(&order.base_mint, order.base_amount);
+
+    if pair_stats.trades_count == 0 {
+        pair_stats.base_mint = order.base_mint;
+        pair_stats.quote_mint = order.quote_mint;
+        pair_stats.bump = pair_stats_bump;
+        stats.pairs_count += 1;
+    }
+
+    pair_stats.record_trade(order.base_amount, quote_amount, now);
+
+    stats.bump = stats_bump;
+    stats.trades_count += 1;
+
+    if order.is_base_native() {
+        stats.native_volume += order.base_amount as u128;
+    } else if order.is_quote_native() {
+        stats.native_volume += quote_amount as u128;
+    }
+}
+
+/// Check, that `recipient` passes `Order` token gate, gate accounts are loaded
+/// from remaining accounts.
+fn check_gate<'a, 'info>(
+    order: &state::Order,
+    recipient: &Pubkey,
+    remaining_accounts: &mut impl Iterator<Item = &'a AccountInfo<'info>>,
+) -> Result<()>
+where
+    'info: 'a,
+{
+    let gate = match order.gate() {
+        Some(gate) => gate,
+        None => return Ok(()),
+    };
+
+    let gate_token_account = next_account_info(remaining_accounts)?;
+
+    // Collection gate is passed by any NFT of verified collection
+    let (mint, min_amount) = match gate {
+        state::Gate::MinBalance { mint, min_amount } => (mint, min_amount),
+        state::Gate::Collection { collection } => {
+            let metadata =
+                state::Metadata::from_account_info(next_account_info(remaining_accounts)?)?;
+
+            if !metadata.is_verified_member_of(&collection) {
+                return Err(error::ErrorCode::GateIsNotPassed.into());
+            }
+
+            (metadata.mint, 1)
+        }
+    };
+
+    if utils::gate_amount(gate_token_account, recipient, &mint)? < min_amount {
+        return Err(error::ErrorCode::GateIsNotPassed.into());
+    }
+
+    Ok(())
+}
 
 impl<'info> ExecuteOrder<'info> {
-    pub fn process(&mut self, escrow_bump: u8) -> Result<()> {
-        if self.order.status != state::OrderStatus::Created {
+    #[allow(clippy::too_many_arguments)]
+    pub fn process(
+        &mut self,
+        escrow_bump: u8,
+        recipient_profile_bump: u8,
+        stats_bump: u8,
+        pair_stats_bump: u8,
+        taker_referral_share_bps: u16,
+        preimage: Option<[u8; 32]>,
+        recipient_salt: Option<[u8; 32]>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut order = self.order.load_mut()?;
+
+        // Hidden `Order` reveals `recipient`, that matches commitment
+        if order.is_hidden() {
+            let salt = recipient_salt.ok_or(error::ErrorCode::RecipientCommitmentMismatch)?;
+
+            if utils::hash_recipient(self.recipient.key, &salt) != order.recipient_commitment {
+                return Err(error::ErrorCode::RecipientCommitmentMismatch.into());
+            }
+
+            order.recipient = self.recipient.key();
+        } else if order.recipient == System::id() && order.is_gated() {
+            // Open token-gated `Order` is executed by any wallet, that passes gate
+            order.recipient = self.recipient.key();
+        } else if order.recipient != self.recipient.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+
+        let remaining_accounts = &mut remaining_accounts.iter();
+
+        check_gate(&order, self.recipient.key, remaining_accounts)?;
+
+        if order.status()? != state::OrderStatus::Created {
             return Err(error::ErrorCode::InvalidOrderStatus.into());
         }
 
-        self.order.status = state::OrderStatus::Completed;
+        if order.is_arbitrated() {
+            return Err(error::ErrorCode::OrderIsArbitrated.into());
+        }
+
+        order.status = state::OrderStatus::Completed as u8;
+        self.order_book.remove(&self.order.key());
+
+        // Reveal secret of hash-time-locked `Order`, so `funder` can read it on-chain
+        if order.is_htlc() {
+            let preimage = preimage.ok_or(error::ErrorCode::InvalidPreimage)?;
+
+            if utils::hash_preimage(&preimage) != order.hashlock {
+                return Err(error::ErrorCode::InvalidPreimage.into());
+            }
+
+            order.preimage = preimage;
+        }
+
+        let quote_amount = order.current_quote_amount(self.clock_sysvar.unix_timestamp);
+
+        // Track both sides trading statistics
+        record_trade(
+            &order,
+            quote_amount,
+            &mut self.funder_profile,
+            &mut self.stats,
+            stats_bump,
+            &mut self.pair_stats,
+            pair_stats_bump,
+            self.clock_sysvar.unix_timestamp,
+        );
+
+        self.recipient_profile.wallet = self.recipient.key();
+        self.recipient_profile.bump = recipient_profile_bump;
+        self.recipient_profile.orders_completed += 1;
+        self.recipient_profile
+            .add_volume(&order.quote_mint, quote_amount);
+
+        // Protocol fees are charged from both sides, referrers receive share of them
+        let protocol = state::Protocol::load_or_default(&self.protocol)?;
+        let base_fee = protocol.fee(order.base_amount);
+
+        // Collection offer accepts any NFT of `quote_collection`, that is sent
+        // to `funder` token account of NFT mint
+        let quote_token_account = if order.is_collection_offer() {
+            let metadata = next_account_info(remaining_accounts)?;
+            let funder_nft_token_account = next_account_info(remaining_accounts)?;
+
+            let metadata = state::Metadata::from_account_info(metadata)?;
+
+            if !metadata.is_verified_member_of(&order.quote_collection) {
+                return Err(error::ErrorCode::NftIsNotCollectionMember.into());
+            }
+
+            if !utils::is_token_account_of(funder_nft_token_account, &order.funder, &metadata.mint)?
+            {
+                return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+            }
+
+            funder_nft_token_account.clone()
+        } else {
+            self.quote_token_account.to_account_info()
+        };
+
+        let maker_referrer = next_maker_referrer(&order, &protocol, base_fee, remaining_accounts)?;
+
+        let taker_referrer = if taker_referral_share_bps > 0 {
+            Some(next_referrer(remaining_accounts, &order.quote_mint)?)
+        } else {
+            None
+        };
+
+        // NFT's are indivisible, so collection offer quote side is not charged.
+        // `recipient`, that holds p2swap tokens, pays fee of matching tier
+        let quote_fee = if order.is_collection_offer() {
+            0
+        } else if let Some(discount_token_account) = remaining_accounts.next() {
+            let discount_amount = utils::discount_amount(
+                discount_token_account,
+                self.recipient.key,
+                &protocol.discount_mint,
+            )?;
+
+            protocol.discounted_fee(quote_amount, discount_amount)
+        } else {
+            protocol.fee(quote_amount)
+        };
+
+        let taker_referrer = taker_referrer.map(|(referrer, referrer_token_account)| {
+            let referral_fee = protocol.referral_fee(quote_fee, taker_referral_share_bps);
+
+            (referrer, referrer_token_account, referral_fee)
+        });
+
+        let taker_referral_fee = taker_referrer.as_ref().map_or(0, |r| r.2);
 
         // Here `quote_token_account` is not checked for `funder` wallet
         // when `Order::is_quote_native`, because check was occur in `CreateOrder`
         // instruction
 
-        // Transfer funds from `recipient` to `funder` (`quote_token_account`).
-        if self.order.is_quote_native() {
-            if self.recipient_token_account.key != self.recipient.key {
-                return Err(error::ErrorCode::RecipientAccountWalletMismatch.into());
+        // Transfer funds from `recipient` to `funder` (`quote_token_account`
+        // or NFT token account of collection offer).
+        if order.is_quote_native() && self.recipient_token_account.key != self.recipient.key {
+            return Err(error::ErrorCode::RecipientAccountWalletMismatch.into());
+        }
+
+        utils::transfer(
+            &self.recipient_token_account.to_account_info(),
+            &quote_token_account,
+            &self.recipient.to_account_info(),
+            &self.token_program.to_account_info(),
+            quote_amount - quote_fee,
+            order.is_quote_native(),
+        )?;
+
+        // Transfer quote tokens fee from `recipient` to treasury and referrer.
+        if quote_fee > taker_referral_fee {
+            if !utils::is_token_account_of(
+                &self.treasury_quote_token_account,
+                &protocol.treasury,
+                &order.quote_mint,
+            )? {
+                return Err(error::ErrorCode::TreasuryAccountMismatch.into());
             }
 
-            utils::sys_transfer(
+            utils::transfer(
                 &self.recipient_token_account.to_account_info(),
-                &self.quote_token_account.to_account_info(),
-                self.order.quote_amount,
-                &[],
+                &self.treasury_quote_token_account.to_account_info(),
+                &self.recipient.to_account_info(),
+                &self.token_program.to_account_info(),
+                quote_fee - taker_referral_fee,
+                order.is_quote_native(),
             )?;
-        } else {
-            let cpi_program = self.token_program.to_account_info();
-            let cpi_accounts = token::Transfer {
-                from: self.recipient_token_account.to_account_info(),
-                to: self.quote_token_account.to_account_info(),
-                authority: self.recipient.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, &[]);
-            token::transfer(cpi_ctx, self.order.quote_amount)?;
         }
 
-        // Transfer funds from `escrow` to `recipient` (`recipient_receive_token_account`).
-        if self.order.is_base_native() {
-            if self.recipient_receive_token_account.key != self.recipient.key {
-                return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        if let Some((referrer, referrer_token_account, referral_fee)) = taker_referrer {
+            if referral_fee > 0 {
+                utils::transfer(
+                    &self.recipient_token_account.to_account_info(),
+                    referrer_token_account,
+                    &self.recipient.to_account_info(),
+                    &self.token_program.to_account_info(),
+                    referral_fee,
+                    order.is_quote_native(),
+                )?;
             }
 
-            utils::move_lamports(
-                &self.escrow.to_account_info(),
-                &self.recipient_receive_token_account.to_account_info(),
-                self.order.base_amount,
-            )?;
-        } else {
-            let order_key = self.order.key();
-
-            let signer_seeds: &[&[&[u8]]] = &[&[
-                utils::ORDER_ESCROW_PREFIX.as_bytes(),
-                self.order.funder.as_ref(),
-                order_key.as_ref(),
-                &[escrow_bump],
-            ]];
-
-            let cpi_program = self.token_program.to_account_info();
-            let cpi_accounts = token::Transfer {
-                from: self.escrow.to_account_info(),
-                to: self.recipient_receive_token_account.to_account_info(),
-                authority: self.escrow.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-            token::transfer(cpi_ctx, self.order.base_amount)?;
+            record_referrer_payout(referrer, referral_fee)?;
         }
 
+        // Transfer funds from `escrow` to `recipient` (`recipient_receive_token_account`).
+        if order.is_base_native() && self.recipient_receive_token_account.key != self.recipient.key
+        {
+            return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        }
+
+        let order_key = self.order.key();
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            order.funder.as_ref(),
+            order_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        utils::transfer_from_escrow(
+            &self.escrow.to_account_info(),
+            &self.recipient_receive_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            order.base_amount - base_fee,
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        // Transfer base tokens fee from `escrow` to treasury and referrer.
+        pay_escrow_fee(
+            &self.escrow.to_account_info(),
+            &self.treasury_base_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            &protocol,
+            &order.base_mint,
+            base_fee,
+            maker_referrer,
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
         // Delete `escrow` account
-        if self.order.is_base_native() {
-            utils::delete_account(
-                &self.escrow.to_account_info(),
-                &self.funder.to_account_info(),
-            )?;
-        } else {
-            let order_key = self.order.key();
-
-            let signer_seeds: &[&[&[u8]]] = &[&[
-                utils::ORDER_ESCROW_PREFIX.as_bytes(),
-                self.order.funder.as_ref(),
-                order_key.as_ref(),
-                &[escrow_bump],
-            ]];
-
-            let cpi_program = self.token_program.to_account_info();
-            let cpi_accounts = token::CloseAccount {
-                account: self.escrow.to_account_info(),
-                destination: self.funder.to_account_info(),
-                authority: self.escrow.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-            token::close_account(cpi_ctx)?;
+        utils::close_order_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.rent_payer.to_account_info(),
+            &self.token_program.to_account_info(),
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        if self.clock_sysvar.unix_timestamp > order.expire_date {
+            return Err(error::ErrorCode::OrderIsExpired.into());
         }
 
-        if self.clock_sysvar.unix_timestamp > self.order.expire_date {
+        // `escrow` of hash-time-locked `Order` belongs to `funder` after timelock
+        if order.is_htlc() && self.clock_sysvar.unix_timestamp >= order.cancel_locked_until {
             return Err(error::ErrorCode::OrderIsExpired.into());
         }
 
-        if let Some(start_date) = self.order.start_date {
+        if let Some(start_date) = order.start_date() {
             if start_date > self.clock_sysvar.unix_timestamp {
                 return Err(error::ErrorCode::OrderIsNotStarted.into());
             }
         }
 
+        emit!(event::OrderExecuted {
+            order: order_key,
+            recipient: self.recipient.key(),
+            base_amount: order.base_amount,
+            quote_amount,
+            reference: order.reference,
+        });
+
         Ok(())
     }
 }
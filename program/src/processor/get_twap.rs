@@ -0,0 +1,14 @@
+use crate::GetTwap;
+use anchor_lang::{prelude::*, solana_program::program::set_return_data};
+
+impl<'info> GetTwap<'info> {
+    pub fn process(&mut self, window: i64) -> Result<()> {
+        let twap = self
+            .pair_stats
+            .twap(self.clock_sysvar.unix_timestamp, window);
+
+        set_return_data(&twap.try_to_vec()?);
+
+        Ok(())
+    }
+}
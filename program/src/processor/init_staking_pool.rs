@@ -0,0 +1,32 @@
+use crate::{error, utils, InitStakingPool};
+use anchor_lang::prelude::*;
+
+impl<'info> InitStakingPool<'info> {
+    pub fn process(
+        &mut self,
+        staking_pool_bump: u8,
+        stake_vault_bump: u8,
+        cooldown: i64,
+    ) -> Result<()> {
+        if self.stake_mint.key() != self.protocol.discount_mint {
+            return Err(error::ErrorCode::StakeMintMismatch.into());
+        }
+
+        self.staking_pool.stake_mint = self.stake_mint.key();
+        self.staking_pool.total_staked = 0;
+        self.staking_pool.cooldown = cooldown.max(0);
+        self.staking_pool.rewards = Vec::new();
+        self.staking_pool.bump = staking_pool_bump;
+
+        // Create vault `spl_token` account for staked tokens
+        utils::init_escrow(
+            &self.admin.to_account_info(),
+            &self.stake_vault.to_account_info(),
+            &self.stake_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            false,
+            &[utils::STAKE_VAULT_PREFIX.as_bytes(), &[stake_vault_bump]],
+        )
+    }
+}
@@ -0,0 +1,268 @@
+use super::execute_order::{next_maker_referrer, pay_escrow_fee, record_trade, ReferrerPayout};
+use crate::{error, event, state, utils, MatchOrders};
+use anchor_lang::prelude::*;
+
+/// Settlement of one matched `Order` escrow.
+struct EscrowSettlement<'a, 'info> {
+    escrow: AccountInfo<'info>,
+    /// Counter `Order` quote token account.
+    receiver: AccountInfo<'info>,
+    matcher_token_account: AccountInfo<'info>,
+    treasury_token_account: AccountInfo<'info>,
+    funder: AccountInfo<'info>,
+    rent_payer: AccountInfo<'info>,
+    mint: Pubkey,
+    amount: u64,
+    fee: u64,
+    surplus: u64,
+    maker_referrer: Option<ReferrerPayout<'a, 'info>>,
+    is_native: bool,
+}
+
+impl<'info> MatchOrders<'info> {
+    pub fn process(
+        &mut self,
+        escrow_a_bump: u8,
+        escrow_b_bump: u8,
+        stats_bump: u8,
+        pair_stats_a_bump: u8,
+        pair_stats_b_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut order_a = self.order_a.load_mut()?;
+        let mut order_b = self.order_b.load_mut()?;
+
+        if order_a.status()? != state::OrderStatus::Created
+            || order_b.status()? != state::OrderStatus::Created
+        {
+            return Err(error::ErrorCode::InvalidOrderStatus.into());
+        }
+
+        let now = self.clock_sysvar.unix_timestamp;
+
+        for order in [&*order_a, &*order_b] {
+            if now > order.expire_date {
+                return Err(error::ErrorCode::OrderIsExpired.into());
+            }
+
+            if let Some(start_date) = order.start_date() {
+                if start_date > now {
+                    return Err(error::ErrorCode::OrderIsNotStarted.into());
+                }
+            }
+        }
+
+        // Collection offers accept NFT's only, counter `Order` can't hold them
+        if order_a.is_collection_offer() || order_b.is_collection_offer() {
+            return Err(error::ErrorCode::OrderIsNotMatchable.into());
+        }
+
+        // Hash-time-locked `Order` is executable with secret only,
+        // arbitrated `Order` is settled by release or arbiter decision
+        if order_a.is_htlc()
+            || order_b.is_htlc()
+            || order_a.is_arbitrated()
+            || order_b.is_arbitrated()
+        {
+            return Err(error::ErrorCode::OrderIsNotMatchable.into());
+        }
+
+        if !order_a.is_matchable_with(&order_b) || !order_b.is_matchable_with(&order_a) {
+            return Err(error::ErrorCode::OrderIsNotMatchable.into());
+        }
+
+        // Mints of pair should differ, so each `Order` has own `OrderBook` and `PairStats`
+        if order_a.base_mint != order_b.quote_mint
+            || order_a.quote_mint != order_b.base_mint
+            || order_a.base_mint == order_a.quote_mint
+        {
+            return Err(error::ErrorCode::OrdersMintMismatch.into());
+        }
+
+        // Each side should receive at least its current quote amount
+        let quote_amount_a = order_a.current_quote_amount(now);
+        let quote_amount_b = order_b.current_quote_amount(now);
+
+        if order_b.base_amount < quote_amount_a || order_a.base_amount < quote_amount_b {
+            return Err(error::ErrorCode::OrdersDoNotCross.into());
+        }
+
+        let surplus_a = order_a.base_amount - quote_amount_b;
+        let surplus_b = order_b.base_amount - quote_amount_a;
+
+        if order_a.is_base_native() && self.matcher_token_account_a.key != self.matcher.key {
+            return Err(error::ErrorCode::MatcherAccountWalletMismatch.into());
+        }
+
+        if order_b.is_base_native() && self.matcher_token_account_b.key != self.matcher.key {
+            return Err(error::ErrorCode::MatcherAccountWalletMismatch.into());
+        }
+
+        order_a.status = state::OrderStatus::Completed as u8;
+        order_b.status = state::OrderStatus::Completed as u8;
+
+        self.order_book_a.remove(&self.order_a.key());
+        self.order_book_b.remove(&self.order_b.key());
+
+        // Track both `Order`'s trading statistics
+        record_trade(
+            &order_a,
+            quote_amount_a,
+            &mut self.funder_a_profile,
+            &mut self.stats,
+            stats_bump,
+            &mut self.pair_stats_a,
+            pair_stats_a_bump,
+            now,
+        );
+
+        record_trade(
+            &order_b,
+            quote_amount_b,
+            &mut self.funder_b_profile,
+            &mut self.stats,
+            stats_bump,
+            &mut self.pair_stats_b,
+            pair_stats_b_bump,
+            now,
+        );
+
+        // Protocol fee is charged from amount, that each escrow sends to counter `funder`,
+        // referrers receive share of it
+        let protocol = state::Protocol::load_or_default(&self.protocol)?;
+        let fee_a = protocol.fee(quote_amount_b);
+        let fee_b = protocol.fee(quote_amount_a);
+
+        let remaining_accounts = &mut remaining_accounts.iter();
+        let maker_referrer_a = next_maker_referrer(&order_a, &protocol, fee_a, remaining_accounts)?;
+        let maker_referrer_b = next_maker_referrer(&order_b, &protocol, fee_b, remaining_accounts)?;
+
+        let order_a_key = self.order_a.key();
+        let escrow_a_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            order_a.funder.as_ref(),
+            order_a_key.as_ref(),
+            &[escrow_a_bump],
+        ];
+
+        let order_b_key = self.order_b.key();
+        let escrow_b_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            order_b.funder.as_ref(),
+            order_b_key.as_ref(),
+            &[escrow_b_bump],
+        ];
+
+        // Here `quote_token_account_a` and `quote_token_account_b` are not checked
+        // for funders wallets in native `SOL` context, because check was occur
+        // in `CreateOrder` instruction
+
+        let settlement_a = (
+            EscrowSettlement {
+                escrow: self.escrow_a.to_account_info(),
+                receiver: self.quote_token_account_b.to_account_info(),
+                matcher_token_account: self.matcher_token_account_a.to_account_info(),
+                treasury_token_account: self.treasury_token_account_a.to_account_info(),
+                funder: self.funder_a.to_account_info(),
+                rent_payer: self.rent_payer_a.to_account_info(),
+                mint: order_a.base_mint,
+                amount: quote_amount_b,
+                fee: fee_a,
+                surplus: surplus_a,
+                maker_referrer: maker_referrer_a,
+                is_native: order_a.is_base_native(),
+            },
+            escrow_a_seeds,
+        );
+        let settlement_b = (
+            EscrowSettlement {
+                escrow: self.escrow_b.to_account_info(),
+                receiver: self.quote_token_account_a.to_account_info(),
+                matcher_token_account: self.matcher_token_account_b.to_account_info(),
+                treasury_token_account: self.treasury_token_account_b.to_account_info(),
+                funder: self.funder_b.to_account_info(),
+                rent_payer: self.rent_payer_b.to_account_info(),
+                mint: order_b.base_mint,
+                amount: quote_amount_a,
+                fee: fee_b,
+                surplus: surplus_b,
+                maker_referrer: maker_referrer_b,
+                is_native: order_b.is_base_native(),
+            },
+            escrow_b_seeds,
+        );
+
+        // Token escrow is settled first, so native `SOL` moves happen after all CPI's
+        let settlements = if order_a.is_base_native() {
+            [settlement_b, settlement_a]
+        } else {
+            [settlement_a, settlement_b]
+        };
+
+        let token_program = self.token_program.to_account_info();
+
+        for (settlement, seeds) in settlements {
+            // Transfer funds from `escrow` to counter `funder`, fee to treasury
+            // and referrer and surplus to `matcher`
+            utils::transfer_from_escrow(
+                &settlement.escrow,
+                &settlement.receiver,
+                &token_program,
+                settlement.amount - settlement.fee,
+                settlement.is_native,
+                seeds,
+            )?;
+
+            pay_escrow_fee(
+                &settlement.escrow,
+                &settlement.treasury_token_account,
+                &token_program,
+                &protocol,
+                &settlement.mint,
+                settlement.fee,
+                settlement.maker_referrer,
+                settlement.is_native,
+                seeds,
+            )?;
+
+            if settlement.surplus > 0 {
+                utils::transfer_from_escrow(
+                    &settlement.escrow,
+                    &settlement.matcher_token_account,
+                    &token_program,
+                    settlement.surplus,
+                    settlement.is_native,
+                    seeds,
+                )?;
+            }
+
+            // Delete escrow account
+            utils::close_order_escrow(
+                &settlement.escrow,
+                &settlement.funder,
+                &settlement.rent_payer,
+                &token_program,
+                settlement.is_native,
+                seeds,
+            )?;
+        }
+
+        emit!(event::OrderExecuted {
+            order: order_a_key,
+            recipient: order_b.funder,
+            base_amount: order_a.base_amount,
+            quote_amount: quote_amount_a,
+            reference: order_a.reference,
+        });
+
+        emit!(event::OrderExecuted {
+            order: order_b_key,
+            recipient: order_a.funder,
+            base_amount: order_b.base_amount,
+            quote_amount: quote_amount_b,
+            reference: order_b.reference,
+        });
+
+        Ok(())
+    }
+}
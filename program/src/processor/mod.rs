@@ -1,3 +1,44 @@
+mod accept_quote;
+mod batch_cancel_orders;
+mod batch_create_orders;
+mod cancel_auction;
+mod cancel_english_auction;
 mod cancel_order;
+mod cancel_rfq;
+mod claim_delivery;
+mod claim_rewards;
+mod close_order;
+mod commit_bid;
+mod create_auction;
+mod create_english_auction;
 mod create_order;
+mod create_referrer;
+mod create_rfq;
+mod create_ring_swap;
+mod decline_order;
+mod deposit_rewards;
+mod deposit_ring_swap_leg;
+mod dispute_order;
 mod execute_order;
+mod get_twap;
+mod init_protocol;
+mod init_staking_pool;
+mod match_orders;
+mod place_bid;
+mod prune_order_book;
+mod refund_bid;
+mod refund_english_bid;
+mod refund_quote;
+mod refund_ring_swap_leg;
+mod release_order;
+mod resolve_dispute;
+mod reveal_bid;
+mod set_fee_tiers;
+mod settle_auction;
+mod settle_english_auction;
+mod settle_ring_swap;
+mod stake_tokens;
+mod submit_quote;
+mod unstake;
+mod update_protocol;
+mod withdraw_stake;
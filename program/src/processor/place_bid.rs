@@ -0,0 +1,67 @@
+use crate::{error, state, utils, PlaceBid};
+use anchor_lang::prelude::*;
+
+impl<'info> PlaceBid<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        if self.auction.status != state::AuctionStatus::Created {
+            return Err(error::ErrorCode::InvalidAuctionStatus.into());
+        }
+
+        let now = self.clock_sysvar.unix_timestamp;
+
+        if let Some(start_date) = self.auction.start_date {
+            if start_date > now {
+                return Err(error::ErrorCode::AuctionIsNotStarted.into());
+            }
+        }
+
+        if now >= self.auction.expire_date {
+            return Err(error::ErrorCode::AuctionIsExpired.into());
+        }
+
+        if self.bidder.key() == self.auction.funder {
+            return Err(error::ErrorCode::BidderMatchFunder.into());
+        }
+
+        if amount < self.auction.next_min_bid_amount() {
+            return Err(error::ErrorCode::BidTooLow.into());
+        }
+
+        if self.auction.is_quote_native() && self.bidder_token_account.key != self.bidder.key {
+            return Err(error::ErrorCode::BidderAccountWalletMismatch.into());
+        }
+
+        if self.auction.is_base_native() && self.receive_token_account.key != self.bidder.key {
+            return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        }
+
+        // Transfer bid from `bidder` to `bid_escrow`
+        utils::transfer(
+            &self.bidder_token_account.to_account_info(),
+            &self.bid_escrow.to_account_info(),
+            &self.bidder.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount,
+            self.auction.is_quote_native(),
+        )?;
+
+        // Outbid amounts are kept in `bid_escrow` and refunded by `RefundEnglishBid`
+        if self.bid.bidder == Pubkey::default() {
+            self.bid.auction = self.auction.key();
+            self.bid.bidder = self.bidder.key();
+        }
+
+        self.bid.amount = self.bid.amount.saturating_add(amount);
+
+        self.auction.highest_bid_amount = amount;
+        self.auction.highest_bidder = self.bidder.key();
+        self.auction.highest_bidder_receive_account = self.receive_token_account.key();
+
+        // Anti-sniping, late bid extends `EnglishAuction`
+        if self.auction.expire_date - now < self.auction.extension_window {
+            self.auction.expire_date = now + self.auction.extension_window;
+        }
+
+        Ok(())
+    }
+}
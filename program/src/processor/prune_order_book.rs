@@ -0,0 +1,31 @@
+use crate::{error, state, PruneOrderBook};
+use anchor_lang::prelude::*;
+
+impl<'info> PruneOrderBook<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        self.order_book
+            .remove_expired(self.clock_sysvar.unix_timestamp);
+
+        for order_info in remaining_accounts {
+            // Closed `Order` account is deleted by runtime
+            let is_closed = order_info.owner != &crate::id() || order_info.data_is_empty();
+
+            if !is_closed {
+                let order_loader = AccountLoader::<state::Order>::try_from(order_info)?;
+                let order = order_loader.load()?;
+
+                if order.order_book != self.order_book.key() {
+                    return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+                }
+
+                if order.status()? == state::OrderStatus::Created {
+                    return Err(error::ErrorCode::InvalidOrderStatus.into());
+                }
+            }
+
+            self.order_book.remove(order_info.key);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{error, state, utils, RefundBid};
+use anchor_lang::prelude::*;
+
+impl<'info> RefundBid<'info> {
+    pub fn process(&mut self, deposit_bump: u8) -> Result<()> {
+        // Winning bid is locked until `Auction` settlement
+        if self.auction.status == state::AuctionStatus::Created
+            && (self.clock_sysvar.unix_timestamp < self.auction.reveal_end_date
+                || self.bid.bidder == self.auction.winner)
+        {
+            return Err(error::ErrorCode::BidIsNotRefundable.into());
+        }
+
+        let auction_key = self.auction.key();
+
+        let deposit_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.bidder.key.as_ref(),
+            auction_key.as_ref(),
+            &[deposit_bump],
+        ];
+
+        // Transfer `bidder` tokens from `deposit`
+        // (native `SOL`'s will be returned while `deposit` deletion)
+        if !self.auction.is_quote_native() {
+            let deposit_amount = utils::escrow_amount(&self.deposit.to_account_info(), false)?;
+
+            utils::transfer_from_escrow(
+                &self.deposit.to_account_info(),
+                &self.refund_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                deposit_amount,
+                false,
+                deposit_seeds,
+            )?;
+        }
+
+        // Delete `deposit` account
+        utils::close_escrow(
+            &self.deposit.to_account_info(),
+            &self.bidder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_quote_native(),
+            deposit_seeds,
+        )?;
+
+        Ok(())
+    }
+}
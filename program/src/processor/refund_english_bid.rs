@@ -0,0 +1,61 @@
+use super::settle_english_auction::close_drained_bid_escrow;
+use crate::{error, state, utils, RefundEnglishBid};
+use anchor_lang::prelude::*;
+
+impl<'info> RefundEnglishBid<'info> {
+    pub fn process(&mut self, bid_escrow_bump: u8) -> Result<()> {
+        let is_winner = self.bid.bidder == self.auction.highest_bidder;
+
+        // The highest bid is locked until `EnglishAuction` settlement
+        if self.auction.status == state::AuctionStatus::Created && is_winner {
+            return Err(error::ErrorCode::BidIsNotRefundable.into());
+        }
+
+        // Refund goes to any `bidder` account, bid source account may be closed by now
+        if !utils::is_token_account_of(
+            &self.refund_token_account.to_account_info(),
+            &self.bid.bidder,
+            &self.auction.quote_mint,
+        )? {
+            return Err(error::ErrorCode::BidderAccountWalletMismatch.into());
+        }
+
+        // Winner gets back bids, that exceed the winning one
+        let amount = if is_winner {
+            self.bid.amount - self.auction.highest_bid_amount
+        } else {
+            self.bid.amount
+        };
+
+        let auction_key = self.auction.key();
+
+        let bid_escrow_seeds: &[&[u8]] = &[
+            utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(),
+            auction_key.as_ref(),
+            &[bid_escrow_bump],
+        ];
+
+        if amount > 0 {
+            utils::transfer_from_escrow(
+                &self.bid_escrow.to_account_info(),
+                &self.refund_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                amount,
+                self.auction.is_quote_native(),
+                bid_escrow_seeds,
+            )?;
+        }
+
+        if self.auction.status == state::AuctionStatus::Settled {
+            close_drained_bid_escrow(
+                &self.bid_escrow.to_account_info(),
+                &self.funder.to_account_info(),
+                &self.token_program.to_account_info(),
+                self.auction.is_quote_native(),
+                bid_escrow_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{error, state, utils, RefundQuote};
+use anchor_lang::prelude::*;
+
+impl<'info> RefundQuote<'info> {
+    pub fn process(&mut self, deposit_bump: u8) -> Result<()> {
+        // `maker` can withdraw `Quote` at any moment,
+        // others can refund it only if it can't be accepted anymore
+        let now = self.clock_sysvar.unix_timestamp;
+        if !self.maker.is_signer
+            && self.rfq.status == state::RfqStatus::Created
+            && now < self.rfq.expire_date
+            && now < self.quote.expire_date
+        {
+            return Err(error::ErrorCode::QuoteIsNotRefundable.into());
+        }
+
+        self.rfq.quotes_count = self.rfq.quotes_count.saturating_sub(1);
+
+        let rfq_key = self.rfq.key();
+
+        let deposit_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.maker.key.as_ref(),
+            rfq_key.as_ref(),
+            &[deposit_bump],
+        ];
+
+        // Transfer `maker` tokens from `deposit`
+        // (native `SOL`'s will be returned while `deposit` deletion)
+        if !self.quote.is_native() {
+            let deposit_amount = utils::escrow_amount(&self.deposit.to_account_info(), false)?;
+
+            utils::transfer_from_escrow(
+                &self.deposit.to_account_info(),
+                &self.refund_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                deposit_amount,
+                false,
+                deposit_seeds,
+            )?;
+        }
+
+        // Delete `deposit` account
+        utils::close_escrow(
+            &self.deposit.to_account_info(),
+            &self.maker.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.quote.is_native(),
+            deposit_seeds,
+        )?;
+
+        Ok(())
+    }
+}
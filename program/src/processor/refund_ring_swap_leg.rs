@@ -0,0 +1,74 @@
+use crate::{error, state, utils, RefundRingSwapLeg};
+use anchor_lang::prelude::*;
+
+impl<'info> RefundRingSwapLeg<'info> {
+    pub fn process(&mut self, leg_index: u8) -> Result<()> {
+        if self.ring_swap.status != state::RingSwapStatus::Created {
+            return Err(error::ErrorCode::InvalidRingSwapStatus.into());
+        }
+
+        let leg = self
+            .ring_swap
+            .legs
+            .get(leg_index as usize)
+            .cloned()
+            .ok_or(error::ErrorCode::RingSwapLegMismatch)?;
+
+        if leg.participant != self.participant.key()
+            || leg.deposit != self.deposit.key()
+            || leg.refund_token_account != self.refund_token_account.key()
+        {
+            return Err(error::ErrorCode::RingSwapLegMismatch.into());
+        }
+
+        // `participant` can withdraw deposit at any moment before settlement,
+        // others can refund it only after `expire_date`
+        if !leg.is_funded
+            || (!self.participant.is_signer
+                && self.clock_sysvar.unix_timestamp < self.ring_swap.expire_date)
+        {
+            return Err(error::ErrorCode::RingSwapLegIsNotRefundable.into());
+        }
+
+        let ring_swap_key = self.ring_swap.key();
+
+        let deposit_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            leg.participant.as_ref(),
+            ring_swap_key.as_ref(),
+            &[leg.deposit_bump],
+        ];
+
+        // Transfer `participant` tokens from `deposit`
+        // (native `SOL`'s will be returned while `deposit` deletion)
+        if !leg.is_native() {
+            let deposit_amount = utils::escrow_amount(&self.deposit.to_account_info(), false)?;
+
+            utils::transfer_from_escrow(
+                &self.deposit.to_account_info(),
+                &self.refund_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                deposit_amount,
+                false,
+                deposit_seeds,
+            )?;
+        }
+
+        // Delete `deposit` account
+        utils::close_escrow(
+            &self.deposit.to_account_info(),
+            &self.participant.to_account_info(),
+            &self.token_program.to_account_info(),
+            leg.is_native(),
+            deposit_seeds,
+        )?;
+
+        let leg = &mut self.ring_swap.legs[leg_index as usize];
+        leg.refund_token_account = Pubkey::default();
+        leg.deposit = Pubkey::default();
+        leg.deposit_bump = 0;
+        leg.is_funded = false;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{error, event, state, utils, ReleaseOrder};
+use anchor_lang::prelude::*;
+
+impl<'info> ReleaseOrder<'info> {
+    pub fn process(&mut self, escrow_bump: u8) -> Result<()> {
+        let mut order = self.order.load_mut()?;
+
+        if !order.is_arbitrated() {
+            return Err(error::ErrorCode::OrderIsNotArbitrated.into());
+        }
+
+        let status = order.status()?;
+
+        // `funder` can release at any moment, `recipient` only if
+        // claimed delivery was not disputed in time
+        if self.authority.key == self.funder.key {
+            if status != state::OrderStatus::Created
+                && status != state::OrderStatus::DeliveryClaimed
+                && status != state::OrderStatus::Disputed
+            {
+                return Err(error::ErrorCode::InvalidOrderStatus.into());
+            }
+        } else if self.authority.key == self.recipient.key {
+            if status != state::OrderStatus::DeliveryClaimed {
+                return Err(error::ErrorCode::InvalidOrderStatus.into());
+            }
+
+            if self.clock_sysvar.unix_timestamp < order.dispute_end_date() {
+                return Err(error::ErrorCode::DisputeWindowIsOpen.into());
+            }
+        } else {
+            return Err(error::ErrorCode::InvalidReleaseAuthority.into());
+        }
+
+        order.status = state::OrderStatus::Completed as u8;
+        self.order_book.remove(&self.order.key());
+
+        if !utils::is_token_account_of(
+            &self.recipient_receive_token_account,
+            self.recipient.key,
+            &order.base_mint,
+        )? {
+            return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        }
+
+        let order_key = self.order.key();
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.funder.key.as_ref(),
+            order_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        // Transfer funds from `escrow` to `recipient` (`recipient_receive_token_account`).
+        utils::transfer_from_escrow(
+            &self.escrow.to_account_info(),
+            &self.recipient_receive_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            order.base_amount,
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        // Delete `escrow` account
+        utils::close_order_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.rent_payer.to_account_info(),
+            &self.token_program.to_account_info(),
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        emit!(event::OrderReleased {
+            order: order_key,
+            authority: self.authority.key(),
+            amount: order.base_amount,
+            reference: order.reference,
+        });
+
+        Ok(())
+    }
+}
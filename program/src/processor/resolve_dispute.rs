@@ -0,0 +1,77 @@
+use crate::{error, event, state, utils, ResolveDispute};
+use anchor_lang::prelude::*;
+
+impl<'info> ResolveDispute<'info> {
+    pub fn process(&mut self, escrow_bump: u8, release: bool) -> Result<()> {
+        let mut order = self.order.load_mut()?;
+
+        if order.status()? != state::OrderStatus::Disputed {
+            return Err(error::ErrorCode::InvalidOrderStatus.into());
+        }
+
+        order.status = if release {
+            state::OrderStatus::Completed as u8
+        } else {
+            state::OrderStatus::Canceled as u8
+        };
+        self.order_book.remove(&self.order.key());
+
+        let order_key = self.order.key();
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.funder.key.as_ref(),
+            order_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        if release {
+            if !utils::is_token_account_of(
+                &self.recipient_receive_token_account,
+                self.recipient.key,
+                &order.base_mint,
+            )? {
+                return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+            }
+
+            // Transfer funds from `escrow` to `recipient` (`recipient_receive_token_account`).
+            utils::transfer_from_escrow(
+                &self.escrow.to_account_info(),
+                &self.recipient_receive_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                order.base_amount,
+                order.is_base_native(),
+                escrow_seeds,
+            )?;
+        } else if !order.is_base_native() {
+            // Transfer `funder` tokens from `escrow` to recorded `funder_token_account`
+            // (native `SOL`'s will be returned while `escrow` deletion)
+            utils::transfer_from_escrow(
+                &self.escrow.to_account_info(),
+                &self.funder_token_account.to_account_info(),
+                &self.token_program.to_account_info(),
+                order.base_amount,
+                false,
+                escrow_seeds,
+            )?;
+        }
+
+        // Delete `escrow` account
+        utils::close_order_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.rent_payer.to_account_info(),
+            &self.token_program.to_account_info(),
+            order.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        emit!(event::DisputeResolved {
+            order: order_key,
+            arbiter: self.arbiter.key(),
+            released: release,
+            reference: order.reference,
+        });
+
+        Ok(())
+    }
+}
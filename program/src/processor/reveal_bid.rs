@@ -0,0 +1,40 @@
+use crate::{error, state, utils, RevealBid};
+use anchor_lang::prelude::*;
+
+impl<'info> RevealBid<'info> {
+    pub fn process(&mut self, amount: u64, salt: [u8; 32]) -> Result<()> {
+        if self.auction.status != state::AuctionStatus::Created {
+            return Err(error::ErrorCode::InvalidAuctionStatus.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp < self.auction.commit_end_date
+            || self.clock_sysvar.unix_timestamp >= self.auction.reveal_end_date
+        {
+            return Err(error::ErrorCode::RevealPhaseIsNotActive.into());
+        }
+
+        if self.bid.status != state::BidStatus::Committed {
+            return Err(error::ErrorCode::InvalidBidStatus.into());
+        }
+
+        let commitment = utils::hash_bid(&self.auction.key(), self.bidder.key, amount, &salt);
+        if commitment != self.bid.commitment {
+            return Err(error::ErrorCode::BidCommitmentMismatch.into());
+        }
+
+        if amount < self.auction.min_bid_amount || amount > self.bid.deposit_amount {
+            return Err(error::ErrorCode::InvalidBidAmount.into());
+        }
+
+        self.bid.status = state::BidStatus::Revealed;
+        self.bid.amount = amount;
+
+        // Earlier revealed bid wins in case of equal amounts
+        if amount > self.auction.highest_bid_amount {
+            self.auction.highest_bid_amount = amount;
+            self.auction.winner = self.bidder.key();
+        }
+
+        Ok(())
+    }
+}
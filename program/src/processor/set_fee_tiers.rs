@@ -0,0 +1,15 @@
+use crate::{error, state, SetFeeTiers};
+use anchor_lang::prelude::*;
+
+impl<'info> SetFeeTiers<'info> {
+    pub fn process(&mut self, fee_tiers: Vec<state::FeeTier>) -> Result<()> {
+        if !self.protocol.is_valid_fee_tiers(&fee_tiers) {
+            return Err(error::ErrorCode::InvalidFeeTiers.into());
+        }
+
+        self.protocol.discount_mint = self.discount_mint.key();
+        self.protocol.fee_tiers = fee_tiers;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{error, state, utils, SettleAuction};
+use anchor_lang::prelude::*;
+
+impl<'info> SettleAuction<'info> {
+    pub fn process(&mut self, escrow_bump: u8, deposit_bump: u8) -> Result<()> {
+        if self.auction.status != state::AuctionStatus::Created {
+            return Err(error::ErrorCode::InvalidAuctionStatus.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp < self.auction.reveal_end_date {
+            return Err(error::ErrorCode::RevealPhaseIsNotOver.into());
+        }
+
+        if !self.auction.has_winner() {
+            return Err(error::ErrorCode::AuctionHasNoWinner.into());
+        }
+
+        // Settlement goes to any `funder` and winner accounts, stored ones may be closed by now
+        if !utils::is_token_account_of(
+            &self.quote_token_account.to_account_info(),
+            &self.auction.funder,
+            &self.auction.quote_mint,
+        )? {
+            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        }
+
+        if !utils::is_token_account_of(
+            &self.receive_token_account.to_account_info(),
+            &self.bid.bidder,
+            &self.auction.base_mint,
+        )? {
+            return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        }
+
+        self.auction.status = state::AuctionStatus::Settled;
+
+        let auction_key = self.auction.key();
+
+        let deposit_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.bid.bidder.as_ref(),
+            auction_key.as_ref(),
+            &[deposit_bump],
+        ];
+
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.auction.funder.as_ref(),
+            auction_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        // Token escrow is settled first, so native `SOL` moves happen after all CPI's
+        if self.auction.is_quote_native() {
+            self.settle_escrow(escrow_seeds)?;
+            self.settle_deposit(deposit_seeds)?;
+        } else {
+            self.settle_deposit(deposit_seeds)?;
+            self.settle_escrow(escrow_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    fn settle_deposit(&self, deposit_seeds: &[&[u8]]) -> Result<()> {
+        // Transfer winning bid from `deposit` to `funder` (`quote_token_account`).
+        utils::transfer_from_escrow(
+            &self.deposit.to_account_info(),
+            &self.quote_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.bid.amount,
+            self.auction.is_quote_native(),
+            deposit_seeds,
+        )?;
+
+        // Refund `deposit` remainder to `bidder`
+        // (native `SOL`'s will be returned while `deposit` deletion)
+        if !self.auction.is_quote_native() {
+            let remainder = utils::escrow_amount(&self.deposit.to_account_info(), false)?;
+
+            if remainder > 0 {
+                utils::transfer_from_escrow(
+                    &self.deposit.to_account_info(),
+                    &self.refund_token_account.to_account_info(),
+                    &self.token_program.to_account_info(),
+                    remainder,
+                    false,
+                    deposit_seeds,
+                )?;
+            }
+        }
+
+        // Delete `deposit` account
+        utils::close_escrow(
+            &self.deposit.to_account_info(),
+            &self.bidder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_quote_native(),
+            deposit_seeds,
+        )
+    }
+
+    fn settle_escrow(&self, escrow_seeds: &[&[u8]]) -> Result<()> {
+        // Transfer funds from `escrow` to winner (`receive_token_account`).
+        utils::transfer_from_escrow(
+            &self.escrow.to_account_info(),
+            &self.receive_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.base_amount,
+            self.auction.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        // Delete `escrow` account
+        utils::close_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_base_native(),
+            escrow_seeds,
+        )
+    }
+}
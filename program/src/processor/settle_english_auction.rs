@@ -0,0 +1,131 @@
+use crate::{error, state, utils, SettleEnglishAuction};
+use anchor_lang::prelude::*;
+
+/// Close `bid_escrow` of settled `EnglishAuction`, when all bids are refunded.
+pub fn close_drained_bid_escrow<'a>(
+    bid_escrow: &AccountInfo<'a>,
+    receiver: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    is_native: bool,
+    bid_escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    // `bid_escrow` is already deleted
+    if bid_escrow.lamports() == 0 {
+        return Ok(());
+    }
+
+    if utils::escrow_amount(bid_escrow, is_native)? > 0 {
+        return Ok(());
+    }
+
+    utils::close_escrow(
+        bid_escrow,
+        receiver,
+        token_program,
+        is_native,
+        bid_escrow_seeds,
+    )
+}
+
+impl<'info> SettleEnglishAuction<'info> {
+    pub fn process(&mut self, escrow_bump: u8, bid_escrow_bump: u8) -> Result<()> {
+        if self.auction.status != state::AuctionStatus::Created {
+            return Err(error::ErrorCode::InvalidAuctionStatus.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp < self.auction.expire_date {
+            return Err(error::ErrorCode::AuctionIsNotExpired.into());
+        }
+
+        if !self.auction.has_bids() {
+            return Err(error::ErrorCode::AuctionHasNoWinner.into());
+        }
+
+        // Settlement goes to any `funder` and winner accounts, stored ones may be closed by now
+        if !utils::is_token_account_of(
+            &self.quote_token_account.to_account_info(),
+            &self.auction.funder,
+            &self.auction.quote_mint,
+        )? {
+            return Err(error::ErrorCode::QuoteAccountWalletMismatch.into());
+        }
+
+        if !utils::is_token_account_of(
+            &self.receive_token_account.to_account_info(),
+            &self.auction.highest_bidder,
+            &self.auction.base_mint,
+        )? {
+            return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        }
+
+        self.auction.status = state::AuctionStatus::Settled;
+
+        let auction_key = self.auction.key();
+
+        let escrow_seeds: &[&[u8]] = &[
+            utils::ORDER_ESCROW_PREFIX.as_bytes(),
+            self.auction.funder.as_ref(),
+            auction_key.as_ref(),
+            &[escrow_bump],
+        ];
+
+        let bid_escrow_seeds: &[&[u8]] = &[
+            utils::AUCTION_BID_ESCROW_PREFIX.as_bytes(),
+            auction_key.as_ref(),
+            &[bid_escrow_bump],
+        ];
+
+        // Token escrow is settled first, so native `SOL` moves happen after all CPI's
+        if self.auction.is_quote_native() {
+            self.settle_escrow(escrow_seeds)?;
+            self.settle_bid_escrow(bid_escrow_seeds)?;
+        } else {
+            self.settle_bid_escrow(bid_escrow_seeds)?;
+            self.settle_escrow(escrow_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    fn settle_bid_escrow(&self, bid_escrow_seeds: &[&[u8]]) -> Result<()> {
+        // Transfer winning bid from `bid_escrow` to `funder` (`quote_token_account`).
+        utils::transfer_from_escrow(
+            &self.bid_escrow.to_account_info(),
+            &self.quote_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.highest_bid_amount,
+            self.auction.is_quote_native(),
+            bid_escrow_seeds,
+        )?;
+
+        // Delete `bid_escrow` account, if there are no bids to refund
+        close_drained_bid_escrow(
+            &self.bid_escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_quote_native(),
+            bid_escrow_seeds,
+        )
+    }
+
+    fn settle_escrow(&self, escrow_seeds: &[&[u8]]) -> Result<()> {
+        // Transfer funds from `escrow` to winner (`receive_token_account`).
+        utils::transfer_from_escrow(
+            &self.escrow.to_account_info(),
+            &self.receive_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.base_amount,
+            self.auction.is_base_native(),
+            escrow_seeds,
+        )?;
+
+        // Delete `escrow` account
+        utils::close_escrow(
+            &self.escrow.to_account_info(),
+            &self.funder.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.auction.is_base_native(),
+            escrow_seeds,
+        )
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{error, state, utils, SettleRingSwap};
+use anchor_lang::prelude::*;
+
+impl<'info> SettleRingSwap<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        if self.ring_swap.status != state::RingSwapStatus::Created {
+            return Err(error::ErrorCode::InvalidRingSwapStatus.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp >= self.ring_swap.expire_date {
+            return Err(error::ErrorCode::RingSwapIsExpired.into());
+        }
+
+        if !self.ring_swap.is_funded() {
+            return Err(error::ErrorCode::RingSwapIsNotFunded.into());
+        }
+
+        if remaining_accounts.len() != self.ring_swap.legs.len() * 3 {
+            return Err(error::ErrorCode::RingSwapLegMismatch.into());
+        }
+
+        self.ring_swap.status = state::RingSwapStatus::Completed;
+
+        let ring_swap_key = self.ring_swap.key();
+
+        let legs = &self.ring_swap.legs;
+
+        for (i, (leg, accounts)) in legs.iter().zip(remaining_accounts.chunks(3)).enumerate() {
+            let deposit = &accounts[0];
+            let participant = &accounts[1];
+            let receive_token_account = &accounts[2];
+
+            if deposit.key() != leg.deposit
+                || participant.key() != leg.participant
+                || receive_token_account.key() != leg.receive_token_account
+            {
+                return Err(error::ErrorCode::RingSwapLegMismatch.into());
+            }
+
+            // Leg tokens should be received by the next participant only
+            let next_leg = &legs[(i + 1) % legs.len()];
+            if !utils::is_token_account_of(receive_token_account, &next_leg.participant, &leg.mint)?
+            {
+                return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+            }
+
+            let deposit_seeds: &[&[u8]] = &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                leg.participant.as_ref(),
+                ring_swap_key.as_ref(),
+                &[leg.deposit_bump],
+            ];
+
+            // Transfer funds from `deposit` to the next participant (`receive_token_account`).
+            utils::transfer_from_escrow(
+                deposit,
+                receive_token_account,
+                &self.token_program.to_account_info(),
+                leg.amount,
+                leg.is_native(),
+                deposit_seeds,
+            )?;
+
+            // Delete `deposit` account
+            utils::close_escrow(
+                deposit,
+                participant,
+                &self.token_program.to_account_info(),
+                leg.is_native(),
+                deposit_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{utils, StakeTokens};
+use anchor_lang::prelude::*;
+
+impl<'info> StakeTokens<'info> {
+    pub fn process(&mut self, stake_bump: u8, _stake_vault_bump: u8, amount: u64) -> Result<()> {
+        self.stake.wallet = self.wallet.key();
+        self.stake.mint = self.staking_pool.stake_mint;
+        self.stake.bump = stake_bump;
+
+        // Accrue rewards of previous stake before change
+        self.stake.settle(&self.staking_pool);
+
+        self.stake.amount += amount;
+        self.staking_pool.total_staked += amount;
+
+        // `spl_token` program checks, that `wallet_token_account` holds `stake_mint` tokens
+        utils::transfer(
+            &self.wallet_token_account.to_account_info(),
+            &self.stake_vault.to_account_info(),
+            &self.wallet.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount,
+            false,
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{error, state, utils, SubmitQuote};
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+impl<'info> SubmitQuote<'info> {
+    pub fn process(
+        &mut self,
+        deposit_bump: u8,
+        amount: u64,
+        expire_date: UnixTimestamp,
+    ) -> Result<()> {
+        if self.rfq.status != state::RfqStatus::Created {
+            return Err(error::ErrorCode::InvalidRfqStatus.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp >= self.rfq.expire_date {
+            return Err(error::ErrorCode::RfqIsExpired.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp >= expire_date {
+            return Err(error::ErrorCode::ExpireDateInThePast.into());
+        }
+
+        if self.maker.key() == self.rfq.funder {
+            return Err(error::ErrorCode::MakerMatchFunder.into());
+        }
+
+        self.quote.rfq = self.rfq.key();
+        self.quote.maker = self.maker.key();
+        self.quote.mint = self.quote_mint.key();
+        self.quote.amount = amount;
+        self.quote.expire_date = expire_date;
+        self.quote.deposit = self.deposit.key();
+        self.quote.refund_token_account = self.maker_token_account.key();
+        self.quote.receive_token_account = self.receive_token_account.key();
+
+        if self.quote.is_native() && self.maker_token_account.key != self.maker.key {
+            return Err(error::ErrorCode::MakerAccountWalletMismatch.into());
+        }
+
+        if self.rfq.is_base_native() && self.receive_token_account.key != self.maker.key {
+            return Err(error::ErrorCode::RecipientReceiveAccountWalletMismatch.into());
+        }
+
+        self.rfq.quotes_count += 1;
+
+        // Create deposit account according to `quote_mint` and lock quote amount
+        utils::create_escrow(
+            &self.maker.to_account_info(),
+            &self.maker.to_account_info(),
+            &self.maker_token_account.to_account_info(),
+            &self.deposit.to_account_info(),
+            &self.quote_mint.to_account_info(),
+            &self.rent_sysvar,
+            &self.token_program.to_account_info(),
+            amount,
+            self.quote.is_native(),
+            &[
+                utils::ORDER_ESCROW_PREFIX.as_bytes(),
+                self.maker.key.as_ref(),
+                self.rfq.key().as_ref(),
+                &[deposit_bump],
+            ],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{error, utils, Unstake};
+use anchor_lang::prelude::*;
+
+impl<'info> Unstake<'info> {
+    pub fn process(&mut self, stake_vault_bump: u8, amount: u64) -> Result<()> {
+        if amount > self.stake.amount {
+            return Err(error::ErrorCode::InsufficientStake.into());
+        }
+
+        // Accrue rewards of previous stake before change
+        self.stake.settle(&self.staking_pool);
+
+        self.stake.amount -= amount;
+        self.staking_pool.total_staked -= amount;
+
+        if self.staking_pool.cooldown > 0 {
+            // Unstaked amount doesn't receive rewards during cooldown,
+            // every new unstake restarts cooldown
+            self.stake.unstaking_amount += amount;
+            self.stake.unstake_available_at =
+                self.clock_sysvar.unix_timestamp + self.staking_pool.cooldown;
+
+            return Ok(());
+        }
+
+        utils::transfer_from_escrow(
+            &self.stake_vault.to_account_info(),
+            &self.wallet_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount,
+            false,
+            &[utils::STAKE_VAULT_PREFIX.as_bytes(), &[stake_vault_bump]],
+        )
+    }
+}
@@ -0,0 +1,21 @@
+use crate::{error, state, UpdateProtocol};
+use anchor_lang::prelude::*;
+
+impl<'info> UpdateProtocol<'info> {
+    pub fn process(&mut self, fee_bps: u16, max_referral_share_bps: u16) -> Result<()> {
+        self.protocol.admin = self.new_admin.key();
+        self.protocol.treasury = self.treasury.key();
+        self.protocol.fee_bps = fee_bps;
+        self.protocol.max_referral_share_bps = max_referral_share_bps;
+
+        if fee_bps > state::MAX_PROTOCOL_FEE_BPS {
+            return Err(error::ErrorCode::InvalidProtocolFee.into());
+        }
+
+        if max_referral_share_bps as u64 > state::BPS_DENOMINATOR {
+            return Err(error::ErrorCode::InvalidReferralShare.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{error, utils, WithdrawStake};
+use anchor_lang::prelude::*;
+
+impl<'info> WithdrawStake<'info> {
+    pub fn process(&mut self, stake_vault_bump: u8) -> Result<()> {
+        if self.stake.unstaking_amount == 0 {
+            return Err(error::ErrorCode::InsufficientStake.into());
+        }
+
+        if self.clock_sysvar.unix_timestamp < self.stake.unstake_available_at {
+            return Err(error::ErrorCode::StakeIsInCooldown.into());
+        }
+
+        let amount = self.stake.unstaking_amount;
+        self.stake.unstaking_amount = 0;
+
+        utils::transfer_from_escrow(
+            &self.stake_vault.to_account_info(),
+            &self.wallet_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount,
+            false,
+            &[utils::STAKE_VAULT_PREFIX.as_bytes(), &[stake_vault_bump]],
+        )
+    }
+}
@@ -0,0 +1,115 @@
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AuctionStatus {
+    Created,
+    Canceled,
+    Settled,
+}
+
+#[account]
+#[derive(Debug)]
+pub struct Auction {
+    /// Current `Auction` status.
+    pub status: AuctionStatus,
+
+    /// Amount, that will be sended by `funder` to the winner.
+    pub base_amount: u64,
+
+    /// Lowest bid amount, that will be accepted.
+    pub min_bid_amount: u64,
+
+    /// Indicate `escrow` mint.
+    pub base_mint: Pubkey,
+
+    /// Indicate bids mint.
+    pub quote_mint: Pubkey,
+
+    /// Creator, auction initiator.
+    pub funder: Pubkey,
+
+    /// Guarantee pool, that hold `funder` tokens and send them to the winner.
+    pub escrow: Pubkey,
+
+    /// Token account (owned by `funder`), that will receive winning bid.
+    pub quote_token_account: Pubkey,
+
+    /// Start date of the commit phase.
+    /// If `None`, then bids are accepted immediately after creation.
+    pub start_date: Option<UnixTimestamp>,
+
+    /// The date from which bids are not accepted and can be revealed.
+    pub commit_end_date: UnixTimestamp,
+
+    /// The date from which bids can't be revealed and `Auction` can be settled.
+    pub reveal_end_date: UnixTimestamp,
+
+    /// Quantity of committed bids.
+    pub bids_count: u32,
+
+    /// Highest revealed bid amount.
+    pub highest_bid_amount: u64,
+
+    /// Bidder of the highest revealed bid.
+    /// Eq to `Pubkey::default()` if no bids were revealed.
+    pub winner: Pubkey,
+}
+
+impl Auction {
+    pub const LEN: usize = 8 + 1 + 8 + 8 + 32 + 32 + 32 + 32 + 32 + 9 + 8 + 8 + 4 + 8 + 32;
+
+    pub fn is_base_native(&self) -> bool {
+        self.base_mint == System::id()
+    }
+
+    pub fn is_quote_native(&self) -> bool {
+        self.quote_mint == System::id()
+    }
+
+    pub fn has_winner(&self) -> bool {
+        self.winner != Pubkey::default()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum BidStatus {
+    Committed,
+    Revealed,
+}
+
+/// Sealed bid (PDA: [AUCTION_BID_PREFIX, auction_pubkey, bidder_pubkey]).
+#[account]
+#[derive(Debug)]
+pub struct Bid {
+    /// Current `Bid` status.
+    pub status: BidStatus,
+
+    /// `Auction`, that `Bid` belongs to.
+    pub auction: Pubkey,
+
+    /// `Bid` owner.
+    pub bidder: Pubkey,
+
+    /// Guarantee pool, that hold `bidder` tokens (deposit).
+    pub deposit: Pubkey,
+
+    /// Token account (owned by `bidder`), that will receive refunded deposit.
+    pub refund_token_account: Pubkey,
+
+    /// Token account (owned by `bidder`), that will receive `Auction` base tokens.
+    pub receive_token_account: Pubkey,
+
+    /// Amount of tokens, that `deposit` holds.
+    /// Should cover the bid, may exceed it to hide the bid amount.
+    pub deposit_amount: u64,
+
+    /// sha256(auction_pubkey, bidder_pubkey, amount (LE), salt).
+    pub commitment: [u8; 32],
+
+    /// Bid amount, set on reveal.
+    pub amount: u64,
+}
+
+impl Bid {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 32 + 32 + 32 + 8 + 32 + 8;
+}
@@ -0,0 +1,105 @@
+use super::AuctionStatus;
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+/// Ascending (english) auction with escrowed bids.
+#[account]
+#[derive(Debug)]
+pub struct EnglishAuction {
+    /// Current `EnglishAuction` status.
+    pub status: AuctionStatus,
+
+    /// Amount, that will be sended by `funder` to the winner.
+    pub base_amount: u64,
+
+    /// Lowest first bid amount.
+    pub min_bid_amount: u64,
+
+    /// Lowest difference between new bid and the highest one.
+    pub min_bid_increment: u64,
+
+    /// Indicate `escrow` mint.
+    pub base_mint: Pubkey,
+
+    /// Indicate bids mint.
+    pub quote_mint: Pubkey,
+
+    /// Creator, auction initiator.
+    pub funder: Pubkey,
+
+    /// Guarantee pool, that hold `funder` tokens and send them to the winner.
+    pub escrow: Pubkey,
+
+    /// Guarantee pool, that hold the highest bid.
+    pub bid_escrow: Pubkey,
+
+    /// Token account (owned by `funder`), that will receive winning bid.
+    pub quote_token_account: Pubkey,
+
+    /// Start date.
+    /// If `None`, then bids are accepted immediately after creation.
+    pub start_date: Option<UnixTimestamp>,
+
+    /// Expire date, can be extended by late bids.
+    pub expire_date: UnixTimestamp,
+
+    /// Bid placed less than `extension_window` seconds before `expire_date`
+    /// moves `expire_date` to `extension_window` seconds after the bid.
+    pub extension_window: i64,
+
+    /// Highest bid amount.
+    pub highest_bid_amount: u64,
+
+    /// Bidder of the highest bid.
+    /// Eq to `Pubkey::default()` if there are no bids.
+    pub highest_bidder: Pubkey,
+
+    /// Token account (owned by `highest_bidder`), that will receive `escrow` tokens.
+    pub highest_bidder_receive_account: Pubkey,
+}
+
+impl EnglishAuction {
+    pub const LEN: usize =
+        8 + 1 + 8 + 8 + 8 + 32 + 32 + 32 + 32 + 32 + 32 + 9 + 8 + 8 + 8 + 32 + 32;
+
+    pub fn is_base_native(&self) -> bool {
+        self.base_mint == System::id()
+    }
+
+    pub fn is_quote_native(&self) -> bool {
+        self.quote_mint == System::id()
+    }
+
+    pub fn has_bids(&self) -> bool {
+        self.highest_bidder != Pubkey::default()
+    }
+
+    /// Return lowest amount of the next bid.
+    pub fn next_min_bid_amount(&self) -> u64 {
+        if self.has_bids() {
+            self.highest_bid_amount
+                .saturating_add(self.min_bid_increment.max(1))
+        } else {
+            self.min_bid_amount
+        }
+    }
+}
+
+/// Bids of one bidder (PDA: [ENGLISH_BID_PREFIX, auction_pubkey, bidder_pubkey]).
+///
+/// Outbid amounts stay in `bid_escrow` until `bidder` claims refund.
+#[account]
+#[derive(Debug)]
+pub struct EnglishBid {
+    /// `EnglishAuction`, that `EnglishBid` belongs to.
+    pub auction: Pubkey,
+
+    /// `EnglishBid` owner.
+    pub bidder: Pubkey,
+
+    /// Amount of `bidder` tokens, that `bid_escrow` holds.
+    pub amount: u64,
+}
+
+impl EnglishBid {
+    pub const LEN: usize = 8 + 32 + 32 + 8;
+}
@@ -1,3 +1,25 @@
+mod auction;
+mod english_auction;
 mod order;
+mod order_book;
+mod profile;
+mod protocol;
+mod referrer;
+mod rfq;
+mod ring_swap;
+mod staking;
+mod stats;
+mod token_metadata;
 
+pub use auction::*;
+pub use english_auction::*;
 pub use order::*;
+pub use order_book::*;
+pub use profile::*;
+pub use protocol::*;
+pub use referrer::*;
+pub use rfq::*;
+pub use ring_swap::*;
+pub use staking::*;
+pub use stats::*;
+pub use token_metadata::*;
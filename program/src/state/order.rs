@@ -1,17 +1,112 @@
+use super::OrderBookEntry;
+use crate::error;
 use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+use std::convert::TryFrom;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+/// Stored in `Order` as `u8`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
 pub enum OrderStatus {
     Created,
     Canceled,
     Completed,
+    Declined,
+    DeliveryClaimed,
+    Disputed,
+}
+
+impl TryFrom<u8> for OrderStatus {
+    type Error = error::ErrorCode;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(OrderStatus::Created),
+            1 => Ok(OrderStatus::Canceled),
+            2 => Ok(OrderStatus::Completed),
+            3 => Ok(OrderStatus::Declined),
+            4 => Ok(OrderStatus::DeliveryClaimed),
+            5 => Ok(OrderStatus::Disputed),
+            _ => Err(error::ErrorCode::InvalidOrderStatus),
+        }
+    }
+}
+
+/// Describe how `recipient` payment changes between `start_date` and `expire_date`.
+/// Stored in `Order` as `u8`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PriceCurve {
+    /// `quote_amount` stays constant during whole `Order` lifetime.
+    Fixed,
+
+    /// Dutch auction, price declines from `quote_amount` to `floor_quote_amount` linearly.
+    Linear,
+
+    /// Dutch auction, price declines from `quote_amount` to `floor_quote_amount`
+    /// fast at the beginning and slowly towards `expire_date`.
+    Quadratic,
+}
+
+impl TryFrom<u8> for PriceCurve {
+    type Error = error::ErrorCode;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PriceCurve::Fixed),
+            1 => Ok(PriceCurve::Linear),
+            2 => Ok(PriceCurve::Quadratic),
+            _ => Err(error::ErrorCode::InvalidPriceCurve),
+        }
+    }
+}
+
+/// Max length of `Order` memo (bytes).
+pub const ORDER_MEMO_LEN: usize = 64;
+
+/// Arbitrated `Order` parameters, provided by `funder`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArbitrationArgs {
+    /// Third party, that decides who gets `escrow` if `funder` disputes delivery.
+    pub arbiter: Pubkey,
+
+    /// Seconds, that `funder` has to dispute claimed delivery
+    /// and arbiter has to resolve dispute.
+    pub dispute_window: i64,
+}
+
+/// Token gate, that executing wallet should pass, provided by `funder`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gate {
+    /// Hold at least `min_amount` of `mint` tokens.
+    MinBalance { mint: Pubkey, min_amount: u64 },
+
+    /// Hold NFT of verified collection (collection mint).
+    Collection { collection: Pubkey },
 }
 
-#[account]
+/// Parameters of one `Order` in `batch_create_orders`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BatchOrderArgs {
+    /// Bump seed of `Order` `escrow`.
+    pub escrow_bump: u8,
+
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub start_date: Option<UnixTimestamp>,
+    pub expire_date: UnixTimestamp,
+    pub price_curve: PriceCurve,
+    pub floor_quote_amount: u64,
+    pub cancel_locked_until: Option<UnixTimestamp>,
+    pub memo: Option<String>,
+    pub reference: Option<[u8; 32]>,
+}
+
+/// Zero-copy, fixed layout (no padding) account, so field offsets never change.
+#[account(zero_copy)]
 #[derive(Debug)]
 pub struct Order {
-    /// Current `Order` status.
-    pub status: OrderStatus,
+    /// Current `Order` status (`OrderStatus` as `u8`).
+    pub status: u8,
 
     /// Amount, that will be sended by `funder`.
     pub base_amount: u64,
@@ -29,6 +124,8 @@ pub struct Order {
     pub funder: Pubkey,
 
     /// Participant, that swap tokens with `funder`.
+    /// If eq to `System::id()`, then `Order` is open for matching with any counter order
+    /// (or hidden, if `recipient_commitment` is set).
     pub recipient: Pubkey,
 
     /// Guarantee pool, that hold `funder` tokens and send them to `recipient`.
@@ -38,15 +135,141 @@ pub struct Order {
     pub quote_token_account: Pubkey,
 
     /// Start date.
-    /// If `None`, then `Order` starts immediately after creation.
-    pub start_date: Option<UnixTimestamp>,
+    /// If eq to `0`, then `Order` starts immediately after creation.
+    pub start_date: UnixTimestamp,
 
     /// Expire date.
     pub expire_date: UnixTimestamp,
+
+    /// Price curve, that define current amount, that will be sended by `recipient`
+    /// (`PriceCurve` as `u8`).
+    pub price_curve: u8,
+
+    /// Lowest amount, that will be sended by `recipient` (reached at `expire_date`).
+    /// Eq to `quote_amount` if `PriceCurve::Fixed`.
+    pub floor_quote_amount: u64,
+
+    /// Creation date.
+    pub created_at: UnixTimestamp,
+
+    /// `OrderBook` of mint pair, that index `Order` while it's active.
+    pub order_book: Pubkey,
+
+    /// `Referrer` of `funder` side, that receives share of protocol fee in base tokens.
+    /// Eq to `Pubkey::default()` if `Order` has no referrer.
+    pub referrer: Pubkey,
+
+    /// Share of protocol fee on `funder` side, that will be paid to `referrer` (basis points).
+    pub referral_share_bps: u16,
+
+    /// Verified collection (collection mint), any NFT of which `funder` accepts as quote tokens.
+    /// Eq to `Pubkey::default()` if `Order` is not collection offer.
+    pub quote_collection: Pubkey,
+
+    /// `funder` token account, that will receive `escrow` tokens back if `recipient` declines `Order`.
+    pub funder_token_account: Pubkey,
+
+    /// Date until which `funder` can't cancel `Order` (firm offer).
+    /// If eq to `0`, then `Order` can be canceled at any moment.
+    pub cancel_locked_until: UnixTimestamp,
+
+    /// SHA-256 hash of secret, that `recipient` should reveal to execute `Order`.
+    /// `cancel_locked_until` is the timelock, after which `funder` can reclaim `escrow`.
+    /// Eq to `[0; 32]` if `Order` is not hash-time-locked.
+    pub hashlock: [u8; 32],
+
+    /// Secret (preimage of `hashlock`), revealed by `recipient` on execution.
+    /// Can be read by `funder` to claim counter assets on another chain.
+    pub preimage: [u8; 32],
+
+    /// Third party, that decides who gets `escrow` if `funder` disputes delivery
+    /// of off-chain leg. Eq to `Pubkey::default()` if `Order` is not arbitrated.
+    pub arbiter: Pubkey,
+
+    /// Seconds, that `funder` has to dispute claimed delivery
+    /// and arbiter has to resolve dispute.
+    pub dispute_window: i64,
+
+    /// Date, when `recipient` claimed delivery (`OrderStatus::DeliveryClaimed`).
+    pub delivery_claimed_at: UnixTimestamp,
+
+    /// Date, when `funder` disputed delivery (`OrderStatus::Disputed`).
+    pub disputed_at: UnixTimestamp,
+
+    /// Client reference (e.g. off-chain deal ID).
+    /// Eq to `[0; 32]` if `Order` has no reference.
+    pub reference: [u8; 32],
+
+    /// UTF-8 memo, padded with zeros.
+    pub memo: [u8; ORDER_MEMO_LEN],
+
+    /// SHA-256 hash of `recipient` and salt, that hides `recipient` until execution
+    /// (`recipient` is set on execution).
+    /// Eq to `[0; 32]` if `Order` is not hidden.
+    pub recipient_commitment: [u8; 32],
+
+    /// Wallet, that paid rent of `Order` and `escrow` accounts and receives it back
+    /// when they are closed. Eq to `funder` if `Order` creation wasn't sponsored.
+    pub rent_payer: Pubkey,
+
+    /// Mint (or verified collection if `gate_min_amount` is `0`), tokens of which
+    /// executing wallet should hold. Eq to `Pubkey::default()` if `Order` is not token-gated.
+    pub gate_mint: Pubkey,
+
+    /// Lowest amount of `gate_mint` tokens, that executing wallet should hold.
+    pub gate_min_amount: u64,
 }
 
 impl Order {
-    pub const LEN: usize = 8 + 1 + 8 + 8 + 32 + 32 + 32 + 32 + 32 + 32 + 9 + 8;
+    pub const LEN: usize = 8 + std::mem::size_of::<Order>();
+
+    /// Account data offset of `funder` (for `getProgramAccounts` filters).
+    pub const FUNDER_OFFSET: usize = 8 + std::mem::offset_of!(Order, funder);
+
+    /// Account data offset of `reference` (for `getProgramAccounts` filters).
+    pub const REFERENCE_OFFSET: usize = 8 + std::mem::offset_of!(Order, reference);
+
+    pub fn status(&self) -> Result<OrderStatus> {
+        Ok(OrderStatus::try_from(self.status)?)
+    }
+
+    pub fn price_curve(&self) -> Result<PriceCurve> {
+        Ok(PriceCurve::try_from(self.price_curve)?)
+    }
+
+    pub fn start_date(&self) -> Option<UnixTimestamp> {
+        if self.start_date == 0 {
+            None
+        } else {
+            Some(self.start_date)
+        }
+    }
+
+    pub fn cancel_locked_until(&self) -> Option<UnixTimestamp> {
+        if self.cancel_locked_until == 0 {
+            None
+        } else {
+            Some(self.cancel_locked_until)
+        }
+    }
+
+    pub fn reference(&self) -> Option<[u8; 32]> {
+        if self.reference == [0; 32] {
+            None
+        } else {
+            Some(self.reference)
+        }
+    }
+
+    pub fn memo(&self) -> String {
+        let len = self
+            .memo
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(ORDER_MEMO_LEN);
+
+        String::from_utf8_lossy(&self.memo[..len]).into_owned()
+    }
 
     pub fn is_base_native(&self) -> bool {
         self.base_mint == System::id()
@@ -55,4 +278,106 @@ impl Order {
     pub fn is_quote_native(&self) -> bool {
         self.quote_mint == System::id()
     }
+
+    pub fn is_collection_offer(&self) -> bool {
+        self.quote_collection != Pubkey::default()
+    }
+
+    pub fn is_htlc(&self) -> bool {
+        self.hashlock != [0; 32]
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.recipient_commitment != [0; 32]
+    }
+
+    pub fn is_gated(&self) -> bool {
+        self.gate_mint != Pubkey::default()
+    }
+
+    pub fn gate(&self) -> Option<Gate> {
+        if !self.is_gated() {
+            None
+        } else if self.gate_min_amount == 0 {
+            Some(Gate::Collection {
+                collection: self.gate_mint,
+            })
+        } else {
+            Some(Gate::MinBalance {
+                mint: self.gate_mint,
+                min_amount: self.gate_min_amount,
+            })
+        }
+    }
+
+    pub fn is_arbitrated(&self) -> bool {
+        self.arbiter != Pubkey::default()
+    }
+
+    /// Date, after which claimed delivery can't be disputed
+    /// and `recipient` can release `escrow` itself.
+    pub fn dispute_end_date(&self) -> UnixTimestamp {
+        self.delivery_claimed_at + self.dispute_window
+    }
+
+    /// Date, after which unresolved dispute can be canceled by `funder`.
+    pub fn arbitration_end_date(&self) -> UnixTimestamp {
+        self.disputed_at + self.dispute_window
+    }
+
+    pub fn has_referrer(&self) -> bool {
+        self.referrer != Pubkey::default()
+    }
+
+    /// Check if `Order` can be matched with `counter_order` (`funder` of `counter_order`
+    /// is `recipient` or `Order` is open). Hidden and token-gated `Order` can be executed only.
+    /// `Order`'s of same `funder` can't be matched.
+    pub fn is_matchable_with(&self, counter_order: &Order) -> bool {
+        !self.is_gated()
+            && self.funder != counter_order.funder
+            && ((self.recipient == System::id() && !self.is_hidden())
+                || self.recipient == counter_order.funder)
+    }
+
+    /// Return `OrderBook` entry of `Order`.
+    pub fn to_order_book_entry(&self, order: &Pubkey) -> OrderBookEntry {
+        OrderBookEntry {
+            order: *order,
+            base_amount: self.base_amount,
+            quote_amount: self.quote_amount,
+            expire_date: self.expire_date,
+        }
+    }
+
+    /// Return amount, that should be sended by `recipient` at `now`.
+    ///
+    /// Price starts declining from `start_date` (or creation date if `None`)
+    /// and reaches `floor_quote_amount` at `expire_date`.
+    pub fn current_quote_amount(&self, now: UnixTimestamp) -> u64 {
+        let start_date = self.start_date().unwrap_or(self.created_at);
+        let price_curve = self.price_curve().unwrap_or(PriceCurve::Fixed);
+
+        if price_curve == PriceCurve::Fixed || now <= start_date {
+            return self.quote_amount;
+        }
+
+        if now >= self.expire_date {
+            return self.floor_quote_amount;
+        }
+
+        let elapsed = (now - start_date) as u128;
+        let duration = (self.expire_date - start_date) as u128;
+        let range = self.quote_amount.saturating_sub(self.floor_quote_amount) as u128;
+
+        let decline = match price_curve {
+            PriceCurve::Fixed => 0,
+            PriceCurve::Linear => range * elapsed / duration,
+            PriceCurve::Quadratic => {
+                let remaining = duration - elapsed;
+                range - range * remaining / duration * remaining / duration
+            }
+        };
+
+        self.quote_amount - decline as u64
+    }
 }
@@ -0,0 +1,105 @@
+use crate::error;
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+use std::cmp::Ordering;
+
+pub const ORDER_BOOK_MAX_ORDERS: usize = 64;
+
+/// Active `Order` summary, stored in `OrderBook`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OrderBookEntry {
+    /// `Order` pubkey.
+    pub order: Pubkey,
+
+    /// Amount, that will be sended by `Order` funder.
+    pub base_amount: u64,
+
+    /// Amount, that will be sended by `Order` recipient (initial one if dutch auction).
+    pub quote_amount: u64,
+
+    /// `Order` expire date.
+    pub expire_date: UnixTimestamp,
+}
+
+impl OrderBookEntry {
+    pub const LEN: usize = 32 + 8 + 8 + 8;
+
+    /// Compare entries by price (quote tokens per base token).
+    pub fn cmp_price(&self, other: &OrderBookEntry) -> Ordering {
+        (self.quote_amount as u128 * other.base_amount as u128)
+            .cmp(&(other.quote_amount as u128 * self.base_amount as u128))
+    }
+}
+
+/// Index of active `Order`'s for mint pair
+/// (PDA: [ORDER_BOOK_PREFIX, base_mint_pubkey, quote_mint_pubkey]).
+///
+/// Holds up to `ORDER_BOOK_MAX_ORDERS` best priced `Order`'s, sorted by price ascending.
+/// Expired and finished `Order`'s entries are removed by `prune_order_book`.
+#[account]
+#[derive(Debug)]
+pub struct OrderBook {
+    /// Mint of `Order`'s escrows.
+    pub base_mint: Pubkey,
+
+    /// Mint of `Order`'s quote tokens.
+    pub quote_mint: Pubkey,
+
+    /// Active `Order`'s, sorted by price ascending (best first).
+    pub entries: Vec<OrderBookEntry>,
+}
+
+impl OrderBook {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + OrderBookEntry::LEN * ORDER_BOOK_MAX_ORDERS;
+
+    /// Insert `entry` keeping price order.
+    /// Fails if `OrderBook` is full.
+    pub fn insert(&mut self, entry: OrderBookEntry) -> Result<()> {
+        if self.entries.len() >= ORDER_BOOK_MAX_ORDERS {
+            return Err(error::ErrorCode::OrderBookIsFull.into());
+        }
+
+        self.insert_sorted(entry);
+
+        Ok(())
+    }
+
+    /// Insert `entry` of new `Order` keeping price order, never fails.
+    /// If `OrderBook` is full, entries expired at `now` are removed, then the worst
+    /// priced entry is evicted (or `entry` isn't indexed, if it is the worst priced one).
+    pub fn insert_or_evict(&mut self, entry: OrderBookEntry, now: UnixTimestamp) {
+        if self.entries.len() >= ORDER_BOOK_MAX_ORDERS {
+            self.remove_expired(now);
+        }
+
+        if self.entries.len() >= ORDER_BOOK_MAX_ORDERS {
+            match self.entries.last() {
+                Some(worst) if entry.cmp_price(worst) == Ordering::Less => {
+                    self.entries.pop();
+                }
+                _ => return,
+            }
+        }
+
+        self.insert_sorted(entry);
+    }
+
+    fn insert_sorted(&mut self, entry: OrderBookEntry) {
+        let index = self
+            .entries
+            .iter()
+            .position(|e| entry.cmp_price(e) == Ordering::Less)
+            .unwrap_or(self.entries.len());
+
+        self.entries.insert(index, entry);
+    }
+
+    /// Remove `Order` entry (if indexed).
+    pub fn remove(&mut self, order: &Pubkey) {
+        self.entries.retain(|e| e.order != *order);
+    }
+
+    /// Remove entries of `Order`'s, expired before `now`.
+    pub fn remove_expired(&mut self, now: UnixTimestamp) {
+        self.entries.retain(|e| now <= e.expire_date);
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+pub const PROFILE_MAX_MINTS: usize = 16;
+
+/// Traded volume of wallet in specific mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProfileVolume {
+    /// Mint of traded tokens.
+    /// If tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    pub mint: Pubkey,
+
+    /// Total amount, that was sended by wallet in completed `Order`'s.
+    pub amount: u64,
+}
+
+impl ProfileVolume {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Trading statistics of wallet (PDA: [PROFILE_PREFIX, wallet_pubkey]).
+///
+/// Updated by `Order` instructions, so counterparties can check wallet reputation.
+#[account]
+#[derive(Debug)]
+pub struct Profile {
+    /// Trader wallet.
+    pub wallet: Pubkey,
+
+    /// Quantity of `Order`'s, created by `wallet`.
+    pub orders_created: u64,
+
+    /// Quantity of `Order`'s, completed by `wallet` as funder or recipient.
+    pub orders_completed: u64,
+
+    /// Quantity of `Order`'s, canceled by `wallet` before expiration.
+    pub orders_canceled: u64,
+
+    /// Quantity of `Order`'s, canceled by `wallet` after expiration.
+    pub orders_expired: u64,
+
+    /// Traded volume per mint (up to `PROFILE_MAX_MINTS` mints, first traded ones).
+    pub volumes: Vec<ProfileVolume>,
+
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Profile {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 4 + ProfileVolume::LEN * PROFILE_MAX_MINTS + 1;
+
+    /// Add traded `amount` of `mint`.
+    /// If `Profile` already tracks `PROFILE_MAX_MINTS` other mints, volume is not recorded.
+    pub fn add_volume(&mut self, mint: &Pubkey, amount: u64) {
+        if let Some(volume) = self.volumes.iter_mut().find(|v| v.mint == *mint) {
+            volume.amount = volume.amount.saturating_add(amount);
+        } else if self.volumes.len() < PROFILE_MAX_MINTS {
+            self.volumes.push(ProfileVolume {
+                mint: *mint,
+                amount,
+            });
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+/// Denominator of values, represented in basis points.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Highest protocol fee (basis points).
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+/// Highest quantity of fee discount tiers.
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Reduced protocol fee for holders of `Protocol::discount_mint`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeTier {
+    /// Lowest `discount_mint` balance of executing wallet.
+    pub min_amount: u64,
+
+    /// Fee, charged from executing wallet side (basis points).
+    pub fee_bps: u16,
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + 2;
+}
+
+/// Protocol configuration (PDA: [PROTOCOL_PREFIX]).
+///
+/// Until it's initialized, default configuration (zero fee) is used.
+#[account]
+#[derive(Debug, Default)]
+pub struct Protocol {
+    /// Protocol admin, that can update configuration.
+    pub admin: Pubkey,
+
+    /// Wallet, that receives protocol fees (or owns fee token accounts).
+    pub treasury: Pubkey,
+
+    /// Fee, charged from both `Order` sides on execution (basis points).
+    pub fee_bps: u16,
+
+    /// Highest share of protocol fee, that can be paid to referrer (basis points).
+    pub max_referral_share_bps: u16,
+
+    /// Mint of p2swap token, that gives fee discount to holders.
+    pub discount_mint: Pubkey,
+
+    /// Fee discount tiers, sorted by `min_amount` (up to `MAX_FEE_TIERS`).
+    pub fee_tiers: Vec<FeeTier>,
+
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Protocol {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 2 + 32 + 4 + MAX_FEE_TIERS * FeeTier::LEN + 1;
+
+    /// Load `Protocol` configuration, default one if account is not initialized yet.
+    pub fn load_or_default(protocol: &AccountInfo) -> Result<Self> {
+        if protocol.data_is_empty() {
+            return Ok(Self::default());
+        }
+
+        Ok(Account::<Self>::try_from(protocol)?.into_inner())
+    }
+
+    /// Return protocol fee for `amount`.
+    pub fn fee(&self, amount: u64) -> u64 {
+        Self::fee_with_bps(amount, self.fee_bps)
+    }
+
+    /// Return protocol fee for `amount`, reduced by tier of `discount_amount`
+    /// (`discount_mint` balance of executing wallet).
+    pub fn discounted_fee(&self, amount: u64, discount_amount: u64) -> u64 {
+        Self::fee_with_bps(amount, self.tier_fee_bps(discount_amount))
+    }
+
+    /// Return fee of highest tier, that `discount_amount` reaches
+    /// (`fee_bps` if there is no such tier).
+    pub fn tier_fee_bps(&self, discount_amount: u64) -> u16 {
+        self.fee_tiers
+            .iter()
+            .filter(|tier| discount_amount >= tier.min_amount)
+            .map(|tier| tier.fee_bps)
+            .fold(self.fee_bps, u16::min)
+    }
+
+    /// Check if fee tiers are sorted by `min_amount` and don't exceed `fee_bps`.
+    pub fn is_valid_fee_tiers(&self, fee_tiers: &[FeeTier]) -> bool {
+        fee_tiers.len() <= MAX_FEE_TIERS
+            && fee_tiers.iter().all(|tier| tier.fee_bps <= self.fee_bps)
+            && fee_tiers.windows(2).all(|tiers| {
+                tiers[0].min_amount < tiers[1].min_amount && tiers[0].fee_bps >= tiers[1].fee_bps
+            })
+    }
+
+    fn fee_with_bps(amount: u64, fee_bps: u16) -> u64 {
+        (amount as u128 * fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Return referrer part of protocol `fee` (`share_bps` is capped by `max_referral_share_bps`).
+    pub fn referral_fee(&self, fee: u64, share_bps: u16) -> u64 {
+        let share_bps = share_bps.min(self.max_referral_share_bps);
+
+        (fee as u128 * share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Referrer payouts for specific mint (PDA: [REFERRER_PREFIX, wallet_pubkey, mint_pubkey]).
+#[account]
+#[derive(Debug)]
+pub struct Referrer {
+    /// Referrer wallet (front-end, aggregator, etc.).
+    pub wallet: Pubkey,
+
+    /// Mint of payouts.
+    /// If payouts are native `SOL`'s, then this field should eq to `System::id()`.
+    pub mint: Pubkey,
+
+    /// Token account (owned by `wallet`), that receives payouts.
+    /// Eq to `wallet` if payouts are native `SOL`'s.
+    pub token_account: Pubkey,
+
+    /// Total paid amount.
+    pub total_amount: u64,
+
+    /// Quantity of payouts.
+    pub payouts_count: u64,
+}
+
+impl Referrer {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8;
+}
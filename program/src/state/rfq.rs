@@ -0,0 +1,83 @@
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum RfqStatus {
+    Created,
+    Canceled,
+    Settled,
+}
+
+/// Request for quotes on escrowed `funder` tokens.
+#[account]
+#[derive(Debug)]
+pub struct Rfq {
+    /// Current `Rfq` status.
+    pub status: RfqStatus,
+
+    /// Amount, that will be sended by `funder` to the accepted `Quote` maker.
+    pub base_amount: u64,
+
+    /// Indicate `escrow` mint.
+    pub base_mint: Pubkey,
+
+    /// Creator, request initiator.
+    pub funder: Pubkey,
+
+    /// Guarantee pool, that hold `funder` tokens and send them to the accepted `Quote` maker.
+    pub escrow: Pubkey,
+
+    /// Expire date, `Quote`'s can't be submitted or accepted after it.
+    pub expire_date: UnixTimestamp,
+
+    /// Quantity of open `Quote`'s.
+    pub quotes_count: u32,
+
+    /// Accepted `Quote`.
+    /// Eq to `Pubkey::default()` if `Rfq` is not settled.
+    pub accepted_quote: Pubkey,
+}
+
+impl Rfq {
+    pub const LEN: usize = 8 + 1 + 8 + 32 + 32 + 32 + 8 + 4 + 32;
+
+    pub fn is_base_native(&self) -> bool {
+        self.base_mint == System::id()
+    }
+}
+
+/// Maker offer for `Rfq` (PDA: [RFQ_QUOTE_PREFIX, rfq_pubkey, maker_pubkey]).
+#[account]
+#[derive(Debug)]
+pub struct Quote {
+    /// `Rfq`, that `Quote` belongs to.
+    pub rfq: Pubkey,
+
+    /// `Quote` owner.
+    pub maker: Pubkey,
+
+    /// Indicate `deposit` mint.
+    pub mint: Pubkey,
+
+    /// Amount, that will be sended by `maker` to `funder`.
+    pub amount: u64,
+
+    /// Expire date, `Quote` can't be accepted after it.
+    pub expire_date: UnixTimestamp,
+
+    /// Guarantee pool, that hold `maker` tokens.
+    pub deposit: Pubkey,
+
+    /// Token account (owned by `maker`), that will receive refunded deposit.
+    pub refund_token_account: Pubkey,
+
+    /// Token account (owned by `maker`), that will receive `Rfq` base tokens.
+    pub receive_token_account: Pubkey,
+}
+
+impl Quote {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 32 + 32 + 32;
+
+    pub fn is_native(&self) -> bool {
+        self.mint == System::id()
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::{prelude::*, solana_program::clock::UnixTimestamp};
+
+pub const RING_SWAP_MIN_LEGS: usize = 3;
+pub const RING_SWAP_MAX_LEGS: usize = 5;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum RingSwapStatus {
+    Created,
+    Completed,
+}
+
+/// `RingSwap` leg parameters, provided by creator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RingSwapLegArgs {
+    /// Participant, that gives tokens to the next participant in the ring.
+    pub participant: Pubkey,
+
+    /// Mint of given tokens.
+    /// If tokens are native `SOL`'s, then this field should eq to `System::id()`.
+    pub mint: Pubkey,
+
+    /// Amount of given tokens.
+    pub amount: u64,
+
+    /// Token account (owned by the next participant), that will receive tokens.
+    /// Should be the next participant wallet if tokens are native `SOL`'s.
+    pub receive_token_account: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RingSwapLeg {
+    /// Participant, that gives tokens to the next participant in the ring.
+    pub participant: Pubkey,
+
+    /// Mint of given tokens.
+    pub mint: Pubkey,
+
+    /// Amount of given tokens.
+    pub amount: u64,
+
+    /// Token account (owned by the next participant), that will receive tokens.
+    pub receive_token_account: Pubkey,
+
+    /// Token account (owned by `participant`), that will receive refund.
+    /// Set on deposit.
+    pub refund_token_account: Pubkey,
+
+    /// Guarantee pool, that hold `participant` tokens.
+    /// Set on deposit.
+    pub deposit: Pubkey,
+
+    /// `deposit` bump seed.
+    pub deposit_bump: u8,
+
+    /// Indicate if `deposit` holds `participant` tokens.
+    pub is_funded: bool,
+}
+
+impl RingSwapLeg {
+    pub const LEN: usize = 32 + 32 + 8 + 32 + 32 + 32 + 1 + 1;
+
+    pub fn is_native(&self) -> bool {
+        self.mint == System::id()
+    }
+}
+
+/// Cyclic swap, each participant gives tokens to the next one
+/// (the last participant gives tokens to the first one).
+#[account]
+#[derive(Debug)]
+pub struct RingSwap {
+    /// Current `RingSwap` status.
+    pub status: RingSwapStatus,
+
+    /// `RingSwap` creator.
+    pub creator: Pubkey,
+
+    /// Expire date, `RingSwap` can't be settled after it and deposits can be refunded.
+    pub expire_date: UnixTimestamp,
+
+    /// Ring legs, leg `i` tokens will be received by participant of leg `i + 1`.
+    pub legs: Vec<RingSwapLeg>,
+}
+
+impl RingSwap {
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 4 + RingSwapLeg::LEN * RING_SWAP_MAX_LEGS;
+
+    pub fn is_funded(&self) -> bool {
+        self.legs.iter().all(|leg| leg.is_funded)
+    }
+}
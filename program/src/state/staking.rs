@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+/// Highest quantity of mints, that `StakingPool` distributes.
+pub const MAX_REWARD_MINTS: usize = 8;
+
+/// Scale of `RewardState::reward_per_share`.
+pub const REWARD_PER_SHARE_SCALE: u128 = 1_000_000_000_000;
+
+/// Accumulated rewards of `StakingPool` in specific mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RewardState {
+    /// Mint of rewards.
+    /// If rewards are native `SOL`'s, then this field should eq to `System::id()`.
+    pub mint: Pubkey,
+
+    /// Rewards per staked token since mint was added (scaled by `REWARD_PER_SHARE_SCALE`).
+    pub reward_per_share: u128,
+
+    /// Total deposited rewards.
+    pub total_amount: u64,
+}
+
+impl RewardState {
+    pub const LEN: usize = 32 + 16 + 8;
+}
+
+/// Pool, that distributes protocol fees to p2swap token stakers (PDA: [STAKING_POOL_PREFIX]).
+#[account]
+#[derive(Debug)]
+pub struct StakingPool {
+    /// Mint of staked p2swap token.
+    pub stake_mint: Pubkey,
+
+    /// Total staked amount (unstaking amounts excluded).
+    pub total_staked: u64,
+
+    /// Delay between `unstake` and `withdraw_stake` (seconds, 0 - without cooldown).
+    pub cooldown: i64,
+
+    /// Rewards per mint, in order of first deposit (up to `MAX_REWARD_MINTS`).
+    pub rewards: Vec<RewardState>,
+
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl StakingPool {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 4 + MAX_REWARD_MINTS * RewardState::LEN + 1;
+
+    /// Return index of `mint` rewards.
+    pub fn reward_index(&self, mint: &Pubkey) -> Option<usize> {
+        self.rewards.iter().position(|reward| reward.mint == *mint)
+    }
+
+    /// Distribute `amount` of `mint` rewards between stakers pro rata to stake.
+    /// Return `None` if `mint` can't be added.
+    pub fn distribute(&mut self, mint: &Pubkey, amount: u64) -> Option<()> {
+        let index = match self.reward_index(mint) {
+            Some(index) => index,
+            None if self.rewards.len() < MAX_REWARD_MINTS => {
+                self.rewards.push(RewardState {
+                    mint: *mint,
+                    ..Default::default()
+                });
+                self.rewards.len() - 1
+            }
+            None => return None,
+        };
+
+        let reward = &mut self.rewards[index];
+        reward.reward_per_share +=
+            amount as u128 * REWARD_PER_SHARE_SCALE / self.total_staked as u128;
+        reward.total_amount += amount;
+
+        Some(())
+    }
+}
+
+/// Unclaimed rewards of `Stake` in mint of `StakingPool::rewards` with the same index.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakeReward {
+    /// `RewardState::reward_per_share` at last `Stake` update.
+    pub reward_per_share_paid: u128,
+
+    /// Rewards, that can be claimed.
+    pub pending: u64,
+}
+
+impl StakeReward {
+    pub const LEN: usize = 16 + 8;
+}
+
+/// Staked p2swap tokens of wallet (PDA: [STAKE_PREFIX, wallet_pubkey]).
+#[account]
+#[derive(Debug)]
+pub struct Stake {
+    /// Staker wallet.
+    pub wallet: Pubkey,
+
+    /// Mint of staked tokens.
+    pub mint: Pubkey,
+
+    /// Staked amount, that receives rewards.
+    pub amount: u64,
+
+    /// Unstaked amount, that can be withdrawn after cooldown.
+    pub unstaking_amount: u64,
+
+    /// Date, when `unstaking_amount` can be withdrawn.
+    pub unstake_available_at: i64,
+
+    /// Unclaimed rewards per `StakingPool::rewards` mint.
+    pub rewards: Vec<StakeReward>,
+
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Stake {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 4 + MAX_REWARD_MINTS * StakeReward::LEN + 1;
+
+    /// Accrue rewards of current `amount`, should be called before `amount` change or claim.
+    pub fn settle(&mut self, pool: &StakingPool) {
+        for (index, reward) in pool.rewards.iter().enumerate() {
+            if self.rewards.len() <= index {
+                self.rewards.push(StakeReward::default());
+            }
+
+            let stake_reward = &mut self.rewards[index];
+            stake_reward.pending += (self.amount as u128
+                * (reward.reward_per_share - stake_reward.reward_per_share_paid)
+                / REWARD_PER_SHARE_SCALE) as u64;
+            stake_reward.reward_per_share_paid = reward.reward_per_share;
+        }
+    }
+}
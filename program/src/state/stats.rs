@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+/// Quantity of last trades, kept by `PairStats` for TWAP.
+pub const PAIR_STATS_OBSERVATIONS: usize = 32;
+
+/// Denominator of `PriceObservation::price`.
+pub const PRICE_SCALE: u128 = 1_000_000_000;
+
+/// Price observation of executed `Order`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriceObservation {
+    /// Unix timestamp of trade.
+    pub timestamp: i64,
+
+    /// Quote amount per one base token (raw amounts), multiplied by `PRICE_SCALE`.
+    pub price: u128,
+
+    /// Base amount of trade.
+    pub size: u64,
+}
+
+impl PriceObservation {
+    pub const LEN: usize = 8 + 16 + 8;
+}
+
+/// Protocol-wide trading statistics (PDA: [STATS_PREFIX]).
+#[account]
+#[derive(Debug)]
+pub struct Stats {
+    /// Quantity of executed `Order`'s.
+    pub trades_count: u64,
+
+    /// Quantity of mint pairs, that have at least one executed `Order`.
+    pub pairs_count: u64,
+
+    /// Total amount of native `SOL`'s (lamports), traded by `Order`'s with native side.
+    pub native_volume: u128,
+
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl Stats {
+    pub const LEN: usize = 8 + 8 + 8 + 16 + 1;
+}
+
+/// Trading statistics of mint pair (PDA: [PAIR_STATS_PREFIX, order_book_pubkey]).
+#[account]
+#[derive(Debug)]
+pub struct PairStats {
+    /// Mint of funder tokens.
+    pub base_mint: Pubkey,
+
+    /// Mint of recipient tokens.
+    pub quote_mint: Pubkey,
+
+    /// Quantity of executed `Order`'s.
+    pub trades_count: u64,
+
+    /// Total amount of base tokens, traded by pair.
+    pub base_volume: u128,
+
+    /// Total amount of quote tokens, traded by pair.
+    pub quote_volume: u128,
+
+    /// Base amount of last trade.
+    pub last_base_amount: u64,
+
+    /// Quote amount of last trade, last price is `last_quote_amount / last_base_amount`.
+    pub last_quote_amount: u64,
+
+    /// Unix timestamp of last trade.
+    pub last_traded_at: i64,
+
+    /// Ring buffer of last trades price observations.
+    pub observations: [PriceObservation; PAIR_STATS_OBSERVATIONS],
+
+    /// Quantity of filled `observations`.
+    pub observations_count: u8,
+
+    /// Index of `observations` slot, that will be written by next trade.
+    pub next_observation: u8,
+
+    /// PDA bump seed.
+    pub bump: u8,
+}
+
+impl PairStats {
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 8
+        + 16
+        + 16
+        + 8
+        + 8
+        + 8
+        + PriceObservation::LEN * PAIR_STATS_OBSERVATIONS
+        + 1
+        + 1
+        + 1;
+
+    /// Record trade of `base_amount` for `quote_amount` at `timestamp`.
+    pub fn record_trade(&mut self, base_amount: u64, quote_amount: u64, timestamp: i64) {
+        self.trades_count += 1;
+        self.base_volume += base_amount as u128;
+        self.quote_volume += quote_amount as u128;
+        self.last_base_amount = base_amount;
+        self.last_quote_amount = quote_amount;
+        self.last_traded_at = timestamp;
+
+        self.observations[self.next_observation as usize] = PriceObservation {
+            timestamp,
+            price: quote_amount as u128 * PRICE_SCALE / base_amount.max(1) as u128,
+            size: base_amount,
+        };
+        self.next_observation =
+            ((self.next_observation as usize + 1) % PAIR_STATS_OBSERVATIONS) as u8;
+        self.observations_count = (self.observations_count + 1).min(PAIR_STATS_OBSERVATIONS as u8);
+    }
+
+    /// Return observations from oldest to latest.
+    pub fn observations(&self) -> impl Iterator<Item = &PriceObservation> {
+        let count = self.observations_count as usize;
+        let oldest = (self.next_observation as usize + PAIR_STATS_OBSERVATIONS - count)
+            % PAIR_STATS_OBSERVATIONS;
+
+        (0..count).map(move |i| &self.observations[(oldest + i) % PAIR_STATS_OBSERVATIONS])
+    }
+
+    /// Return time-weighted average price (scaled by `PRICE_SCALE`) over `window` seconds till `now`.
+    ///
+    /// Every observation price lasts till next observation (latest one till `now`),
+    /// time before oldest observation is not counted.
+    /// Return `None` if there are no trades at or before `now` for window.
+    pub fn twap(&self, now: i64, window: i64) -> Option<u128> {
+        let start = now.saturating_sub(window);
+
+        let mut weighted_price: u128 = 0;
+        let mut duration: u128 = 0;
+        let mut latest: Option<&PriceObservation> = None;
+
+        let mut observations = self.observations().peekable();
+
+        while let Some(observation) = observations.next() {
+            let end = observations
+                .peek()
+                .map_or(now, |next| next.timestamp)
+                .min(now);
+            let begin = observation.timestamp.max(start);
+
+            if end > begin {
+                let elapsed = (end - begin) as u128;
+
+                weighted_price =
+                    weighted_price.saturating_add(observation.price.saturating_mul(elapsed));
+                duration += elapsed;
+            }
+
+            if observation.timestamp <= now {
+                latest = Some(observation);
+            }
+        }
+
+        if let Some(price) = weighted_price.checked_div(duration) {
+            return Some(price);
+        }
+
+        // Window holds only trades of `now` moment
+        latest
+            .filter(|observation| observation.timestamp >= start)
+            .map(|observation| observation.price)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{error, utils};
+use anchor_lang::prelude::*;
+
+/// Metaplex token metadata program.
+pub mod token_metadata_program {
+    anchor_lang::declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+/// `Key::MetadataV1` of Metaplex token metadata program.
+pub const METADATA_V1_KEY: u8 = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MetadataCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MetadataCollection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// Leading part of Metaplex token metadata account layout (up to `collection`).
+/// Fields after `collection` are not used and skipped while deserialization.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Metadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<MetadataCreator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<MetadataCollection>,
+}
+
+impl Metadata {
+    /// Load metadata from account, that should be metadata PDA of its `mint`.
+    pub fn from_account_info(metadata: &AccountInfo) -> Result<Metadata> {
+        if *metadata.owner != token_metadata_program::ID {
+            return Err(error::ErrorCode::InvalidTokenMetadata.into());
+        }
+
+        let data = metadata.try_borrow_data()?;
+        let metadata_state = Metadata::deserialize(&mut &data[..])
+            .map_err(|_| error::ErrorCode::InvalidTokenMetadata)?;
+
+        if metadata_state.key != METADATA_V1_KEY
+            || utils::find_metadata_address(&metadata_state.mint).0 != *metadata.key
+        {
+            return Err(error::ErrorCode::InvalidTokenMetadata.into());
+        }
+
+        Ok(metadata_state)
+    }
+
+    /// Check if `mint` is verified member of `collection`.
+    pub fn is_verified_member_of(&self, collection: &Pubkey) -> bool {
+        match &self.collection {
+            Some(metadata_collection) => {
+                metadata_collection.verified && metadata_collection.key == *collection
+            }
+            None => false,
+        }
+    }
+}
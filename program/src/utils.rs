@@ -1,11 +1,27 @@
-use crate::id;
+use crate::{error, id};
 use anchor_lang::{
     prelude::*,
-    solana_program::{program::invoke_signed, system_instruction},
+    solana_program::{hash::hashv, program::invoke_signed, system_instruction},
 };
+use anchor_spl::token;
 
 pub const ORDER_ESCROW_PREFIX: &str = "p2s_order_escrow";
 pub const ORDER_ESCROW_NATIVE_SIZE: usize = 8 + 1;
+pub const AUCTION_BID_PREFIX: &str = "p2s_auction_bid";
+pub const AUCTION_BID_ESCROW_PREFIX: &str = "p2s_auction_bid_escrow";
+pub const ENGLISH_BID_PREFIX: &str = "p2s_english_bid";
+pub const RFQ_QUOTE_PREFIX: &str = "p2s_rfq_quote";
+pub const ORDER_BOOK_PREFIX: &str = "p2s_order_book";
+pub const PROTOCOL_PREFIX: &str = "p2s_protocol";
+pub const REFERRER_PREFIX: &str = "p2s_referrer";
+pub const PROFILE_PREFIX: &str = "p2s_profile";
+pub const STATS_PREFIX: &str = "p2s_stats";
+pub const PAIR_STATS_PREFIX: &str = "p2s_pair_stats";
+pub const STAKING_POOL_PREFIX: &str = "p2s_staking_pool";
+pub const STAKE_VAULT_PREFIX: &str = "p2s_stake_vault";
+pub const STAKE_PREFIX: &str = "p2s_stake";
+pub const REWARD_VAULT_PREFIX: &str = "p2s_reward_vault";
+pub const METADATA_PREFIX: &str = "metadata";
 
 /// Return `Order` tokens escrow `Pubkey` and bump seed.
 pub fn find_order_escrow_address(funder_wallet: &Pubkey, order: &Pubkey) -> (Pubkey, u8) {
@@ -19,6 +35,145 @@ pub fn find_order_escrow_address(funder_wallet: &Pubkey, order: &Pubkey) -> (Pub
     )
 }
 
+/// Return `Auction` `Bid` `Pubkey` and bump seed.
+pub fn find_auction_bid_address(auction: &Pubkey, bidder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            AUCTION_BID_PREFIX.as_bytes(),
+            auction.as_ref(),
+            bidder.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+/// Return `EnglishAuction` bids escrow `Pubkey` and bump seed.
+pub fn find_auction_bid_escrow_address(auction: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AUCTION_BID_ESCROW_PREFIX.as_bytes(), auction.as_ref()],
+        &id(),
+    )
+}
+
+/// Return `EnglishAuction` `EnglishBid` `Pubkey` and bump seed.
+pub fn find_english_bid_address(auction: &Pubkey, bidder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ENGLISH_BID_PREFIX.as_bytes(),
+            auction.as_ref(),
+            bidder.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+/// Return `Rfq` `Quote` `Pubkey` and bump seed.
+pub fn find_rfq_quote_address(rfq: &Pubkey, maker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RFQ_QUOTE_PREFIX.as_bytes(), rfq.as_ref(), maker.as_ref()],
+        &id(),
+    )
+}
+
+/// Return `OrderBook` `Pubkey` and bump seed for mint pair.
+pub fn find_order_book_address(base_mint: &Pubkey, quote_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ORDER_BOOK_PREFIX.as_bytes(),
+            base_mint.as_ref(),
+            quote_mint.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+/// Return `Protocol` configuration `Pubkey` and bump seed.
+pub fn find_protocol_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROTOCOL_PREFIX.as_bytes()], &id())
+}
+
+/// Return `Referrer` `Pubkey` and bump seed for referrer wallet and payouts mint.
+pub fn find_referrer_address(wallet: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFERRER_PREFIX.as_bytes(), wallet.as_ref(), mint.as_ref()],
+        &id(),
+    )
+}
+
+/// Return `Profile` `Pubkey` and bump seed for wallet.
+pub fn find_profile_address(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROFILE_PREFIX.as_bytes(), wallet.as_ref()], &id())
+}
+
+/// Return protocol-wide `Stats` `Pubkey` and bump seed.
+pub fn find_stats_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STATS_PREFIX.as_bytes()], &id())
+}
+
+/// Return `PairStats` `Pubkey` and bump seed for mint pair `OrderBook`.
+pub fn find_pair_stats_address(order_book: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAIR_STATS_PREFIX.as_bytes(), order_book.as_ref()], &id())
+}
+
+/// Return `StakingPool` `Pubkey` and bump seed.
+pub fn find_staking_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKING_POOL_PREFIX.as_bytes()], &id())
+}
+
+/// Return `StakingPool` vault `Pubkey` and bump seed for staked tokens.
+pub fn find_stake_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_VAULT_PREFIX.as_bytes()], &id())
+}
+
+/// Return `Stake` `Pubkey` and bump seed for wallet.
+pub fn find_stake_address(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_PREFIX.as_bytes(), wallet.as_ref()], &id())
+}
+
+/// Return `StakingPool` vault `Pubkey` and bump seed for rewards in `mint`.
+pub fn find_reward_vault_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REWARD_VAULT_PREFIX.as_bytes(), mint.as_ref()], &id())
+}
+
+/// Return Metaplex token metadata `Pubkey` and bump seed of `mint`.
+pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    let program_id = crate::state::token_metadata_program::id();
+
+    Pubkey::find_program_address(
+        &[
+            METADATA_PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+        ],
+        &program_id,
+    )
+}
+
+/// Return sealed `Bid` commitment.
+///
+/// Commitment: sha256(auction_pubkey, bidder_pubkey, amount (LE), salt).
+pub fn hash_bid(auction: &Pubkey, bidder: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[
+        auction.as_ref(),
+        bidder.as_ref(),
+        &amount.to_le_bytes(),
+        salt.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Return hidden `Order` recipient commitment: sha256(recipient, salt).
+pub fn hash_recipient(recipient: &Pubkey, salt: &[u8; 32]) -> [u8; 32] {
+    hashv(&[recipient.as_ref(), salt.as_ref()]).to_bytes()
+}
+
+/// Return hash-time-locked `Order` hashlock of `preimage`.
+///
+/// Hashlock: sha256(preimage).
+pub fn hash_preimage(preimage: &[u8; 32]) -> [u8; 32] {
+    hashv(&[preimage.as_ref()]).to_bytes()
+}
+
 /// Move lamports from `src` to `dst` account.
 #[inline(always)]
 pub fn move_lamports<'a>(
@@ -58,7 +213,7 @@ pub fn sys_transfer<'a>(
     invoke_signed(
         &system_instruction::transfer(from.key, to.key, lamports),
         &[from.clone(), to.clone()],
-        &[&signer_seeds],
+        &[signer_seeds],
     )?;
 
     Ok(())
@@ -77,8 +232,251 @@ pub fn sys_create_account<'a>(
     invoke_signed(
         &system_instruction::create_account(from.key, to.key, lamports, space as u64, owner),
         &[from.clone(), to.clone()],
-        &[&signer_seeds],
+        &[signer_seeds],
     )?;
 
     Ok(())
 }
+
+/// Create empty `escrow` account (PDA).
+///
+/// If `is_native`, then `escrow` will hold native `SOL`'s.
+/// Otherwise `escrow` will be initialized as `spl_token` account of `mint`.
+pub fn init_escrow<'a>(
+    payer: &AccountInfo<'a>,
+    escrow: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    rent_sysvar: &Sysvar<'a, Rent>,
+    token_program: &AccountInfo<'a>,
+    is_native: bool,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    if is_native {
+        // Create native escrow account to hold native `SOL`'s
+        sys_create_account(
+            payer,
+            escrow,
+            rent_sysvar.minimum_balance(ORDER_ESCROW_NATIVE_SIZE),
+            ORDER_ESCROW_NATIVE_SIZE,
+            &id(),
+            escrow_seeds,
+        )
+    } else {
+        // Create `spl` escrow account to hold `spl_token`'s
+        sys_create_account(
+            payer,
+            escrow,
+            rent_sysvar.minimum_balance(token::TokenAccount::LEN),
+            token::TokenAccount::LEN,
+            &token::Token::id(),
+            escrow_seeds,
+        )?;
+
+        // Initialize escrow `spl_token` account
+        let cpi_accounts = token::InitializeAccount {
+            account: escrow.clone(),
+            mint: mint.clone(),
+            authority: escrow.clone(),
+            rent: rent_sysvar.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
+        token::initialize_account(cpi_ctx)
+    }
+}
+
+/// Create `escrow` account (PDA) and lock `amount` of `authority`'s tokens from `source` in it.
+///
+/// If `is_native`, then `escrow` will hold native `SOL`'s and `source` should be `authority` wallet.
+/// Otherwise `escrow` will be initialized as `spl_token` account of `mint`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_escrow<'a>(
+    payer: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    escrow: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    rent_sysvar: &Sysvar<'a, Rent>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    is_native: bool,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    init_escrow(
+        payer,
+        escrow,
+        mint,
+        rent_sysvar,
+        token_program,
+        is_native,
+        escrow_seeds,
+    )?;
+
+    transfer(source, escrow, authority, token_program, amount, is_native)
+}
+
+/// Transfer `amount` of tokens from `source` (owned by `authority`) to `destination`.
+///
+/// If `is_native`, then `source` should be `authority` wallet.
+pub fn transfer<'a>(
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    is_native: bool,
+) -> Result<()> {
+    if is_native {
+        sys_transfer(source, destination, amount, &[])
+    } else {
+        let cpi_accounts = token::Transfer {
+            from: source.clone(),
+            to: destination.clone(),
+            authority: authority.clone(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)
+    }
+}
+
+/// Transfer `amount` of tokens from `escrow` (PDA) to `destination`.
+pub fn transfer_from_escrow<'a>(
+    escrow: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    is_native: bool,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    if is_native {
+        move_lamports(escrow, destination, amount)
+    } else {
+        let signer_seeds: &[&[&[u8]]] = &[escrow_seeds];
+
+        let cpi_accounts = token::Transfer {
+            from: escrow.clone(),
+            to: destination.clone(),
+            authority: escrow.clone(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)
+    }
+}
+
+/// Close `escrow` (PDA), rent and all remaining native `SOL`'s will be transferred to `receiver`.
+///
+/// `spl_token` `escrow` should be empty.
+pub fn close_escrow<'a>(
+    escrow: &AccountInfo<'a>,
+    receiver: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    is_native: bool,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    if is_native {
+        delete_account(escrow, receiver)
+    } else {
+        let signer_seeds: &[&[&[u8]]] = &[escrow_seeds];
+
+        let cpi_accounts = token::CloseAccount {
+            account: escrow.clone(),
+            destination: receiver.clone(),
+            authority: escrow.clone(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)
+    }
+}
+
+/// Close `Order` `escrow` (PDA), rent will be refunded to `rent_payer`
+/// and all remaining native `SOL`'s will be transferred to `receiver`.
+///
+/// `spl_token` `escrow` should be empty.
+pub fn close_order_escrow<'a>(
+    escrow: &AccountInfo<'a>,
+    receiver: &AccountInfo<'a>,
+    rent_payer: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    is_native: bool,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    if is_native && receiver.key != rent_payer.key {
+        let amount = escrow_amount(escrow, true)?;
+        move_lamports(escrow, receiver, amount)?;
+    }
+
+    close_escrow(escrow, rent_payer, token_program, is_native, escrow_seeds)
+}
+
+/// Return amount of tokens, that `escrow` holds (rent excluded).
+pub fn escrow_amount(escrow: &AccountInfo, is_native: bool) -> Result<u64> {
+    if is_native {
+        let rent = Rent::get()?.minimum_balance(ORDER_ESCROW_NATIVE_SIZE);
+        Ok(escrow.lamports().saturating_sub(rent))
+    } else {
+        let escrow_token_account =
+            token::TokenAccount::try_deserialize_unchecked(&mut escrow.data.borrow().as_ref())?;
+        Ok(escrow_token_account.amount)
+    }
+}
+
+/// Check if `token_account` is owned by `wallet` and holds `mint` tokens
+/// (`token_account` should be `wallet` if `mint` is `System::id()`).
+pub fn is_token_account_of(
+    token_account: &AccountInfo,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Result<bool> {
+    if *mint == System::id() {
+        return Ok(token_account.key == wallet);
+    }
+
+    if *token_account.owner != token::ID {
+        return Ok(false);
+    }
+
+    let account = token::TokenAccount::try_deserialize(&mut &token_account.try_borrow_data()?[..])?;
+
+    Ok(account.owner == *wallet && account.mint == *mint)
+}
+
+/// Return `mint` balance of `wallet`, that is held by `token_account`.
+pub fn gate_amount(token_account: &AccountInfo, wallet: &Pubkey, mint: &Pubkey) -> Result<u64> {
+    if *mint == System::id() || !is_token_account_of(token_account, wallet, mint)? {
+        return Err(error::ErrorCode::GateIsNotPassed.into());
+    }
+
+    let account = token::TokenAccount::try_deserialize(&mut &token_account.try_borrow_data()?[..])?;
+
+    Ok(account.amount)
+}
+
+/// Return `discount_mint` balance of `wallet`, that is held by `discount_token_account`
+/// (`spl_token` account or `Stake`).
+pub fn discount_amount(
+    discount_token_account: &AccountInfo,
+    wallet: &Pubkey,
+    discount_mint: &Pubkey,
+) -> Result<u64> {
+    if *discount_token_account.owner == id() {
+        let stake = Account::<crate::state::Stake>::try_from(discount_token_account)?;
+
+        if stake.wallet != *wallet || stake.mint != *discount_mint {
+            return Err(error::ErrorCode::DiscountAccountMismatch.into());
+        }
+
+        return Ok(stake.amount);
+    }
+
+    if *discount_mint == System::id()
+        || !is_token_account_of(discount_token_account, wallet, discount_mint)?
+    {
+        return Err(error::ErrorCode::DiscountAccountMismatch.into());
+    }
+
+    let account =
+        token::TokenAccount::try_deserialize(&mut &discount_token_account.try_borrow_data()?[..])?;
+
+    Ok(account.amount)
+}
@@ -0,0 +1,329 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer, sysvar};
+
+const DISPUTE_WINDOW: i64 = 3600;
+
+/// Create arbitrated `Order`, that sells native `SOL`'s for off-chain leg,
+/// then claim delivery by `recipient`.
+async fn create_and_claim_delivery(
+    context: &mut ProgramTestContext,
+    order: &Keypair,
+    funder: &Keypair,
+    recipient: &Keypair,
+    arbiter: &Pubkey,
+    base_amount: u64,
+) {
+    // Quote leg is settled off-chain, so nothing is paid here
+    utils::create_order(
+        context,
+        order,
+        funder,
+        &recipient.pubkey(),
+        base_amount,
+        0,
+        utils::OrderParams {
+            arbitration: Some(p2swap::state::ArbitrationArgs {
+                arbiter: *arbiter,
+                dispute_window: DISPUTE_WINDOW,
+            }),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let accounts = p2swap::accounts::ClaimDelivery {
+        order: order.pubkey(),
+        recipient: recipient.pubkey(),
+        clock_sysvar: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::ClaimDelivery {}.data(),
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, recipient, &[recipient])
+        .await
+        .unwrap();
+}
+
+async fn release_order(
+    context: &mut ProgramTestContext,
+    order: &Pubkey,
+    funder: &Pubkey,
+    recipient: &Pubkey,
+    authority: &Keypair,
+) -> std::result::Result<(), BanksClientError> {
+    let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(funder, order);
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let accounts = p2swap::accounts::ReleaseOrder {
+        order: *order,
+        authority: authority.pubkey(),
+        funder: *funder,
+        rent_payer: *funder,
+        recipient: *recipient,
+        recipient_receive_token_account: *recipient,
+        escrow,
+        order_book,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::ReleaseOrder { escrow_bump }.data(),
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, authority, &[authority]).await
+}
+
+#[tokio::test]
+async fn success_release_by_funder() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let arbiter = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), 10u64.pow(8)).await;
+
+    create_and_claim_delivery(
+        &mut context,
+        &order,
+        &funder,
+        &recipient,
+        &arbiter.pubkey(),
+        base_amount,
+    )
+    .await;
+
+    let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+    assert_eq!(
+        order_state.status().unwrap(),
+        p2swap::state::OrderStatus::DeliveryClaimed
+    );
+
+    release_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient.pubkey(),
+        &funder,
+    )
+    .await
+    .unwrap();
+
+    let recipient_account = context
+        .banks_client
+        .get_account(recipient.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(recipient_account.lamports > base_amount);
+
+    let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+    assert_eq!(
+        order_state.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
+}
+
+#[tokio::test]
+async fn success_prune_claimed_order() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let arbiter = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), 10u64.pow(8)).await;
+
+    create_and_claim_delivery(
+        &mut context,
+        &order,
+        &funder,
+        &recipient,
+        &arbiter.pubkey(),
+        base_amount,
+    )
+    .await;
+
+    // Claimed `Order` can't be executed, so it is pruned from `OrderBook`
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    utils::prune_order_book(&mut context, &order_book, &[order.pubkey()])
+        .await
+        .unwrap();
+
+    let order_book_state = utils::get_order_book(&mut context, &order_book).await;
+    assert!(order_book_state.entries.is_empty());
+
+    release_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient.pubkey(),
+        &funder,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn success_resolve_dispute_refund() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let arbiter = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), 10u64.pow(8)).await;
+    utils::airdrop(&mut context, &arbiter.pubkey(), 10u64.pow(8)).await;
+
+    create_and_claim_delivery(
+        &mut context,
+        &order,
+        &funder,
+        &recipient,
+        &arbiter.pubkey(),
+        base_amount,
+    )
+    .await;
+
+    // DisputeOrder
+    let accounts = p2swap::accounts::DisputeOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        clock_sysvar: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::DisputeOrder {}.data(),
+        accounts,
+    };
+
+    utils::process_instruction(&mut context, instruction, &funder, &[&funder])
+        .await
+        .unwrap();
+
+    // ResolveDispute
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let accounts = p2swap::accounts::ResolveDispute {
+        order: order.pubkey(),
+        arbiter: arbiter.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        recipient: recipient.pubkey(),
+        funder_token_account: funder.pubkey(),
+        recipient_receive_token_account: recipient.pubkey(),
+        escrow,
+        order_book,
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::ResolveDispute {
+        escrow_bump,
+        release: false,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(&mut context, instruction, &arbiter, &[&arbiter])
+        .await
+        .unwrap();
+
+    let escrow_account = context.banks_client.get_account(escrow).await.unwrap();
+    assert!(escrow_account.is_none());
+
+    let funder_account = context
+        .banks_client
+        .get_account(funder.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(funder_account.lamports > base_amount);
+
+    let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+    assert_eq!(
+        order_state.status().unwrap(),
+        p2swap::state::OrderStatus::Canceled
+    );
+}
+
+#[tokio::test]
+async fn fail_dispute_window_is_open() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let arbiter = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), 10u64.pow(8)).await;
+
+    create_and_claim_delivery(
+        &mut context,
+        &order,
+        &funder,
+        &recipient,
+        &arbiter.pubkey(),
+        base_amount,
+    )
+    .await;
+
+    let error = release_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient.pubkey(),
+        &recipient,
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6064 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
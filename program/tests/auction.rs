@@ -0,0 +1,744 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    borsh::try_from_slice_unchecked, instruction::Instruction, signature::Keypair, signer::Signer,
+    sysvar,
+};
+
+#[allow(clippy::too_many_arguments)]
+async fn create_auction(
+    context: &mut ProgramTestContext,
+    auction: &Keypair,
+    funder: &Keypair,
+    base_mint: &Pubkey,
+    funder_token_account: &Pubkey,
+    base_amount: u64,
+    min_bid_amount: u64,
+    commit_end_date: i64,
+    reveal_end_date: i64,
+) -> std::result::Result<(), BanksClientError> {
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &auction.pubkey());
+
+    let accounts = p2swap::accounts::CreateAuction {
+        auction: auction.pubkey(),
+        funder: funder.pubkey(),
+        funder_token_account: *funder_token_account,
+        escrow,
+        quote_token_account: funder.pubkey(),
+        escrow_mint: *base_mint,
+        quote_mint: System::id(),
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateAuction {
+        escrow_bump,
+        base_amount,
+        min_bid_amount,
+        start_date: None,
+        commit_end_date,
+        reveal_end_date,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, funder, &[funder, auction]).await
+}
+
+async fn commit_bid(
+    context: &mut ProgramTestContext,
+    auction: &Pubkey,
+    bidder: &Keypair,
+    receive_token_account: &Pubkey,
+    amount: u64,
+    salt: &[u8; 32],
+    deposit_amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let (bid, _) = p2swap::utils::find_auction_bid_address(auction, &bidder.pubkey());
+    let (deposit, deposit_bump) =
+        p2swap::utils::find_order_escrow_address(&bidder.pubkey(), auction);
+
+    let accounts = p2swap::accounts::CommitBid {
+        auction: *auction,
+        bid,
+        bidder: bidder.pubkey(),
+        bidder_token_account: bidder.pubkey(),
+        receive_token_account: *receive_token_account,
+        deposit,
+        quote_mint: System::id(),
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CommitBid {
+        deposit_bump,
+        commitment: p2swap::utils::hash_bid(auction, &bidder.pubkey(), amount, salt),
+        deposit_amount,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, bidder, &[bidder]).await
+}
+
+async fn reveal_bid(
+    context: &mut ProgramTestContext,
+    auction: &Pubkey,
+    bidder: &Keypair,
+    amount: u64,
+    salt: [u8; 32],
+) -> std::result::Result<(), BanksClientError> {
+    let (bid, _) = p2swap::utils::find_auction_bid_address(auction, &bidder.pubkey());
+
+    let accounts = p2swap::accounts::RevealBid {
+        auction: *auction,
+        bid,
+        bidder: bidder.pubkey(),
+        clock_sysvar: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::RevealBid { amount, salt }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, bidder, &[bidder]).await
+}
+
+async fn settle_auction(
+    context: &mut ProgramTestContext,
+    auction: &Pubkey,
+    funder: &Pubkey,
+    quote_token_account: &Pubkey,
+    bidder: &Pubkey,
+    receive_token_account: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(funder, auction);
+    let (bid, _) = p2swap::utils::find_auction_bid_address(auction, bidder);
+    let (deposit, deposit_bump) = p2swap::utils::find_order_escrow_address(bidder, auction);
+
+    let accounts = p2swap::accounts::SettleAuction {
+        auction: *auction,
+        funder: *funder,
+        escrow,
+        quote_token_account: *quote_token_account,
+        bid,
+        bidder: *bidder,
+        refund_token_account: *bidder,
+        receive_token_account: *receive_token_account,
+        deposit,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::SettleAuction {
+        escrow_bump,
+        deposit_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    utils::process_instruction(context, instruction, &payer, &[&payer]).await
+}
+
+async fn refund_bid(
+    context: &mut ProgramTestContext,
+    auction: &Pubkey,
+    bidder: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let (bid, _) = p2swap::utils::find_auction_bid_address(auction, bidder);
+    let (deposit, deposit_bump) = p2swap::utils::find_order_escrow_address(bidder, auction);
+
+    let accounts = p2swap::accounts::RefundBid {
+        auction: *auction,
+        bid,
+        bidder: *bidder,
+        refund_token_account: *bidder,
+        deposit,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::RefundBid { deposit_bump }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    utils::process_instruction(context, instruction, &payer, &[&payer]).await
+}
+
+#[tokio::test]
+async fn success_native() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder_a = Keypair::new();
+    let bidder_b = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+    let bidder_balance = 10 * 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let commit_end_date = now + 300;
+    let reveal_end_date = now + 1000;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &bidder_a.pubkey(), bidder_balance).await;
+    utils::airdrop(&mut context, &bidder_b.pubkey(), bidder_balance).await;
+
+    create_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &System::id(),
+        &funder.pubkey(),
+        base_amount,
+        min_bid_amount,
+        commit_end_date,
+        reveal_end_date,
+    )
+    .await
+    .unwrap();
+
+    let salt_a = [1u8; 32];
+    let salt_b = [2u8; 32];
+
+    commit_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_a,
+        &bidder_a.pubkey(),
+        5 * 10u64.pow(9),
+        &salt_a,
+        6 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+    commit_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_b,
+        &bidder_b.pubkey(),
+        7 * 10u64.pow(9),
+        &salt_b,
+        8 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    utils::wait_until(&mut context, commit_end_date).await;
+
+    reveal_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_a,
+        5 * 10u64.pow(9),
+        salt_a,
+    )
+    .await
+    .unwrap();
+    reveal_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_b,
+        7 * 10u64.pow(9),
+        salt_b,
+    )
+    .await
+    .unwrap();
+
+    let auction_account = context
+        .banks_client
+        .get_account(auction.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let auction_state =
+        try_from_slice_unchecked::<p2swap::state::Auction>(&auction_account.data[8..]).unwrap();
+    assert_eq!(auction_state.bids_count, 2);
+    assert_eq!(auction_state.highest_bid_amount, 7 * 10u64.pow(9));
+    assert_eq!(auction_state.winner, bidder_b.pubkey());
+
+    utils::wait_until(&mut context, reveal_end_date).await;
+
+    let funder_balance_before = context
+        .banks_client
+        .get_account(funder.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    settle_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &bidder_b.pubkey(),
+        &bidder_b.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    refund_bid(&mut context, &auction.pubkey(), &bidder_a.pubkey())
+        .await
+        .unwrap();
+
+    let (escrow, _) = p2swap::utils::find_order_escrow_address(&funder.pubkey(), &auction.pubkey());
+    let escrow_account = context.banks_client.get_account(escrow).await.unwrap();
+    assert!(escrow_account.is_none());
+
+    let (bid_b, _) = p2swap::utils::find_auction_bid_address(&auction.pubkey(), &bidder_b.pubkey());
+    let bid_account = context.banks_client.get_account(bid_b).await.unwrap();
+    assert!(bid_account.is_none());
+
+    let (deposit_b, _) =
+        p2swap::utils::find_order_escrow_address(&bidder_b.pubkey(), &auction.pubkey());
+    let deposit_account = context.banks_client.get_account(deposit_b).await.unwrap();
+    assert!(deposit_account.is_none());
+
+    let funder_balance_after = context
+        .banks_client
+        .get_account(funder.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(funder_balance_after > funder_balance_before + 7 * 10u64.pow(9));
+
+    let bidder_a_balance = context
+        .banks_client
+        .get_account(bidder_a.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(bidder_a_balance > bidder_balance - 10u64.pow(6));
+
+    let bidder_b_balance = context
+        .banks_client
+        .get_account(bidder_b.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(bidder_b_balance > bidder_balance + 2 * 10u64.pow(9));
+
+    let auction_account = context
+        .banks_client
+        .get_account(auction.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let auction_state =
+        try_from_slice_unchecked::<p2swap::state::Auction>(&auction_account.data[8..]).unwrap();
+    assert_eq!(auction_state.status, p2swap::state::AuctionStatus::Settled);
+}
+
+#[tokio::test]
+async fn fail_commit_phase_is_over() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let commit_end_date = now + 20;
+    let reveal_end_date = now + 10000;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &bidder.pubkey(), base_amount).await;
+
+    create_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &System::id(),
+        &funder.pubkey(),
+        base_amount,
+        min_bid_amount,
+        commit_end_date,
+        reveal_end_date,
+    )
+    .await
+    .unwrap();
+
+    utils::wait_until(&mut context, commit_end_date).await;
+
+    let error = commit_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        &bidder.pubkey(),
+        min_bid_amount,
+        &[1u8; 32],
+        min_bid_amount,
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6013 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn fail_bid_commitment_mismatch() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let commit_end_date = now + 20;
+    let reveal_end_date = now + 10000;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &bidder.pubkey(), base_amount).await;
+
+    create_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &System::id(),
+        &funder.pubkey(),
+        base_amount,
+        min_bid_amount,
+        commit_end_date,
+        reveal_end_date,
+    )
+    .await
+    .unwrap();
+
+    commit_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        &bidder.pubkey(),
+        min_bid_amount,
+        &[1u8; 32],
+        min_bid_amount,
+    )
+    .await
+    .unwrap();
+
+    utils::wait_until(&mut context, commit_end_date).await;
+
+    let error = reveal_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        min_bid_amount,
+        [2u8; 32],
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6016 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn fail_bid_is_not_refundable() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let commit_end_date = now + 1000;
+    let reveal_end_date = now + 10000;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &bidder.pubkey(), base_amount).await;
+
+    create_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &System::id(),
+        &funder.pubkey(),
+        base_amount,
+        min_bid_amount,
+        commit_end_date,
+        reveal_end_date,
+    )
+    .await
+    .unwrap();
+
+    commit_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        &bidder.pubkey(),
+        min_bid_amount,
+        &[1u8; 32],
+        min_bid_amount,
+    )
+    .await
+    .unwrap();
+
+    let error = refund_bid(&mut context, &auction.pubkey(), &bidder.pubkey())
+        .await
+        .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6022 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn success_settle_after_winner_account_closed() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let commit_end_date = now + 300;
+    let reveal_end_date = now + 1000;
+
+    utils::airdrop(&mut context, &funder.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &bidder.pubkey(), 10 * 10u64.pow(9)).await;
+
+    let mint = Keypair::new();
+    let funder_token_account = Keypair::new();
+    let bidder_token_account = Keypair::new();
+    let bidder_new_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &funder.pubkey(), 9).await;
+    for (account, owner) in [
+        (&funder_token_account, &funder),
+        (&bidder_token_account, &bidder),
+        (&bidder_new_token_account, &bidder),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), &owner.pubkey()).await;
+    }
+    utils::mint_to(
+        &mut context,
+        &mint.pubkey(),
+        &funder_token_account.pubkey(),
+        &funder,
+        base_amount,
+    )
+    .await;
+
+    create_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &mint.pubkey(),
+        &funder_token_account.pubkey(),
+        base_amount,
+        min_bid_amount,
+        commit_end_date,
+        reveal_end_date,
+    )
+    .await
+    .unwrap();
+
+    let salt = [1u8; 32];
+
+    // Receive account should belong to `bidder`
+    let error = commit_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        &funder_token_account.pubkey(),
+        2 * 10u64.pow(9),
+        &salt,
+        2 * 10u64.pow(9),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6003));
+
+    commit_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        &bidder_token_account.pubkey(),
+        2 * 10u64.pow(9),
+        &salt,
+        2 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    utils::wait_until(&mut context, commit_end_date).await;
+
+    reveal_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        2 * 10u64.pow(9),
+        salt,
+    )
+    .await
+    .unwrap();
+
+    // Winner closes its (empty) receive token account before settlement
+    let instruction = spl_token::instruction::close_account(
+        &spl_token::id(),
+        &bidder_token_account.pubkey(),
+        &bidder.pubkey(),
+        &bidder.pubkey(),
+        &[],
+    )
+    .unwrap();
+    utils::process_instruction(&mut context, instruction, &bidder, &[&bidder])
+        .await
+        .unwrap();
+
+    utils::wait_until(&mut context, reveal_end_date).await;
+
+    // Escrow tokens can't be sent to account of other wallet
+    let error = settle_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &bidder.pubkey(),
+        &funder_token_account.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6003));
+
+    // Winning bid can't be sent to wallet of other user
+    let error = settle_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &bidder.pubkey(),
+        &bidder.pubkey(),
+        &bidder_new_token_account.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6000));
+
+    settle_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &bidder.pubkey(),
+        &bidder_new_token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &bidder_new_token_account.pubkey()).await,
+        base_amount
+    );
+
+    let (escrow, _) = p2swap::utils::find_order_escrow_address(&funder.pubkey(), &auction.pubkey());
+    let escrow_account = context.banks_client.get_account(escrow).await.unwrap();
+    assert!(escrow_account.is_none());
+}
@@ -0,0 +1,201 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    sysvar,
+};
+
+/// Create batch of `Order`'s, that sell native `SOL`'s for native `SOL`'s.
+async fn batch_create_orders(
+    context: &mut ProgramTestContext,
+    orders: &[Keypair],
+    funder: &Keypair,
+    recipient: &Pubkey,
+    base_amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let mut accounts = p2swap::accounts::BatchCreateOrders {
+        funder: funder.pubkey(),
+        funder_token_account: funder.pubkey(),
+        quote_token_account: funder.pubkey(),
+        escrow_mint: System::id(),
+        quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let mut args = Vec::new();
+
+    for (i, order) in orders.iter().enumerate() {
+        let (escrow, escrow_bump) =
+            p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+
+        accounts.push(AccountMeta::new(order.pubkey(), true));
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new_readonly(*recipient, false));
+
+        args.push(p2swap::state::BatchOrderArgs {
+            escrow_bump,
+            base_amount,
+            quote_amount: base_amount * (i as u64 + 1),
+            start_date: None,
+            expire_date: 9999999999,
+            price_curve: p2swap::state::PriceCurve::Fixed,
+            floor_quote_amount: 0,
+            cancel_locked_until: None,
+            memo: None,
+            reference: None,
+        });
+    }
+
+    let data = p2swap::instruction::BatchCreateOrders { orders: args }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let mut signers = vec![funder];
+    signers.extend(orders.iter());
+
+    utils::process_instruction(context, instruction, funder, &signers).await
+}
+
+#[tokio::test]
+async fn success_batch_create_cancel() {
+    let mut context = utils::setup_test_context().await;
+
+    let orders = vec![Keypair::new(), Keypair::new(), Keypair::new()];
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), 4 * base_amount).await;
+
+    batch_create_orders(
+        &mut context,
+        &orders,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+    )
+    .await
+    .unwrap();
+
+    for (i, order) in orders.iter().enumerate() {
+        let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+        assert_eq!(
+            order_state.status().unwrap(),
+            p2swap::state::OrderStatus::Created
+        );
+        assert_eq!(order_state.funder, funder.pubkey());
+        assert_eq!(order_state.recipient, recipient.pubkey());
+        assert_eq!({ order_state.quote_amount }, base_amount * (i as u64 + 1));
+    }
+
+    // BatchCancelOrders
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let mut accounts = p2swap::accounts::BatchCancelOrders {
+        funder: funder.pubkey(),
+        funder_token_account: funder.pubkey(),
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let mut escrows = Vec::new();
+    let mut escrow_bumps = Vec::new();
+
+    for order in orders.iter() {
+        let (escrow, escrow_bump) =
+            p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+
+        accounts.push(AccountMeta::new(order.pubkey(), false));
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new(funder.pubkey(), false));
+
+        escrows.push(escrow);
+        escrow_bumps.push(escrow_bump);
+    }
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::BatchCancelOrders { escrow_bumps }.data(),
+        accounts,
+    };
+
+    utils::process_instruction(&mut context, instruction, &funder, &[&funder])
+        .await
+        .unwrap();
+
+    for (order, escrow) in orders.iter().zip(escrows) {
+        let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+        assert_eq!(
+            order_state.status().unwrap(),
+            p2swap::state::OrderStatus::Canceled
+        );
+
+        let escrow_account = context.banks_client.get_account(escrow).await.unwrap();
+        assert!(escrow_account.is_none());
+    }
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+    let order_book_account = context
+        .banks_client
+        .get_account(order_book)
+        .await
+        .unwrap()
+        .unwrap();
+    let order_book =
+        p2swap::state::OrderBook::try_deserialize(&mut order_book_account.data.as_slice()).unwrap();
+    assert!(order_book.entries.is_empty());
+}
+
+#[tokio::test]
+async fn fail_batch_recipient_match_funder() {
+    let mut context = utils::setup_test_context().await;
+
+    let orders = vec![Keypair::new(), Keypair::new()];
+    let funder = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), 3 * base_amount).await;
+
+    let error = batch_create_orders(
+        &mut context,
+        &orders,
+        &funder,
+        &funder.pubkey(),
+        base_amount,
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6009 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
@@ -2,7 +2,6 @@ mod utils;
 
 use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
 use anchor_spl::token;
-use p2swap;
 use solana_program_test::*;
 use solana_sdk::{
     borsh::try_from_slice_unchecked, instruction::Instruction, signature::Keypair, signer::Signer,
@@ -27,15 +26,25 @@ async fn success_native() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -49,6 +58,17 @@ async fn success_native() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -78,11 +98,17 @@ async fn success_native() {
 
     context.warp_to_slot(3).unwrap();
 
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
     let accounts = p2swap::accounts::CancelOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
     }
@@ -123,8 +149,23 @@ async fn success_native() {
         .unwrap();
     assert!(funder_account.lamports > funder_balance_before);
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.status, p2swap::state::OrderStatus::Canceled);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Canceled
+    );
+
+    let order_book_account = context
+        .banks_client
+        .get_account(order_book)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let order_book =
+        try_from_slice_unchecked::<p2swap::state::OrderBook>(&order_book_account.data[8..])
+            .unwrap();
+    assert!(order_book.entries.is_empty());
 }
 
 #[tokio::test]
@@ -165,15 +206,26 @@ async fn success_spl_token() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) =
+        p2swap::utils::find_order_book_address(&funder_token_mint.pubkey(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder_token_account.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: funder_token_mint.pubkey(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -187,6 +239,17 @@ async fn success_spl_token() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -220,11 +283,17 @@ async fn success_spl_token() {
 
     context.warp_to_slot(3).unwrap();
 
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
     let accounts = p2swap::accounts::CancelOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         funder_token_account: funder_token_account.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
     }
@@ -272,8 +341,11 @@ async fn success_spl_token() {
 
     assert!(funder_token_account_balance_after > funder_token_account_balance_before);
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.status, p2swap::state::OrderStatus::Canceled);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Canceled
+    );
 }
 
 #[tokio::test]
@@ -294,15 +366,25 @@ async fn fail_invalid_order_status() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -316,6 +398,17 @@ async fn fail_invalid_order_status() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -336,11 +429,17 @@ async fn fail_invalid_order_status() {
 
     context.warp_to_slot(3).unwrap();
 
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
     let accounts = p2swap::accounts::CancelOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
     }
@@ -365,11 +464,17 @@ async fn fail_invalid_order_status() {
 
     context.warp_to_slot(6).unwrap();
 
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
     let accounts = p2swap::accounts::CancelOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
     }
@@ -423,15 +528,25 @@ async fn fail_funder_account_wallet_mismatch() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -445,6 +560,17 @@ async fn fail_funder_account_wallet_mismatch() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -465,11 +591,17 @@ async fn fail_funder_account_wallet_mismatch() {
 
     context.warp_to_slot(3).unwrap();
 
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
     let accounts = p2swap::accounts::CancelOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         funder_token_account: order.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
     }
@@ -504,3 +636,147 @@ async fn fail_funder_account_wallet_mismatch() {
     }
     assert_eq!(true, false);
 }
+
+#[tokio::test]
+async fn fail_cancel_is_locked() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let quote_amount = 11 * 10u64.pow(9);
+    let expire_date = 9999999999;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+
+    let clock = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap();
+    let cancel_locked_until = clock.unix_timestamp + 3600;
+
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let accounts = p2swap::accounts::CreateOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        recipient: recipient.pubkey(),
+        funder_token_account: funder.pubkey(),
+        escrow,
+        quote_token_account: funder.pubkey(),
+        escrow_mint: System::id(),
+        quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateOrder {
+        escrow_bump,
+        base_amount,
+        quote_amount,
+        start_date: None,
+        expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: Some(cancel_locked_until),
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&funder.pubkey()),
+        &[&funder, &order],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let order_account = context
+        .banks_client
+        .get_account(order.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let order_state =
+        p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(order_state.cancel_locked_until(), Some(cancel_locked_until));
+
+    context.warp_to_slot(3).unwrap();
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let accounts = p2swap::accounts::CancelOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        funder_token_account: funder.pubkey(),
+        escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CancelOrder { escrow_bump }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&funder.pubkey()),
+        &[&funder],
+        context.last_blockhash,
+    );
+
+    let error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6056 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
@@ -0,0 +1,135 @@
+mod utils;
+
+use anchor_lang::prelude::*;
+use solana_program_test::*;
+use solana_sdk::{instruction::AccountMeta, signature::Keypair, signer::Signer};
+
+/// Create collection offer (native `SOL`'s for any NFT of `collection`),
+/// then execute it with NFT of `nft_mint`.
+async fn create_and_execute(
+    verified: bool,
+) -> (
+    ProgramTestContext,
+    Keypair,
+    Keypair,
+    std::result::Result<(), BanksClientError>,
+) {
+    let nft_mint = Keypair::new();
+    let collection = Pubkey::new_unique();
+    let (metadata, _) = p2swap::utils::find_metadata_address(&nft_mint.pubkey());
+
+    let mut context = utils::setup_test_context_with_accounts(vec![(
+        metadata,
+        utils::metadata_account(&nft_mint.pubkey(), &collection, verified),
+    )])
+    .await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+    let mint_authority = Keypair::new();
+    let recipient_nft_token_account = Keypair::new();
+    let funder_nft_token_account = Keypair::new();
+
+    let base_amount = 50 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), 10u64.pow(8)).await;
+
+    utils::create_mint(&mut context, &nft_mint, &mint_authority.pubkey(), 0).await;
+    utils::create_token_account(
+        &mut context,
+        &recipient_nft_token_account,
+        &nft_mint.pubkey(),
+        &recipient.pubkey(),
+    )
+    .await;
+    utils::create_token_account(
+        &mut context,
+        &funder_nft_token_account,
+        &nft_mint.pubkey(),
+        &funder.pubkey(),
+    )
+    .await;
+    utils::mint_to(
+        &mut context,
+        &nft_mint.pubkey(),
+        &recipient_nft_token_account.pubkey(),
+        &mint_authority,
+        1,
+    )
+    .await;
+
+    utils::init_protocol(&mut context, &treasury.pubkey(), 100, 0).await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        1,
+        utils::OrderParams {
+            quote_mint: collection,
+            collection_offer: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let result = utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            quote_mint: collection,
+            recipient_token_account: Some(recipient_nft_token_account.pubkey()),
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            remaining_accounts: vec![
+                AccountMeta::new_readonly(metadata, false),
+                AccountMeta::new(funder_nft_token_account.pubkey(), false),
+            ],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    (context, order, funder_nft_token_account, result)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, order, funder_nft_token_account, result) = create_and_execute(true).await;
+
+    result.unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &funder_nft_token_account.pubkey()).await,
+        1
+    );
+
+    let order = utils::get_order(&mut context, &order.pubkey()).await;
+    assert!(order.is_collection_offer());
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
+}
+
+#[tokio::test]
+async fn fail_nft_is_not_collection_member() {
+    let (_, _, _, result) = create_and_execute(false).await;
+
+    let error_code = utils::get_error_code(result.unwrap_err());
+    if let Some(error_code) = error_code {
+        if error_code == 6055 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
@@ -0,0 +1,194 @@
+//! Compute units benchmark of `Order` instructions.
+//!
+//! Instructions are built through public `p2swap` API only, so benchmark can be
+//! executed on any revision to compare results:
+//! `cargo test-bpf --test compute_units -- --nocapture`.
+//!
+//! Native (non-BPF) runs don't meter compute units, so benchmark is BPF only.
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::Instruction, signature::Keypair, signer::Signer, sysvar, transaction::Transaction,
+};
+
+/// Compute units budgets of `Order` instructions, see README "Compute units".
+const CREATE_ORDER_UNITS: u64 = 100_000;
+const EXECUTE_ORDER_UNITS: u64 = 120_000;
+const CANCEL_ORDER_UNITS: u64 = 60_000;
+
+/// Simulate transaction to record consumed compute units, then process it.
+async fn measure_instruction(
+    context: &mut ProgramTestContext,
+    name: &str,
+    instruction: Instruction,
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> u64 {
+    let last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &signers.to_vec(),
+        last_blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(tx.clone())
+        .await
+        .unwrap();
+
+    assert!(matches!(simulation.result, Some(Ok(()))));
+
+    let units_consumed = simulation.simulation_details.unwrap().units_consumed;
+    println!("{}: {} compute units", name, units_consumed);
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    units_consumed
+}
+
+async fn create_order(
+    context: &mut ProgramTestContext,
+    order: &Keypair,
+    funder: &Keypair,
+    recipient: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) -> u64 {
+    let instruction = utils::create_order_instruction(
+        &order.pubkey(),
+        &funder.pubkey(),
+        recipient,
+        base_amount,
+        quote_amount,
+        utils::OrderParams::default(),
+    );
+
+    measure_instruction(
+        context,
+        "create_order",
+        instruction,
+        funder,
+        &[funder, order],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn benchmark_native() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let canceled_order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let quote_amount = 11 * 10u64.pow(9);
+
+    utils::airdrop(
+        &mut context,
+        &funder.pubkey(),
+        base_amount * 2 + 10u64.pow(8),
+    )
+    .await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    // CreateOrder
+    let create_order_units = create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        quote_amount,
+    )
+    .await;
+
+    // ExecuteOrder
+    let instruction = utils::execute_order_instruction(
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient.pubkey(),
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            ..Default::default()
+        },
+    );
+
+    let execute_order_units = measure_instruction(
+        &mut context,
+        "execute_order",
+        instruction,
+        &recipient,
+        &[&recipient],
+    )
+    .await;
+
+    // CancelOrder
+    create_order(
+        &mut context,
+        &canceled_order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        quote_amount,
+    )
+    .await;
+
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &canceled_order.pubkey());
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let accounts = p2swap::accounts::CancelOrder {
+        order: canceled_order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        funder_token_account: funder.pubkey(),
+        escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CancelOrder { escrow_bump }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let cancel_order_units = measure_instruction(
+        &mut context,
+        "cancel_order",
+        instruction,
+        &funder,
+        &[&funder],
+    )
+    .await;
+
+    assert!(create_order_units <= CREATE_ORDER_UNITS);
+    assert!(execute_order_units <= EXECUTE_ORDER_UNITS);
+    assert!(cancel_order_units <= CANCEL_ORDER_UNITS);
+}
@@ -0,0 +1,453 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, System};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    sysvar,
+    transaction::Transaction,
+};
+
+/// Small program, that holds native `SOL`'s in PDA's and trades them through `p2swap` CPI.
+mod example {
+    use anchor_lang::{
+        prelude::*,
+        solana_program::{account_info::next_account_info, entrypoint::ProgramResult},
+    };
+    use p2swap::interface;
+
+    pub const VAULT_SEED: &[u8] = b"vault";
+    pub const RECIPIENT_SEED: &[u8] = b"recipient";
+    pub const ORDER_SEED: &[u8] = b"order";
+
+    pub fn id() -> Pubkey {
+        Pubkey::new_from_array([7; 32])
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    #[allow(clippy::enum_variant_names)]
+    pub enum ExampleInstruction {
+        /// Create `Order`, funded by vault PDA, for recipient PDA.
+        ///
+        /// Accounts: [order, vault, recipient, escrow, order_book, funder_profile, protocol, rent, clock, token,
+        /// system, p2swap].
+        CreateOrder {
+            order_nonce: u8,
+            base_amount: u64,
+            quote_amount: u64,
+            expire_date: i64,
+        },
+
+        /// Execute `Order` by recipient PDA.
+        ///
+        /// Accounts: [order, vault, recipient, escrow, order_book, funder_profile, recipient_profile,
+        /// stats, pair_stats, protocol, treasury, clock, token, system, p2swap].
+        ExecuteOrder,
+
+        /// Cancel `Order` by vault PDA.
+        ///
+        /// Accounts: [order, vault, escrow, order_book, funder_profile, clock, token, system, p2swap].
+        CancelOrder,
+    }
+
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> ProgramResult {
+        let (_, vault_bump) = Pubkey::find_program_address(&[VAULT_SEED], program_id);
+        let (_, recipient_bump) = Pubkey::find_program_address(&[RECIPIENT_SEED], program_id);
+
+        let accounts_iter = &mut accounts.iter();
+
+        let instruction = ExampleInstruction::try_from_slice(data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        match instruction {
+            ExampleInstruction::CreateOrder {
+                order_nonce,
+                base_amount,
+                quote_amount,
+                expire_date,
+            } => {
+                let order = next_account_info(accounts_iter)?;
+                let vault = next_account_info(accounts_iter)?;
+                let recipient = next_account_info(accounts_iter)?;
+                let escrow = next_account_info(accounts_iter)?;
+                let order_book = next_account_info(accounts_iter)?;
+                let funder_profile = next_account_info(accounts_iter)?;
+                let protocol = next_account_info(accounts_iter)?;
+                let rent_sysvar = next_account_info(accounts_iter)?;
+                let clock_sysvar = next_account_info(accounts_iter)?;
+                let token_program = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+                let p2swap_program = next_account_info(accounts_iter)?;
+
+                let (_, order_bump) =
+                    Pubkey::find_program_address(&[ORDER_SEED, &[order_nonce]], program_id);
+
+                interface::create_order(
+                    p2swap_program,
+                    interface::CreateOrder {
+                        order: order.clone(),
+                        funder: vault.clone(),
+                        rent_payer: vault.clone(),
+                        recipient: recipient.clone(),
+                        funder_token_account: vault.clone(),
+                        escrow: escrow.clone(),
+                        quote_token_account: vault.clone(),
+                        escrow_mint: system_program.clone(),
+                        quote_mint: system_program.clone(),
+                        order_book: order_book.clone(),
+                        funder_profile: funder_profile.clone(),
+                        protocol: protocol.clone(),
+                        rent_sysvar: rent_sysvar.clone(),
+                        clock_sysvar: clock_sysvar.clone(),
+                        token_program: token_program.clone(),
+                        system_program: system_program.clone(),
+                        referrer: None,
+                    },
+                    interface::CreateOrderArgs {
+                        base_amount,
+                        quote_amount,
+                        start_date: None,
+                        expire_date,
+                        price_curve: p2swap::state::PriceCurve::Fixed,
+                        floor_quote_amount: quote_amount,
+                        referral_share_bps: 0,
+                        collection_offer: false,
+                        cancel_locked_until: None,
+                        hashlock: None,
+                        arbitration: None,
+                        memo: None,
+                        reference: None,
+                        recipient_commitment: None,
+                        gate: None,
+                    },
+                    &[
+                        &[VAULT_SEED, &[vault_bump]],
+                        &[ORDER_SEED, &[order_nonce], &[order_bump]],
+                    ],
+                )?;
+            }
+            ExampleInstruction::ExecuteOrder => {
+                let order = next_account_info(accounts_iter)?;
+                let vault = next_account_info(accounts_iter)?;
+                let recipient = next_account_info(accounts_iter)?;
+                let escrow = next_account_info(accounts_iter)?;
+                let order_book = next_account_info(accounts_iter)?;
+                let funder_profile = next_account_info(accounts_iter)?;
+                let recipient_profile = next_account_info(accounts_iter)?;
+                let stats = next_account_info(accounts_iter)?;
+                let pair_stats = next_account_info(accounts_iter)?;
+                let protocol = next_account_info(accounts_iter)?;
+                let treasury = next_account_info(accounts_iter)?;
+                let clock_sysvar = next_account_info(accounts_iter)?;
+                let token_program = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+                let p2swap_program = next_account_info(accounts_iter)?;
+
+                interface::execute_order(
+                    p2swap_program,
+                    interface::ExecuteOrder {
+                        order: order.clone(),
+                        funder: vault.clone(),
+                        rent_payer: vault.clone(),
+                        recipient: recipient.clone(),
+                        recipient_token_account: recipient.clone(),
+                        recipient_receive_token_account: recipient.clone(),
+                        escrow: escrow.clone(),
+                        quote_token_account: vault.clone(),
+                        order_book: order_book.clone(),
+                        funder_profile: funder_profile.clone(),
+                        recipient_profile: recipient_profile.clone(),
+                        stats: stats.clone(),
+                        pair_stats: pair_stats.clone(),
+                        protocol: protocol.clone(),
+                        treasury_base_token_account: treasury.clone(),
+                        treasury_quote_token_account: treasury.clone(),
+                        clock_sysvar: clock_sysvar.clone(),
+                        token_program: token_program.clone(),
+                        system_program: system_program.clone(),
+                        gate: None,
+                        collection_nft: None,
+                        maker_referrer: None,
+                        taker_referrer: None,
+                        discount_token_account: None,
+                    },
+                    0,
+                    None,
+                    None,
+                    &[&[RECIPIENT_SEED, &[recipient_bump]]],
+                )?;
+            }
+            ExampleInstruction::CancelOrder => {
+                let order = next_account_info(accounts_iter)?;
+                let vault = next_account_info(accounts_iter)?;
+                let escrow = next_account_info(accounts_iter)?;
+                let order_book = next_account_info(accounts_iter)?;
+                let funder_profile = next_account_info(accounts_iter)?;
+                let clock_sysvar = next_account_info(accounts_iter)?;
+                let token_program = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+                let p2swap_program = next_account_info(accounts_iter)?;
+
+                interface::cancel_order(
+                    p2swap_program,
+                    interface::CancelOrder {
+                        order: order.clone(),
+                        funder: vault.clone(),
+                        rent_payer: vault.clone(),
+                        funder_token_account: vault.clone(),
+                        escrow: escrow.clone(),
+                        order_book: order_book.clone(),
+                        funder_profile: funder_profile.clone(),
+                        clock_sysvar: clock_sysvar.clone(),
+                        token_program: token_program.clone(),
+                        system_program: system_program.clone(),
+                    },
+                    &[&[VAULT_SEED, &[vault_bump]]],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct ExampleAccounts {
+    order: Pubkey,
+    vault: Pubkey,
+    recipient: Pubkey,
+    escrow: Pubkey,
+    order_book: Pubkey,
+    funder_profile: Pubkey,
+    recipient_profile: Pubkey,
+    stats: Pubkey,
+    pair_stats: Pubkey,
+    protocol: Pubkey,
+}
+
+impl ExampleAccounts {
+    fn new(order_nonce: u8) -> Self {
+        let (order, _) =
+            Pubkey::find_program_address(&[example::ORDER_SEED, &[order_nonce]], &example::id());
+        let (vault, _) = Pubkey::find_program_address(&[example::VAULT_SEED], &example::id());
+        let (recipient, _) =
+            Pubkey::find_program_address(&[example::RECIPIENT_SEED], &example::id());
+        let (escrow, _) = p2swap::utils::find_order_escrow_address(&vault, &order);
+        let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+        let (funder_profile, _) = p2swap::utils::find_profile_address(&vault);
+        let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient);
+        let (stats, _) = p2swap::utils::find_stats_address();
+        let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+        let (protocol, _) = p2swap::utils::find_protocol_address();
+
+        ExampleAccounts {
+            order,
+            vault,
+            recipient,
+            escrow,
+            order_book,
+            funder_profile,
+            recipient_profile,
+            stats,
+            pair_stats,
+            protocol,
+        }
+    }
+}
+
+async fn setup_context() -> ProgramTestContext {
+    let mut program_test = utils::program_test();
+    program_test.add_program(
+        "p2swap_example",
+        example::id(),
+        processor!(example::process_instruction),
+    );
+
+    program_test.start_with_context().await
+}
+
+async fn process_example_instruction(
+    context: &mut ProgramTestContext,
+    instruction: example::ExampleInstruction,
+    accounts: Vec<AccountMeta>,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: example::id(),
+        data: instruction.try_to_vec().unwrap(),
+        accounts,
+    };
+
+    let last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn create_order(
+    context: &mut ProgramTestContext,
+    accounts: &ExampleAccounts,
+    order_nonce: u8,
+    base_amount: u64,
+    quote_amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    process_example_instruction(
+        context,
+        example::ExampleInstruction::CreateOrder {
+            order_nonce,
+            base_amount,
+            quote_amount,
+            expire_date: 9999999999,
+        },
+        vec![
+            AccountMeta::new(accounts.order, false),
+            AccountMeta::new(accounts.vault, false),
+            AccountMeta::new_readonly(accounts.recipient, false),
+            AccountMeta::new(accounts.escrow, false),
+            AccountMeta::new(accounts.order_book, false),
+            AccountMeta::new(accounts.funder_profile, false),
+            AccountMeta::new_readonly(accounts.protocol, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(System::id(), false),
+            AccountMeta::new_readonly(p2swap::id(), false),
+        ],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn success_create_execute() {
+    let mut context = setup_context().await;
+
+    let accounts = ExampleAccounts::new(0);
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &accounts.vault, 10 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &accounts.recipient, 10 * 10u64.pow(9)).await;
+
+    let treasury = Keypair::new();
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    create_order(&mut context, &accounts, 0, base_amount, quote_amount)
+        .await
+        .unwrap();
+
+    let order = utils::get_order(&mut context, &accounts.order).await;
+    assert_eq!(order.status().unwrap(), p2swap::state::OrderStatus::Created);
+    assert_eq!(order.funder, accounts.vault);
+    assert_eq!(order.recipient, accounts.recipient);
+    assert_eq!(order.escrow, accounts.escrow);
+
+    let vault_balance_before = context
+        .banks_client
+        .get_account(accounts.vault)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    process_example_instruction(
+        &mut context,
+        example::ExampleInstruction::ExecuteOrder,
+        vec![
+            AccountMeta::new(accounts.order, false),
+            AccountMeta::new(accounts.vault, false),
+            AccountMeta::new(accounts.recipient, false),
+            AccountMeta::new(accounts.escrow, false),
+            AccountMeta::new(accounts.order_book, false),
+            AccountMeta::new(accounts.funder_profile, false),
+            AccountMeta::new(accounts.recipient_profile, false),
+            AccountMeta::new(accounts.stats, false),
+            AccountMeta::new(accounts.pair_stats, false),
+            AccountMeta::new_readonly(protocol, false),
+            AccountMeta::new(treasury.pubkey(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(System::id(), false),
+            AccountMeta::new_readonly(p2swap::id(), false),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let order = utils::get_order(&mut context, &accounts.order).await;
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
+
+    let vault_balance_after = context
+        .banks_client
+        .get_account(accounts.vault)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(vault_balance_after > vault_balance_before + quote_amount);
+
+    let escrow_account = context
+        .banks_client
+        .get_account(accounts.escrow)
+        .await
+        .unwrap();
+    assert!(escrow_account.is_none());
+}
+
+#[tokio::test]
+async fn success_create_cancel() {
+    let mut context = setup_context().await;
+
+    let accounts = ExampleAccounts::new(1);
+
+    utils::airdrop(&mut context, &accounts.vault, 10 * 10u64.pow(9)).await;
+
+    create_order(&mut context, &accounts, 1, 10u64.pow(9), 10u64.pow(9))
+        .await
+        .unwrap();
+
+    process_example_instruction(
+        &mut context,
+        example::ExampleInstruction::CancelOrder,
+        vec![
+            AccountMeta::new(accounts.order, false),
+            AccountMeta::new(accounts.vault, false),
+            AccountMeta::new(accounts.escrow, false),
+            AccountMeta::new(accounts.order_book, false),
+            AccountMeta::new(accounts.funder_profile, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(System::id(), false),
+            AccountMeta::new_readonly(p2swap::id(), false),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let order = utils::get_order(&mut context, &accounts.order).await;
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Canceled
+    );
+
+    let escrow_account = context
+        .banks_client
+        .get_account(accounts.escrow)
+        .await
+        .unwrap();
+    assert!(escrow_account.is_none());
+}
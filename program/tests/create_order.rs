@@ -2,7 +2,6 @@ mod utils;
 
 use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
 use anchor_spl::token;
-use p2swap;
 use solana_program_test::*;
 use solana_sdk::{
     borsh::try_from_slice_unchecked, instruction::Instruction, signature::Keypair, signer::Signer,
@@ -27,15 +26,25 @@ async fn success_native() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -49,6 +58,17 @@ async fn success_native() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -85,18 +105,24 @@ async fn success_native() {
         .unwrap()
         .unwrap();
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.base_amount, base_amount);
-    assert_eq!(order.quote_amount, quote_amount);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!({ order.base_amount }, base_amount);
+    assert_eq!({ order.quote_amount }, quote_amount);
     assert_eq!(order.funder, funder.pubkey());
     assert_eq!(order.recipient, recipient.pubkey());
     assert_eq!(order.escrow, escrow);
     assert_eq!(order.quote_token_account, funder.pubkey());
-    assert!(order.start_date.is_none());
+    assert!(order.start_date().is_none());
     assert!(order.is_base_native());
     assert!(order.is_quote_native());
-    assert_eq!(order.expire_date, expire_date);
-    assert_eq!(order.status, p2swap::state::OrderStatus::Created);
+    assert_eq!({ order.expire_date }, expire_date);
+    assert_eq!(
+        order.price_curve().unwrap(),
+        p2swap::state::PriceCurve::Fixed
+    );
+    assert_eq!({ order.floor_quote_amount }, quote_amount);
+    assert_eq!(order.status().unwrap(), p2swap::state::OrderStatus::Created);
+    assert_eq!(order.order_book, order_book);
 }
 
 #[tokio::test]
@@ -137,15 +163,26 @@ async fn success_spl_token() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) =
+        p2swap::utils::find_order_book_address(&funder_token_mint.pubkey(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder_token_account.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: funder_token_mint.pubkey(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -159,6 +196,17 @@ async fn success_spl_token() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -195,18 +243,18 @@ async fn success_spl_token() {
         .unwrap()
         .unwrap();
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.base_amount, base_amount);
-    assert_eq!(order.quote_amount, quote_amount);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!({ order.base_amount }, base_amount);
+    assert_eq!({ order.quote_amount }, quote_amount);
     assert_eq!(order.funder, funder.pubkey());
     assert_eq!(order.recipient, recipient.pubkey());
     assert_eq!(order.escrow, escrow);
     assert_eq!(order.quote_token_account, funder.pubkey());
-    assert!(order.start_date.is_none());
+    assert!(order.start_date().is_none());
     assert!(!order.is_base_native());
     assert!(order.is_quote_native());
-    assert_eq!(order.expire_date, expire_date);
-    assert_eq!(order.status, p2swap::state::OrderStatus::Created);
+    assert_eq!({ order.expire_date }, expire_date);
+    assert_eq!(order.status().unwrap(), p2swap::state::OrderStatus::Created);
 }
 
 #[tokio::test]
@@ -227,15 +275,25 @@ async fn fail_funder_account_wallet_mismatch() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: recipient.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -249,6 +307,17 @@ async fn fail_funder_account_wallet_mismatch() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -300,15 +369,25 @@ async fn fail_start_date_in_the_past() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -322,6 +401,17 @@ async fn fail_start_date_in_the_past() {
         quote_amount,
         start_date: Some(start_date),
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -372,15 +462,25 @@ async fn fail_quote_account_wallet_mismatch() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: recipient.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -394,6 +494,17 @@ async fn fail_quote_account_wallet_mismatch() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -444,15 +555,25 @@ async fn fail_expire_date_in_the_past() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -466,6 +587,17 @@ async fn fail_expire_date_in_the_past() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -516,15 +648,25 @@ async fn fail_recipient_match_funder() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: funder.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -538,6 +680,17 @@ async fn fail_recipient_match_funder() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -569,3 +722,292 @@ async fn fail_recipient_match_funder() {
     }
     assert_eq!(true, false);
 }
+
+#[tokio::test]
+async fn fail_invalid_floor_quote_amount() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let quote_amount = 11 * 10u64.pow(9);
+    let expire_date = 9999999999;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), quote_amount * 2).await;
+
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let accounts = p2swap::accounts::CreateOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        recipient: recipient.pubkey(),
+        funder_token_account: funder.pubkey(),
+        escrow,
+        quote_token_account: funder.pubkey(),
+        escrow_mint: System::id(),
+        quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateOrder {
+        escrow_bump,
+        base_amount,
+        quote_amount,
+        start_date: None,
+        expire_date,
+        price_curve: p2swap::state::PriceCurve::Linear,
+        floor_quote_amount: quote_amount + 1,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&funder.pubkey()),
+        &[&funder, &order],
+        context.last_blockhash,
+    );
+
+    let error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6010 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn success_order_book_sorted() {
+    let mut context = utils::setup_test_context().await;
+
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let expire_date = 9999999999;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 10).await;
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let mut orders = vec![];
+    for quote_amount in [12, 11, 13].map(|amount| amount * 10u64.pow(8)) {
+        let order = Keypair::new();
+
+        let (escrow, escrow_bump) =
+            p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+
+        let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+        let (protocol, _) = p2swap::utils::find_protocol_address();
+
+        let accounts = p2swap::accounts::CreateOrder {
+            order: order.pubkey(),
+            funder: funder.pubkey(),
+            rent_payer: funder.pubkey(),
+            recipient: recipient.pubkey(),
+            funder_token_account: funder.pubkey(),
+            escrow,
+            quote_token_account: funder.pubkey(),
+            escrow_mint: System::id(),
+            quote_mint: System::id(),
+            order_book,
+            funder_profile,
+            protocol,
+            rent_sysvar: sysvar::rent::id(),
+            clock_sysvar: sysvar::clock::id(),
+            token_program: spl_token::id(),
+            system_program: System::id(),
+        }
+        .to_account_metas(None);
+
+        let data = p2swap::instruction::CreateOrder {
+            escrow_bump,
+            base_amount,
+            quote_amount,
+            start_date: None,
+            expire_date,
+            price_curve: p2swap::state::PriceCurve::Fixed,
+            floor_quote_amount: quote_amount,
+            referral_share_bps: 0,
+            collection_offer: false,
+            cancel_locked_until: None,
+            hashlock: None,
+            arbitration: None,
+            memo: None,
+            reference: None,
+            recipient_commitment: None,
+            gate: None,
+        }
+        .data();
+
+        let instruction = Instruction {
+            program_id: p2swap::id(),
+            data,
+            accounts,
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&funder.pubkey()),
+            &[&funder, &order],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        orders.push(order.pubkey());
+    }
+
+    let order_book_account = context
+        .banks_client
+        .get_account(order_book)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let order_book =
+        try_from_slice_unchecked::<p2swap::state::OrderBook>(&order_book_account.data[8..])
+            .unwrap();
+    assert_eq!(order_book.base_mint, System::id());
+    assert_eq!(order_book.quote_mint, System::id());
+    assert_eq!(
+        order_book
+            .entries
+            .iter()
+            .map(|entry| entry.order)
+            .collect::<Vec<_>>(),
+        vec![orders[1], orders[0], orders[2]]
+    );
+}
+
+#[tokio::test]
+async fn success_memo_and_reference() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let quote_amount = 11 * 10u64.pow(9);
+    let expire_date = 9999999999;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), quote_amount * 2).await;
+
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let accounts = p2swap::accounts::CreateOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        recipient: recipient.pubkey(),
+        funder_token_account: funder.pubkey(),
+        escrow,
+        quote_token_account: funder.pubkey(),
+        escrow_mint: System::id(),
+        quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateOrder {
+        escrow_bump,
+        base_amount,
+        quote_amount,
+        start_date: None,
+        expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: Some(String::from("deal #42")),
+        reference: Some([42; 32]),
+        recipient_commitment: None,
+        gate: None,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&funder.pubkey()),
+        &[&funder, &order],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let order_account = context
+        .banks_client
+        .get_account(order.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(order.memo(), "deal #42");
+    assert_eq!(order.reference(), Some([42; 32]));
+}
@@ -0,0 +1,711 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    borsh::try_from_slice_unchecked, instruction::Instruction, signature::Keypair, signer::Signer,
+    sysvar,
+};
+
+#[allow(clippy::too_many_arguments)]
+async fn create_english_auction(
+    context: &mut ProgramTestContext,
+    auction: &Keypair,
+    funder: &Keypair,
+    base_mint: &Pubkey,
+    funder_token_account: &Pubkey,
+    quote_mint: &Pubkey,
+    quote_token_account: &Pubkey,
+    base_amount: u64,
+    min_bid_amount: u64,
+    expire_date: i64,
+    extension_window: i64,
+) -> std::result::Result<(), BanksClientError> {
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &auction.pubkey());
+    let (bid_escrow, bid_escrow_bump) =
+        p2swap::utils::find_auction_bid_escrow_address(&auction.pubkey());
+
+    let accounts = p2swap::accounts::CreateEnglishAuction {
+        auction: auction.pubkey(),
+        funder: funder.pubkey(),
+        funder_token_account: *funder_token_account,
+        escrow,
+        bid_escrow,
+        quote_token_account: *quote_token_account,
+        escrow_mint: *base_mint,
+        quote_mint: *quote_mint,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateEnglishAuction {
+        escrow_bump,
+        bid_escrow_bump,
+        base_amount,
+        min_bid_amount,
+        min_bid_increment: 10u64.pow(8),
+        start_date: None,
+        expire_date,
+        extension_window,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, funder, &[funder, auction]).await
+}
+
+async fn place_bid(
+    context: &mut ProgramTestContext,
+    auction: &Pubkey,
+    bidder: &Keypair,
+    bidder_token_account: &Pubkey,
+    receive_token_account: &Pubkey,
+    amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let (bid_escrow, _) = p2swap::utils::find_auction_bid_escrow_address(auction);
+    let (bid, _) = p2swap::utils::find_english_bid_address(auction, &bidder.pubkey());
+
+    let accounts = p2swap::accounts::PlaceBid {
+        auction: *auction,
+        bidder: bidder.pubkey(),
+        bidder_token_account: *bidder_token_account,
+        receive_token_account: *receive_token_account,
+        bid_escrow,
+        bid,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::PlaceBid { amount }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, bidder, &[bidder]).await
+}
+
+async fn refund_english_bid(
+    context: &mut ProgramTestContext,
+    auction: &Pubkey,
+    funder: &Pubkey,
+    bidder: &Pubkey,
+    refund_token_account: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let (bid_escrow, bid_escrow_bump) = p2swap::utils::find_auction_bid_escrow_address(auction);
+    let (bid, _) = p2swap::utils::find_english_bid_address(auction, bidder);
+
+    let accounts = p2swap::accounts::RefundEnglishBid {
+        auction: *auction,
+        funder: *funder,
+        bid,
+        bidder: *bidder,
+        refund_token_account: *refund_token_account,
+        bid_escrow,
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::RefundEnglishBid { bid_escrow_bump }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[]).await
+}
+
+async fn settle_english_auction(
+    context: &mut ProgramTestContext,
+    auction: &Pubkey,
+    funder: &Pubkey,
+    quote_token_account: &Pubkey,
+    receive_token_account: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(funder, auction);
+    let (bid_escrow, bid_escrow_bump) = p2swap::utils::find_auction_bid_escrow_address(auction);
+
+    let accounts = p2swap::accounts::SettleEnglishAuction {
+        auction: *auction,
+        funder: *funder,
+        escrow,
+        bid_escrow,
+        quote_token_account: *quote_token_account,
+        receive_token_account: *receive_token_account,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::SettleEnglishAuction {
+        escrow_bump,
+        bid_escrow_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    utils::process_instruction(context, instruction, &payer, &[&payer]).await
+}
+
+#[tokio::test]
+async fn success_native() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder_a = Keypair::new();
+    let bidder_b = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+    let bidder_balance = 10 * 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let expire_date = now + 300;
+    let extension_window = 600;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &bidder_a.pubkey(), bidder_balance).await;
+    utils::airdrop(&mut context, &bidder_b.pubkey(), bidder_balance).await;
+
+    create_english_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &System::id(),
+        &funder.pubkey(),
+        &System::id(),
+        &funder.pubkey(),
+        base_amount,
+        min_bid_amount,
+        expire_date,
+        extension_window,
+    )
+    .await
+    .unwrap();
+
+    place_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_a,
+        &bidder_a.pubkey(),
+        &bidder_a.pubkey(),
+        2 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    place_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_b,
+        &bidder_b.pubkey(),
+        &bidder_b.pubkey(),
+        3 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    // Outbid bid stays in `bid_escrow` until refund is claimed
+    let bidder_a_balance = utils::get_lamports(&mut context, &bidder_a.pubkey()).await;
+    assert!(bidder_a_balance < bidder_balance - 2 * 10u64.pow(9));
+
+    refund_english_bid(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &bidder_a.pubkey(),
+        &bidder_a.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let bidder_a_balance = utils::get_lamports(&mut context, &bidder_a.pubkey()).await;
+    assert!(bidder_a_balance > bidder_balance - 10u64.pow(6));
+
+    // The highest bid is locked until settlement
+    let error = refund_english_bid(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &bidder_b.pubkey(),
+        &bidder_b.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6022));
+
+    let auction_account = context
+        .banks_client
+        .get_account(auction.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let auction_state =
+        try_from_slice_unchecked::<p2swap::state::EnglishAuction>(&auction_account.data[8..])
+            .unwrap();
+    assert_eq!(auction_state.highest_bid_amount, 3 * 10u64.pow(9));
+    assert_eq!(auction_state.highest_bidder, bidder_b.pubkey());
+    assert!(auction_state.expire_date > expire_date);
+
+    let error = settle_english_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &bidder_b.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6027));
+
+    utils::wait_until(&mut context, auction_state.expire_date).await;
+
+    let funder_balance_before = context
+        .banks_client
+        .get_account(funder.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    settle_english_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &bidder_b.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let (escrow, _) = p2swap::utils::find_order_escrow_address(&funder.pubkey(), &auction.pubkey());
+    let escrow_account = context.banks_client.get_account(escrow).await.unwrap();
+    assert!(escrow_account.is_none());
+
+    let (bid_escrow, _) = p2swap::utils::find_auction_bid_escrow_address(&auction.pubkey());
+    let bid_escrow_account = context.banks_client.get_account(bid_escrow).await.unwrap();
+    assert!(bid_escrow_account.is_none());
+
+    let funder_balance_after = context
+        .banks_client
+        .get_account(funder.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(funder_balance_after > funder_balance_before + 3 * 10u64.pow(9));
+
+    let bidder_b_balance = context
+        .banks_client
+        .get_account(bidder_b.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(bidder_b_balance > bidder_balance + 6 * 10u64.pow(9));
+
+    let auction_account = context
+        .banks_client
+        .get_account(auction.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let auction_state =
+        try_from_slice_unchecked::<p2swap::state::EnglishAuction>(&auction_account.data[8..])
+            .unwrap();
+    assert_eq!(auction_state.status, p2swap::state::AuctionStatus::Settled);
+
+    // Winner has no excess to refund, but gets `EnglishBid` rent back
+    refund_english_bid(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &bidder_b.pubkey(),
+        &bidder_b.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let (bid, _) = p2swap::utils::find_english_bid_address(&auction.pubkey(), &bidder_b.pubkey());
+    let bid_account = context.banks_client.get_account(bid).await.unwrap();
+    assert!(bid_account.is_none());
+}
+
+#[tokio::test]
+async fn fail_bid_too_low() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder_a = Keypair::new();
+    let bidder_b = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+    let bidder_balance = 10 * 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &bidder_a.pubkey(), bidder_balance).await;
+    utils::airdrop(&mut context, &bidder_b.pubkey(), bidder_balance).await;
+
+    create_english_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &System::id(),
+        &funder.pubkey(),
+        &System::id(),
+        &funder.pubkey(),
+        base_amount,
+        min_bid_amount,
+        now + 1000,
+        0,
+    )
+    .await
+    .unwrap();
+
+    place_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_a,
+        &bidder_a.pubkey(),
+        &bidder_a.pubkey(),
+        2 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    // Lower than the highest bid with `min_bid_increment`
+    let error = place_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_b,
+        &bidder_b.pubkey(),
+        &bidder_b.pubkey(),
+        2 * 10u64.pow(9) + 1,
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6025 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn success_refund_after_bidder_account_closed() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder_a = Keypair::new();
+    let bidder_b = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &bidder_a.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &bidder_b.pubkey(), 10u64.pow(9)).await;
+
+    let mint = Keypair::new();
+    let funder_token_account = Keypair::new();
+    let bidder_a_token_account = Keypair::new();
+    let bidder_a_new_token_account = Keypair::new();
+    let bidder_b_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &funder.pubkey(), 9).await;
+    for (account, owner) in [
+        (&funder_token_account, &funder),
+        (&bidder_a_token_account, &bidder_a),
+        (&bidder_a_new_token_account, &bidder_a),
+        (&bidder_b_token_account, &bidder_b),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), &owner.pubkey()).await;
+    }
+    for (account, amount) in [
+        (&bidder_a_token_account, 2 * 10u64.pow(9)),
+        (&bidder_b_token_account, 3 * 10u64.pow(9)),
+    ] {
+        utils::mint_to(
+            &mut context,
+            &mint.pubkey(),
+            &account.pubkey(),
+            &funder,
+            amount,
+        )
+        .await;
+    }
+
+    create_english_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &System::id(),
+        &funder.pubkey(),
+        &mint.pubkey(),
+        &funder_token_account.pubkey(),
+        base_amount,
+        min_bid_amount,
+        now + 1000,
+        0,
+    )
+    .await
+    .unwrap();
+
+    place_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_a,
+        &bidder_a_token_account.pubkey(),
+        &bidder_a.pubkey(),
+        2 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    // Outbid bidder closes its (now empty) bid token account
+    let instruction = spl_token::instruction::close_account(
+        &spl_token::id(),
+        &bidder_a_token_account.pubkey(),
+        &bidder_a.pubkey(),
+        &bidder_a.pubkey(),
+        &[],
+    )
+    .unwrap();
+    utils::process_instruction(&mut context, instruction, &bidder_a, &[&bidder_a])
+        .await
+        .unwrap();
+
+    // Closed account of previous bidder doesn't block new bids
+    place_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder_b,
+        &bidder_b_token_account.pubkey(),
+        &bidder_b.pubkey(),
+        3 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    // Refund can't be sent to account of other wallet
+    let error = refund_english_bid(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &bidder_a.pubkey(),
+        &bidder_b_token_account.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6024));
+
+    refund_english_bid(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &bidder_a.pubkey(),
+        &bidder_a_new_token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &bidder_a_new_token_account.pubkey()).await,
+        2 * 10u64.pow(9)
+    );
+
+    let (bid, _) = p2swap::utils::find_english_bid_address(&auction.pubkey(), &bidder_a.pubkey());
+    let bid_account = context.banks_client.get_account(bid).await.unwrap();
+    assert!(bid_account.is_none());
+}
+
+#[tokio::test]
+async fn success_settle_after_winner_account_closed() {
+    let mut context = utils::setup_test_context().await;
+
+    let auction = Keypair::new();
+    let funder = Keypair::new();
+    let bidder = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let min_bid_amount = 10u64.pow(9);
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+
+    utils::airdrop(&mut context, &funder.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &bidder.pubkey(), 10 * 10u64.pow(9)).await;
+
+    let mint = Keypair::new();
+    let funder_token_account = Keypair::new();
+    let bidder_token_account = Keypair::new();
+    let bidder_new_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &funder.pubkey(), 9).await;
+    for (account, owner) in [
+        (&funder_token_account, &funder),
+        (&bidder_token_account, &bidder),
+        (&bidder_new_token_account, &bidder),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), &owner.pubkey()).await;
+    }
+    utils::mint_to(
+        &mut context,
+        &mint.pubkey(),
+        &funder_token_account.pubkey(),
+        &funder,
+        base_amount,
+    )
+    .await;
+
+    create_english_auction(
+        &mut context,
+        &auction,
+        &funder,
+        &mint.pubkey(),
+        &funder_token_account.pubkey(),
+        &System::id(),
+        &funder.pubkey(),
+        base_amount,
+        min_bid_amount,
+        now + 1000,
+        0,
+    )
+    .await
+    .unwrap();
+
+    place_bid(
+        &mut context,
+        &auction.pubkey(),
+        &bidder,
+        &bidder.pubkey(),
+        &bidder_token_account.pubkey(),
+        2 * 10u64.pow(9),
+    )
+    .await
+    .unwrap();
+
+    // Winner closes its (empty) receive token account before settlement
+    let instruction = spl_token::instruction::close_account(
+        &spl_token::id(),
+        &bidder_token_account.pubkey(),
+        &bidder.pubkey(),
+        &bidder.pubkey(),
+        &[],
+    )
+    .unwrap();
+    utils::process_instruction(&mut context, instruction, &bidder, &[&bidder])
+        .await
+        .unwrap();
+
+    utils::wait_until(&mut context, now + 1000).await;
+
+    // Escrow tokens can't be sent to account of other wallet
+    let error = settle_english_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &funder_token_account.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6003));
+
+    // Winning bid can't be sent to wallet of other user
+    let error = settle_english_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &bidder.pubkey(),
+        &bidder_new_token_account.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    assert_eq!(error_code, Some(6000));
+
+    settle_english_auction(
+        &mut context,
+        &auction.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &bidder_new_token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &bidder_new_token_account.pubkey()).await,
+        base_amount
+    );
+
+    let (escrow, _) = p2swap::utils::find_order_escrow_address(&funder.pubkey(), &auction.pubkey());
+    let escrow_account = context.banks_client.get_account(escrow).await.unwrap();
+    assert!(escrow_account.is_none());
+}
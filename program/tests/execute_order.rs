@@ -2,11 +2,9 @@ mod utils;
 
 use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
 use anchor_spl::token;
-use p2swap;
 use solana_program_test::*;
 use solana_sdk::{
-    borsh::try_from_slice_unchecked, instruction::Instruction, signature::Keypair, signer::Signer,
-    sysvar, transaction::Transaction,
+    instruction::Instruction, signature::Keypair, signer::Signer, sysvar, transaction::Transaction,
 };
 use std::time;
 
@@ -22,21 +20,36 @@ async fn success_native() {
     let quote_amount = 11 * 10u64.pow(9);
     let expire_date = 9999999999;
 
-    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10000000).await;
-    utils::airdrop(&mut context, &recipient.pubkey(), quote_amount + 10000000).await;
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
 
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -50,6 +63,17 @@ async fn success_native() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -70,21 +94,44 @@ async fn success_native() {
 
     context.warp_to_slot(3).unwrap();
 
+    // Fee is not charged until `Protocol` is initialized, so treasury accounts are not checked
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
     let accounts = p2swap::accounts::ExecuteOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         recipient_token_account: recipient.pubkey(),
         recipient_receive_token_account: recipient.pubkey(),
         quote_token_account: funder.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
+        protocol,
+        treasury_base_token_account: recipient.pubkey(),
+        treasury_quote_token_account: recipient.pubkey(),
     }
     .to_account_metas(None);
 
-    let data = p2swap::instruction::ExecuteOrder { escrow_bump }.data();
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: 0,
+        preimage: None,
+        recipient_salt: None,
+    }
+    .data();
 
     let instruction = Instruction {
         program_id: p2swap::id(),
@@ -126,8 +173,11 @@ async fn success_native() {
         .unwrap()
         .unwrap();
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.status, p2swap::state::OrderStatus::Completed);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
 }
 
 #[tokio::test]
@@ -176,15 +226,26 @@ async fn success_native_to_spl() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) =
+        p2swap::utils::find_order_book_address(&funder_token_mint.pubkey(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder_token_account.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: funder_token_mint.pubkey(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -198,6 +259,17 @@ async fn success_native_to_spl() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -218,21 +290,44 @@ async fn success_native_to_spl() {
 
     context.warp_to_slot(3).unwrap();
 
+    let treasury = Keypair::new();
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
     let accounts = p2swap::accounts::ExecuteOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         recipient_token_account: recipient.pubkey(),
         recipient_receive_token_account: recipient_receive_token_account.pubkey(),
         quote_token_account: funder.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
+        protocol,
+        treasury_base_token_account: treasury.pubkey(),
+        treasury_quote_token_account: treasury.pubkey(),
     }
     .to_account_metas(None);
 
-    let data = p2swap::instruction::ExecuteOrder { escrow_bump }.data();
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: 0,
+        preimage: None,
+        recipient_salt: None,
+    }
+    .data();
 
     let instruction = Instruction {
         program_id: p2swap::id(),
@@ -293,8 +388,11 @@ async fn success_native_to_spl() {
         .unwrap()
         .unwrap();
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.status, p2swap::state::OrderStatus::Completed);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
 }
 
 #[tokio::test]
@@ -372,15 +470,28 @@ async fn success_spl() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(
+        &funder_token_mint.pubkey(),
+        &recipient_token_mint.pubkey(),
+    );
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder_token_account.pubkey(),
         escrow,
         quote_token_account: funder_receive_token_account.pubkey(),
         escrow_mint: funder_token_mint.pubkey(),
         quote_mint: recipient_token_mint.pubkey(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -394,6 +505,17 @@ async fn success_spl() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -414,21 +536,44 @@ async fn success_spl() {
 
     context.warp_to_slot(3).unwrap();
 
+    let treasury = Keypair::new();
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
     let accounts = p2swap::accounts::ExecuteOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         recipient_token_account: recipient_token_account.pubkey(),
         recipient_receive_token_account: recipient_receive_token_account.pubkey(),
         quote_token_account: funder_receive_token_account.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
+        protocol,
+        treasury_base_token_account: treasury.pubkey(),
+        treasury_quote_token_account: treasury.pubkey(),
     }
     .to_account_metas(None);
 
-    let data = p2swap::instruction::ExecuteOrder { escrow_bump }.data();
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: 0,
+        preimage: None,
+        recipient_salt: None,
+    }
+    .data();
 
     let instruction = Instruction {
         program_id: p2swap::id(),
@@ -487,8 +632,11 @@ async fn success_spl() {
         .unwrap()
         .unwrap();
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.status, p2swap::state::OrderStatus::Completed);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
 }
 
 #[tokio::test]
@@ -537,15 +685,26 @@ async fn success_spl_to_native() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) =
+        p2swap::utils::find_order_book_address(&System::id(), &recipient_token_mint.pubkey());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder_token_account.pubkey(),
         escrow_mint: System::id(),
         quote_mint: recipient_token_mint.pubkey(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -559,6 +718,17 @@ async fn success_spl_to_native() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -579,21 +749,44 @@ async fn success_spl_to_native() {
 
     context.warp_to_slot(3).unwrap();
 
+    let treasury = Keypair::new();
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
     let accounts = p2swap::accounts::ExecuteOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         recipient_token_account: recipient_token_account.pubkey(),
         recipient_receive_token_account: recipient.pubkey(),
         quote_token_account: funder_token_account.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
+        protocol,
+        treasury_base_token_account: treasury.pubkey(),
+        treasury_quote_token_account: treasury.pubkey(),
     }
     .to_account_metas(None);
 
-    let data = p2swap::instruction::ExecuteOrder { escrow_bump }.data();
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: 0,
+        preimage: None,
+        recipient_salt: None,
+    }
+    .data();
 
     let instruction = Instruction {
         program_id: p2swap::id(),
@@ -653,8 +846,11 @@ async fn success_spl_to_native() {
         .unwrap()
         .unwrap();
 
-    let order = try_from_slice_unchecked::<p2swap::state::Order>(&order_account.data[8..]).unwrap();
-    assert_eq!(order.status, p2swap::state::OrderStatus::Completed);
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
 }
 
 #[tokio::test]
@@ -681,15 +877,25 @@ async fn fail_order_is_expired() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -703,6 +909,17 @@ async fn fail_order_is_expired() {
         quote_amount,
         start_date: None,
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -723,21 +940,44 @@ async fn fail_order_is_expired() {
 
     context.warp_to_slot(3).unwrap();
 
+    let treasury = Keypair::new();
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
     let accounts = p2swap::accounts::ExecuteOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         recipient_token_account: recipient.pubkey(),
         recipient_receive_token_account: recipient.pubkey(),
         quote_token_account: funder.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
+        protocol,
+        treasury_base_token_account: treasury.pubkey(),
+        treasury_quote_token_account: treasury.pubkey(),
     }
     .to_account_metas(None);
 
-    let data = p2swap::instruction::ExecuteOrder { escrow_bump }.data();
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: 0,
+        preimage: None,
+        recipient_salt: None,
+    }
+    .data();
 
     let instruction = Instruction {
         program_id: p2swap::id(),
@@ -752,7 +992,7 @@ async fn fail_order_is_expired() {
         context.last_blockhash,
     );
 
-    utils::wait(&mut context, time::Duration::from_secs(1)).await;
+    utils::wait_until(&mut context, expire_date).await;
 
     let error = context
         .banks_client
@@ -802,15 +1042,25 @@ async fn fail_order_is_not_started() {
     let (escrow, escrow_bump) =
         p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
 
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
     let accounts = p2swap::accounts::CreateOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         funder_token_account: funder.pubkey(),
         escrow,
         quote_token_account: funder.pubkey(),
         escrow_mint: System::id(),
         quote_mint: System::id(),
+        order_book,
+        funder_profile,
+        protocol,
         rent_sysvar: sysvar::rent::id(),
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
@@ -824,6 +1074,17 @@ async fn fail_order_is_not_started() {
         quote_amount,
         start_date: Some(start_date),
         expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
     }
     .data();
 
@@ -844,21 +1105,44 @@ async fn fail_order_is_not_started() {
 
     context.warp_to_slot(3).unwrap();
 
+    let treasury = Keypair::new();
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
     let accounts = p2swap::accounts::ExecuteOrder {
         order: order.pubkey(),
         funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
         recipient: recipient.pubkey(),
         recipient_token_account: recipient.pubkey(),
         recipient_receive_token_account: recipient.pubkey(),
         quote_token_account: funder.pubkey(),
         escrow,
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
         clock_sysvar: sysvar::clock::id(),
         token_program: spl_token::id(),
         system_program: System::id(),
+        protocol,
+        treasury_base_token_account: treasury.pubkey(),
+        treasury_quote_token_account: treasury.pubkey(),
     }
     .to_account_metas(None);
 
-    let data = p2swap::instruction::ExecuteOrder { escrow_bump }.data();
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: 0,
+        preimage: None,
+        recipient_salt: None,
+    }
+    .data();
 
     let instruction = Instruction {
         program_id: p2swap::id(),
@@ -887,3 +1171,208 @@ async fn fail_order_is_not_started() {
     }
     assert_eq!(true, false);
 }
+
+#[tokio::test]
+async fn success_dutch_auction() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let quote_amount = 11 * 10u64.pow(9);
+    let floor_quote_amount = 5 * 10u64.pow(9);
+    let expire_date = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 1000;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), quote_amount * 2).await;
+
+    let recipient_token_mint = Keypair::new();
+    let recipient_token_account = Keypair::new();
+    let funder_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &recipient_token_mint, &recipient.pubkey(), 9).await;
+    utils::create_token_account(
+        &mut context,
+        &recipient_token_account,
+        &recipient_token_mint.pubkey(),
+        &recipient.pubkey(),
+    )
+    .await;
+    utils::create_token_account(
+        &mut context,
+        &funder_token_account,
+        &recipient_token_mint.pubkey(),
+        &funder.pubkey(),
+    )
+    .await;
+    utils::mint_to(
+        &mut context,
+        &recipient_token_mint.pubkey(),
+        &recipient_token_account.pubkey(),
+        &recipient,
+        quote_amount,
+    )
+    .await;
+
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+
+    let (order_book, _) =
+        p2swap::utils::find_order_book_address(&System::id(), &recipient_token_mint.pubkey());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let accounts = p2swap::accounts::CreateOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        recipient: recipient.pubkey(),
+        funder_token_account: funder.pubkey(),
+        escrow,
+        quote_token_account: funder_token_account.pubkey(),
+        escrow_mint: System::id(),
+        quote_mint: recipient_token_mint.pubkey(),
+        order_book,
+        funder_profile,
+        protocol,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateOrder {
+        escrow_bump,
+        base_amount,
+        quote_amount,
+        start_date: None,
+        expire_date,
+        price_curve: p2swap::state::PriceCurve::Linear,
+        floor_quote_amount,
+        referral_share_bps: 0,
+        collection_offer: false,
+        cancel_locked_until: None,
+        hashlock: None,
+        arbitration: None,
+        memo: None,
+        reference: None,
+        recipient_commitment: None,
+        gate: None,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&funder.pubkey()),
+        &[&funder, &order],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let treasury = Keypair::new();
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(&recipient.pubkey());
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
+    let accounts = p2swap::accounts::ExecuteOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        recipient: recipient.pubkey(),
+        recipient_token_account: recipient_token_account.pubkey(),
+        recipient_receive_token_account: recipient.pubkey(),
+        quote_token_account: funder_token_account.pubkey(),
+        escrow,
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+        protocol,
+        treasury_base_token_account: treasury.pubkey(),
+        treasury_quote_token_account: treasury.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: 0,
+        preimage: None,
+        recipient_salt: None,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&recipient.pubkey()),
+        &[&recipient],
+        context.last_blockhash,
+    );
+
+    utils::wait(&mut context, time::Duration::from_secs(1)).await;
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let funder_token_account_data = context
+        .banks_client
+        .get_account(funder_token_account.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let funder_token_account_balance =
+        token::TokenAccount::try_deserialize_unchecked(&mut funder_token_account_data.as_ref())
+            .unwrap()
+            .amount;
+
+    assert!(funder_token_account_balance < quote_amount);
+    assert!(funder_token_account_balance > floor_quote_amount);
+
+    let order_account = context
+        .banks_client
+        .get_account(order.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let order = p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+    assert_eq!(
+        order.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
+    assert_eq!(
+        order.price_curve().unwrap(),
+        p2swap::state::PriceCurve::Linear
+    );
+}
@@ -0,0 +1,272 @@
+mod utils;
+
+use anchor_lang::{prelude::*, InstructionData, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+/// Set `Protocol` fee tiers, signed by protocol admin (`context.payer`).
+async fn set_fee_tiers(
+    context: &mut ProgramTestContext,
+    protocol: &Pubkey,
+    discount_mint: &Pubkey,
+    fee_tiers: Vec<p2swap::state::FeeTier>,
+) -> std::result::Result<(), BanksClientError> {
+    let accounts = p2swap::accounts::SetFeeTiers {
+        protocol: *protocol,
+        admin: context.payer.pubkey(),
+        discount_mint: *discount_mint,
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::SetFeeTiers { fee_tiers }.data(),
+        accounts,
+    };
+
+    let last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+fn fee_tiers() -> Vec<p2swap::state::FeeTier> {
+    vec![
+        p2swap::state::FeeTier {
+            min_amount: 100,
+            fee_bps: 50,
+        },
+        p2swap::state::FeeTier {
+            min_amount: 1000,
+            fee_bps: 20,
+        },
+        p2swap::state::FeeTier {
+            min_amount: 10000,
+            fee_bps: 0,
+        },
+    ]
+}
+
+#[tokio::test]
+async fn success_discounted_fee() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+
+    let discount_mint = Keypair::new();
+    let discount_token_account = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    // 1% protocol fee
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 100, 0).await;
+
+    let mint_authority = Keypair::new();
+    utils::create_mint(&mut context, &discount_mint, &mint_authority.pubkey(), 0).await;
+    utils::create_token_account(
+        &mut context,
+        &discount_token_account,
+        &discount_mint.pubkey(),
+        &recipient.pubkey(),
+    )
+    .await;
+    utils::mint_to(
+        &mut context,
+        &discount_mint.pubkey(),
+        &discount_token_account.pubkey(),
+        &mint_authority,
+        5000,
+    )
+    .await;
+
+    set_fee_tiers(
+        &mut context,
+        &protocol,
+        &discount_mint.pubkey(),
+        fee_tiers(),
+    )
+    .await
+    .unwrap();
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            remaining_accounts: vec![AccountMeta::new_readonly(
+                discount_token_account.pubkey(),
+                false,
+            )],
+            ..utils::ExecuteParams::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // `funder` side is charged full fee, `recipient` side - fee of tier with 1000 tokens
+    let base_fee = base_amount / 100;
+    let quote_fee = quote_amount * 20 / 10000;
+
+    assert_eq!(
+        utils::get_lamports(&mut context, &treasury.pubkey()).await,
+        base_fee + quote_fee
+    );
+}
+
+#[tokio::test]
+async fn fail_invalid_fee_tiers() {
+    let mut context = utils::setup_test_context().await;
+
+    let treasury = Keypair::new();
+    let discount_mint = Keypair::new();
+
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 100, 0).await;
+
+    // Tier fee is higher than protocol fee
+    let mut fee_tiers = fee_tiers();
+    fee_tiers[0].fee_bps = 200;
+
+    let error = set_fee_tiers(&mut context, &protocol, &discount_mint.pubkey(), fee_tiers)
+        .await
+        .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6068 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn fail_discount_account_mismatch() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+
+    let discount_mint = Keypair::new();
+    let discount_token_account = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    let protocol = utils::init_protocol(&mut context, &treasury.pubkey(), 100, 0).await;
+
+    // Discount tokens are held by `funder`, not by `recipient`
+    let mint_authority = Keypair::new();
+    utils::create_mint(&mut context, &discount_mint, &mint_authority.pubkey(), 0).await;
+    utils::create_token_account(
+        &mut context,
+        &discount_token_account,
+        &discount_mint.pubkey(),
+        &funder.pubkey(),
+    )
+    .await;
+    utils::mint_to(
+        &mut context,
+        &discount_mint.pubkey(),
+        &discount_token_account.pubkey(),
+        &mint_authority,
+        5000,
+    )
+    .await;
+
+    set_fee_tiers(
+        &mut context,
+        &protocol,
+        &discount_mint.pubkey(),
+        fee_tiers(),
+    )
+    .await
+    .unwrap();
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    let error = utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            remaining_accounts: vec![AccountMeta::new_readonly(
+                discount_token_account.pubkey(),
+                false,
+            )],
+            ..utils::ExecuteParams::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6069 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
@@ -0,0 +1,181 @@
+mod utils;
+
+use anchor_lang::{prelude::*, System};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+#[tokio::test]
+async fn success_execute_hidden_order() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    let salt = [7; 32];
+    let recipient_commitment = p2swap::utils::hash_recipient(&recipient.pubkey(), &salt);
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &System::id(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams {
+            recipient_commitment: Some(recipient_commitment),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Only commitment of `recipient` is stored
+    let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+    assert!(order_state.is_hidden());
+    assert_eq!(order_state.recipient, System::id());
+    assert_eq!(order_state.recipient_commitment, recipient_commitment);
+
+    utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            recipient_salt: Some(salt),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // `recipient` is revealed on execution
+    let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+    assert_eq!(
+        order_state.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
+    assert_eq!(order_state.recipient, recipient.pubkey());
+}
+
+#[tokio::test]
+async fn fail_recipient_commitment_mismatch() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let other_wallet = Keypair::new();
+    let treasury = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &other_wallet.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    let salt = [7; 32];
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &System::id(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams {
+            recipient_commitment: Some(p2swap::utils::hash_recipient(&recipient.pubkey(), &salt)),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Salt is leaked, but signer doesn't match commitment
+    let error = utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &other_wallet,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            recipient_salt: Some(salt),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6079 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn fail_hidden_order_with_recipient() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+
+    let error = utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        base_amount,
+        utils::OrderParams {
+            recipient_commitment: Some(p2swap::utils::hash_recipient(
+                &recipient.pubkey(),
+                &[7; 32],
+            )),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6078 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
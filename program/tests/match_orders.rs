@@ -0,0 +1,576 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    borsh::try_from_slice_unchecked, instruction::Instruction, signature::Keypair, signer::Signer,
+    sysvar,
+};
+
+#[allow(clippy::too_many_arguments)]
+async fn match_orders(
+    context: &mut ProgramTestContext,
+    order_a: &Pubkey,
+    funder_a: &Pubkey,
+    quote_token_account_a: &Pubkey,
+    order_b: &Pubkey,
+    funder_b: &Pubkey,
+    quote_token_account_b: &Pubkey,
+    base_mint_a: &Pubkey,
+    base_mint_b: &Pubkey,
+    matcher: &Keypair,
+    matcher_token_account_a: &Pubkey,
+    matcher_token_account_b: &Pubkey,
+    treasury_token_account_a: &Pubkey,
+    treasury_token_account_b: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let (escrow_a, escrow_a_bump) = p2swap::utils::find_order_escrow_address(funder_a, order_a);
+    let (escrow_b, escrow_b_bump) = p2swap::utils::find_order_escrow_address(funder_b, order_b);
+    let (order_book_a, _) = p2swap::utils::find_order_book_address(base_mint_a, base_mint_b);
+    let (order_book_b, _) = p2swap::utils::find_order_book_address(base_mint_b, base_mint_a);
+    let (funder_a_profile, _) = p2swap::utils::find_profile_address(funder_a);
+    let (funder_b_profile, _) = p2swap::utils::find_profile_address(funder_b);
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats_a, _) = p2swap::utils::find_pair_stats_address(&order_book_a);
+    let (pair_stats_b, _) = p2swap::utils::find_pair_stats_address(&order_book_b);
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let accounts = p2swap::accounts::MatchOrders {
+        order_a: *order_a,
+        funder_a: *funder_a,
+        rent_payer_a: *funder_a,
+        escrow_a,
+        quote_token_account_a: *quote_token_account_a,
+        order_book_a,
+        order_b: *order_b,
+        funder_b: *funder_b,
+        rent_payer_b: *funder_b,
+        escrow_b,
+        quote_token_account_b: *quote_token_account_b,
+        order_book_b,
+        matcher: matcher.pubkey(),
+        matcher_token_account_a: *matcher_token_account_a,
+        matcher_token_account_b: *matcher_token_account_b,
+        funder_a_profile,
+        funder_b_profile,
+        stats,
+        pair_stats_a,
+        pair_stats_b,
+        protocol,
+        treasury_token_account_a: *treasury_token_account_a,
+        treasury_token_account_b: *treasury_token_account_b,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::MatchOrders {
+        escrow_a_bump,
+        escrow_b_bump,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, matcher, &[matcher]).await
+}
+
+#[tokio::test]
+async fn success_native_to_spl() {
+    let mut context = utils::setup_test_context().await;
+
+    let order_a = Keypair::new();
+    let order_b = Keypair::new();
+    let funder_a = Keypair::new();
+    let funder_b = Keypair::new();
+    let matcher = Keypair::new();
+
+    utils::airdrop(&mut context, &funder_a.pubkey(), 20 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &funder_b.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &matcher.pubkey(), 10u64.pow(9)).await;
+
+    let mint = Keypair::new();
+    let funder_a_token_account = Keypair::new();
+    let funder_b_token_account = Keypair::new();
+    let matcher_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &funder_b.pubkey(), 9).await;
+    for (account, owner) in [
+        (&funder_a_token_account, &funder_a),
+        (&funder_b_token_account, &funder_b),
+        (&matcher_token_account, &matcher),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), &owner.pubkey()).await;
+    }
+    utils::mint_to(
+        &mut context,
+        &mint.pubkey(),
+        &funder_b_token_account.pubkey(),
+        &funder_b,
+        110 * 10u64.pow(9),
+    )
+    .await;
+
+    // `funder_a` sells 10 SOL for 100 tokens, open for any counter order
+    utils::create_order(
+        &mut context,
+        &order_a,
+        &funder_a,
+        &System::id(),
+        10 * 10u64.pow(9),
+        100 * 10u64.pow(9),
+        utils::OrderParams {
+            quote_mint: mint.pubkey(),
+            quote_token_account: Some(funder_a_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // `funder_b` sells 110 tokens for 9 SOL
+    utils::create_order(
+        &mut context,
+        &order_b,
+        &funder_b,
+        &funder_a.pubkey(),
+        110 * 10u64.pow(9),
+        9 * 10u64.pow(9),
+        utils::OrderParams {
+            escrow_mint: mint.pubkey(),
+            funder_token_account: Some(funder_b_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let funder_b_balance_before = context
+        .banks_client
+        .get_account(funder_b.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    let matcher_balance_before = context
+        .banks_client
+        .get_account(matcher.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    match_orders(
+        &mut context,
+        &order_a.pubkey(),
+        &funder_a.pubkey(),
+        &funder_a_token_account.pubkey(),
+        &order_b.pubkey(),
+        &funder_b.pubkey(),
+        &funder_b.pubkey(),
+        &System::id(),
+        &mint.pubkey(),
+        &matcher,
+        &matcher.pubkey(),
+        &matcher_token_account.pubkey(),
+        &matcher.pubkey(),
+        &matcher_token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &funder_a_token_account.pubkey()).await,
+        100 * 10u64.pow(9)
+    );
+    assert_eq!(
+        utils::get_token_balance(&mut context, &matcher_token_account.pubkey()).await,
+        10 * 10u64.pow(9)
+    );
+
+    let funder_b_balance_after = context
+        .banks_client
+        .get_account(funder_b.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(funder_b_balance_after >= funder_b_balance_before + 9 * 10u64.pow(9));
+
+    let matcher_balance_after = context
+        .banks_client
+        .get_account(matcher.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    // `matcher` pays rent of `Stats` and `PairStats`
+    assert!(matcher_balance_after > matcher_balance_before + 10u64.pow(9) - 10u64.pow(8));
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &mint.pubkey());
+    let order_book_account = context
+        .banks_client
+        .get_account(order_book)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let order_book =
+        try_from_slice_unchecked::<p2swap::state::OrderBook>(&order_book_account.data[8..])
+            .unwrap();
+    assert!(order_book.entries.is_empty());
+
+    for order in [&order_a, &order_b] {
+        let order_account = context
+            .banks_client
+            .get_account(order.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let order =
+            p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap();
+        assert_eq!(
+            order.status().unwrap(),
+            p2swap::state::OrderStatus::Completed
+        );
+
+        let escrow_account = context
+            .banks_client
+            .get_account(order.escrow)
+            .await
+            .unwrap();
+        assert!(escrow_account.is_none());
+    }
+}
+
+#[tokio::test]
+async fn success_protocol_fee() {
+    let mut context = utils::setup_test_context().await;
+
+    let order_a = Keypair::new();
+    let order_b = Keypair::new();
+    let funder_a = Keypair::new();
+    let funder_b = Keypair::new();
+    let matcher = Keypair::new();
+    let treasury = Keypair::new();
+
+    utils::airdrop(&mut context, &funder_a.pubkey(), 20 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &funder_b.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &matcher.pubkey(), 10u64.pow(9)).await;
+
+    let mint = Keypair::new();
+    let funder_a_token_account = Keypair::new();
+    let funder_b_token_account = Keypair::new();
+    let matcher_token_account = Keypair::new();
+    let treasury_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &funder_b.pubkey(), 9).await;
+    for (account, owner) in [
+        (&funder_a_token_account, &funder_a.pubkey()),
+        (&funder_b_token_account, &funder_b.pubkey()),
+        (&matcher_token_account, &matcher.pubkey()),
+        (&treasury_token_account, &treasury.pubkey()),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), owner).await;
+    }
+    utils::mint_to(
+        &mut context,
+        &mint.pubkey(),
+        &funder_b_token_account.pubkey(),
+        &funder_b,
+        110 * 10u64.pow(9),
+    )
+    .await;
+
+    // 1% fee
+    utils::init_protocol(&mut context, &treasury.pubkey(), 100, 0).await;
+
+    // `funder_a` sells 10 SOL for 100 tokens
+    utils::create_order(
+        &mut context,
+        &order_a,
+        &funder_a,
+        &System::id(),
+        10 * 10u64.pow(9),
+        100 * 10u64.pow(9),
+        utils::OrderParams {
+            quote_mint: mint.pubkey(),
+            quote_token_account: Some(funder_a_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // `funder_b` sells 110 tokens for 9 SOL
+    utils::create_order(
+        &mut context,
+        &order_b,
+        &funder_b,
+        &System::id(),
+        110 * 10u64.pow(9),
+        9 * 10u64.pow(9),
+        utils::OrderParams {
+            escrow_mint: mint.pubkey(),
+            funder_token_account: Some(funder_b_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    match_orders(
+        &mut context,
+        &order_a.pubkey(),
+        &funder_a.pubkey(),
+        &funder_a_token_account.pubkey(),
+        &order_b.pubkey(),
+        &funder_b.pubkey(),
+        &funder_b.pubkey(),
+        &System::id(),
+        &mint.pubkey(),
+        &matcher,
+        &matcher.pubkey(),
+        &matcher_token_account.pubkey(),
+        &treasury.pubkey(),
+        &treasury_token_account.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    // Fee is charged from amount, that is sent to counter funder, surplus is not charged
+    assert_eq!(
+        utils::get_lamports(&mut context, &treasury.pubkey()).await,
+        9 * 10u64.pow(7)
+    );
+    assert_eq!(
+        utils::get_token_balance(&mut context, &treasury_token_account.pubkey()).await,
+        10u64.pow(9)
+    );
+    assert_eq!(
+        utils::get_token_balance(&mut context, &funder_a_token_account.pubkey()).await,
+        99 * 10u64.pow(9)
+    );
+    assert_eq!(
+        utils::get_token_balance(&mut context, &matcher_token_account.pubkey()).await,
+        10 * 10u64.pow(9)
+    );
+
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let stats_account = context
+        .banks_client
+        .get_account(stats)
+        .await
+        .unwrap()
+        .unwrap();
+    let stats = p2swap::state::Stats::try_deserialize(&mut stats_account.data.as_slice()).unwrap();
+    assert_eq!(stats.trades_count, 2);
+    assert_eq!(stats.pairs_count, 2);
+    assert_eq!(stats.native_volume, 19 * 10u64.pow(9) as u128);
+
+    for funder in [&funder_a, &funder_b] {
+        let (profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+        let profile_account = context
+            .banks_client
+            .get_account(profile)
+            .await
+            .unwrap()
+            .unwrap();
+        let profile =
+            p2swap::state::Profile::try_deserialize(&mut profile_account.data.as_slice()).unwrap();
+        assert_eq!(profile.orders_completed, 1);
+    }
+}
+
+#[tokio::test]
+async fn fail_orders_do_not_cross() {
+    let mut context = utils::setup_test_context().await;
+
+    let order_a = Keypair::new();
+    let order_b = Keypair::new();
+    let funder_a = Keypair::new();
+    let funder_b = Keypair::new();
+    let matcher = Keypair::new();
+
+    utils::airdrop(&mut context, &funder_a.pubkey(), 20 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &funder_b.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &matcher.pubkey(), 10u64.pow(9)).await;
+
+    let mint = Keypair::new();
+    let funder_a_token_account = Keypair::new();
+    let funder_b_token_account = Keypair::new();
+    let matcher_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &funder_b.pubkey(), 9).await;
+    for (account, owner) in [
+        (&funder_a_token_account, &funder_a),
+        (&funder_b_token_account, &funder_b),
+        (&matcher_token_account, &matcher),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), &owner.pubkey()).await;
+    }
+    utils::mint_to(
+        &mut context,
+        &mint.pubkey(),
+        &funder_b_token_account.pubkey(),
+        &funder_b,
+        90 * 10u64.pow(9),
+    )
+    .await;
+
+    // `funder_a` sells 10 SOL for 100 tokens
+    utils::create_order(
+        &mut context,
+        &order_a,
+        &funder_a,
+        &System::id(),
+        10 * 10u64.pow(9),
+        100 * 10u64.pow(9),
+        utils::OrderParams {
+            quote_mint: mint.pubkey(),
+            quote_token_account: Some(funder_a_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // `funder_b` sells only 90 tokens for 9 SOL
+    utils::create_order(
+        &mut context,
+        &order_b,
+        &funder_b,
+        &System::id(),
+        90 * 10u64.pow(9),
+        9 * 10u64.pow(9),
+        utils::OrderParams {
+            escrow_mint: mint.pubkey(),
+            funder_token_account: Some(funder_b_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let error = match_orders(
+        &mut context,
+        &order_a.pubkey(),
+        &funder_a.pubkey(),
+        &funder_a_token_account.pubkey(),
+        &order_b.pubkey(),
+        &funder_b.pubkey(),
+        &funder_b.pubkey(),
+        &System::id(),
+        &mint.pubkey(),
+        &matcher,
+        &matcher.pubkey(),
+        &matcher_token_account.pubkey(),
+        &matcher.pubkey(),
+        &matcher_token_account.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6044 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn fail_same_funder() {
+    let mut context = utils::setup_test_context().await;
+
+    let order_a = Keypair::new();
+    let order_b = Keypair::new();
+    let funder = Keypair::new();
+    let matcher = Keypair::new();
+
+    utils::airdrop(&mut context, &funder.pubkey(), 20 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &matcher.pubkey(), 10u64.pow(9)).await;
+
+    let mint = Keypair::new();
+    let funder_token_account = Keypair::new();
+    let matcher_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &funder.pubkey(), 9).await;
+    for (account, owner) in [
+        (&funder_token_account, &funder),
+        (&matcher_token_account, &matcher),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), &owner.pubkey()).await;
+    }
+    utils::mint_to(
+        &mut context,
+        &mint.pubkey(),
+        &funder_token_account.pubkey(),
+        &funder,
+        110 * 10u64.pow(9),
+    )
+    .await;
+
+    // `funder` sells 10 SOL for 100 tokens and 110 tokens for 9 SOL
+    utils::create_order(
+        &mut context,
+        &order_a,
+        &funder,
+        &System::id(),
+        10 * 10u64.pow(9),
+        100 * 10u64.pow(9),
+        utils::OrderParams {
+            quote_mint: mint.pubkey(),
+            quote_token_account: Some(funder_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    utils::create_order(
+        &mut context,
+        &order_b,
+        &funder,
+        &System::id(),
+        110 * 10u64.pow(9),
+        9 * 10u64.pow(9),
+        utils::OrderParams {
+            escrow_mint: mint.pubkey(),
+            funder_token_account: Some(funder_token_account.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let error = match_orders(
+        &mut context,
+        &order_a.pubkey(),
+        &funder.pubkey(),
+        &funder_token_account.pubkey(),
+        &order_b.pubkey(),
+        &funder.pubkey(),
+        &funder.pubkey(),
+        &System::id(),
+        &mint.pubkey(),
+        &matcher,
+        &matcher.pubkey(),
+        &matcher_token_account.pubkey(),
+        &matcher.pubkey(),
+        &matcher_token_account.pubkey(),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(utils::get_error_code(error), Some(6045));
+}
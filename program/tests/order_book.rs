@@ -0,0 +1,192 @@
+mod utils;
+
+use anchor_lang::{Id, System};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, sysvar};
+
+#[tokio::test]
+async fn success_prune_expired_orders() {
+    let mut context = utils::setup_test_context().await;
+
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    utils::airdrop(&mut context, &funder.pubkey(), 10u64.pow(10)).await;
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let now = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+
+    // Fill `OrderBook` with short-lived `Order`'s
+    let expire_date = now + 60;
+    for i in 0..p2swap::state::ORDER_BOOK_MAX_ORDERS as u64 {
+        utils::create_order(
+            &mut context,
+            &Keypair::new(),
+            &funder,
+            &recipient.pubkey(),
+            10u64.pow(6),
+            10u64.pow(6) + i,
+            utils::OrderParams {
+                expire_date,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let order = Keypair::new();
+
+    utils::wait_until(&mut context, expire_date + 1).await;
+
+    utils::prune_order_book(&mut context, &order_book, &[])
+        .await
+        .unwrap();
+
+    let order_book_state = utils::get_order_book(&mut context, &order_book).await;
+    assert!(order_book_state.entries.is_empty());
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        10u64.pow(6),
+        10u64.pow(6),
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    let order_book_state = utils::get_order_book(&mut context, &order_book).await;
+    assert_eq!(order_book_state.entries.len(), 1);
+    assert_eq!(order_book_state.entries[0].order, order.pubkey());
+}
+
+#[tokio::test]
+async fn success_create_order_in_full_order_book() {
+    let mut context = utils::setup_test_context().await;
+
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    utils::airdrop(&mut context, &funder.pubkey(), 10u64.pow(10)).await;
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    // Fill `OrderBook`, the last `Order` is the worst priced one
+    let mut orders = Vec::new();
+    for i in 0..p2swap::state::ORDER_BOOK_MAX_ORDERS as u64 {
+        let order = Keypair::new();
+
+        utils::create_order(
+            &mut context,
+            &order,
+            &funder,
+            &recipient.pubkey(),
+            10u64.pow(6),
+            10u64.pow(6) + i,
+            utils::OrderParams::default(),
+        )
+        .await
+        .unwrap();
+
+        orders.push(order.pubkey());
+    }
+
+    // 65th `Order` is created, but isn't indexed as the worst priced one
+    let worse_order = Keypair::new();
+
+    utils::create_order(
+        &mut context,
+        &worse_order,
+        &funder,
+        &recipient.pubkey(),
+        10u64.pow(6),
+        2 * 10u64.pow(6),
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    let order_state = utils::get_order(&mut context, &worse_order.pubkey()).await;
+    assert_eq!(
+        order_state.status().unwrap(),
+        p2swap::state::OrderStatus::Created
+    );
+
+    let order_book_state = utils::get_order_book(&mut context, &order_book).await;
+    assert_eq!(
+        order_book_state.entries.len(),
+        p2swap::state::ORDER_BOOK_MAX_ORDERS
+    );
+    assert!(order_book_state
+        .entries
+        .iter()
+        .all(|e| e.order != worse_order.pubkey()));
+
+    // Better priced `Order` evicts the worst priced entry
+    let better_order = Keypair::new();
+
+    utils::create_order(
+        &mut context,
+        &better_order,
+        &funder,
+        &recipient.pubkey(),
+        10u64.pow(6),
+        10u64.pow(6) - 1,
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    let order_book_state = utils::get_order_book(&mut context, &order_book).await;
+    assert_eq!(
+        order_book_state.entries.len(),
+        p2swap::state::ORDER_BOOK_MAX_ORDERS
+    );
+    assert_eq!(order_book_state.entries[0].order, better_order.pubkey());
+    assert_eq!(
+        order_book_state.entries.last().unwrap().order,
+        orders[orders.len() - 2]
+    );
+}
+
+#[tokio::test]
+async fn fail_prune_active_order() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    utils::airdrop(&mut context, &funder.pubkey(), 10u64.pow(9)).await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        10u64.pow(6),
+        10u64.pow(6),
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let err = utils::prune_order_book(&mut context, &order_book, &[order.pubkey()])
+        .await
+        .unwrap_err();
+    assert_eq!(utils::get_error_code(err), Some(6008));
+
+    let order_book_state = utils::get_order_book(&mut context, &order_book).await;
+    assert_eq!(order_book_state.entries.len(), 1);
+}
@@ -0,0 +1,169 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer, sysvar};
+
+async fn get_profile(context: &mut ProgramTestContext, wallet: &Pubkey) -> p2swap::state::Profile {
+    let (profile, _) = p2swap::utils::find_profile_address(wallet);
+
+    let profile_account = context
+        .banks_client
+        .get_account(profile)
+        .await
+        .unwrap()
+        .unwrap();
+
+    p2swap::state::Profile::try_deserialize(&mut profile_account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn success_execute_updates_profiles() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    let treasury = Keypair::new();
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    let funder_profile = get_profile(&mut context, &funder.pubkey()).await;
+    assert_eq!(funder_profile.wallet, funder.pubkey());
+    assert_eq!(funder_profile.orders_created, 1);
+    assert_eq!(funder_profile.orders_completed, 0);
+
+    utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let funder_profile = get_profile(&mut context, &funder.pubkey()).await;
+    assert_eq!(funder_profile.orders_created, 1);
+    assert_eq!(funder_profile.orders_completed, 1);
+    assert_eq!(
+        funder_profile.volumes,
+        vec![p2swap::state::ProfileVolume {
+            mint: System::id(),
+            amount: base_amount,
+        }]
+    );
+
+    let recipient_profile = get_profile(&mut context, &recipient.pubkey()).await;
+    assert_eq!(recipient_profile.wallet, recipient.pubkey());
+    assert_eq!(recipient_profile.orders_created, 0);
+    assert_eq!(recipient_profile.orders_completed, 1);
+    assert_eq!(
+        recipient_profile.volumes,
+        vec![p2swap::state::ProfileVolume {
+            mint: System::id(),
+            amount: quote_amount,
+        }]
+    );
+}
+
+#[tokio::test]
+async fn success_cancel_after_expiration() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+
+    let clock = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap();
+    let expire_date = clock.unix_timestamp + 60;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        base_amount,
+        utils::OrderParams {
+            expire_date,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    utils::wait_until(&mut context, expire_date + 1).await;
+
+    let (escrow, escrow_bump) =
+        p2swap::utils::find_order_escrow_address(&funder.pubkey(), &order.pubkey());
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+
+    let (funder_profile, _) = p2swap::utils::find_profile_address(&funder.pubkey());
+
+    let accounts = p2swap::accounts::CancelOrder {
+        order: order.pubkey(),
+        funder: funder.pubkey(),
+        rent_payer: funder.pubkey(),
+        funder_token_account: funder.pubkey(),
+        escrow,
+        order_book,
+        funder_profile,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CancelOrder { escrow_bump }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(&mut context, instruction, &funder, &[&funder])
+        .await
+        .unwrap();
+
+    let funder_profile = get_profile(&mut context, &funder.pubkey()).await;
+    assert_eq!(funder_profile.orders_created, 1);
+    assert_eq!(funder_profile.orders_canceled, 0);
+    assert_eq!(funder_profile.orders_expired, 1);
+}
@@ -0,0 +1,229 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    borsh::try_from_slice_unchecked,
+    instruction::{AccountMeta, Instruction},
+    signature::Keypair,
+    signer::Signer,
+};
+
+async fn create_referrer(
+    context: &mut ProgramTestContext,
+    wallet: &Keypair,
+) -> std::result::Result<Pubkey, BanksClientError> {
+    let (referrer, _) = p2swap::utils::find_referrer_address(&wallet.pubkey(), &System::id());
+
+    let accounts = p2swap::accounts::CreateReferrer {
+        referrer,
+        wallet: wallet.pubkey(),
+        token_account: wallet.pubkey(),
+        mint: System::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateReferrer {}.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, wallet, &[wallet]).await?;
+
+    Ok(referrer)
+}
+
+async fn get_referrer(
+    context: &mut ProgramTestContext,
+    referrer: &Pubkey,
+) -> p2swap::state::Referrer {
+    let referrer_account = context
+        .banks_client
+        .get_account(*referrer)
+        .await
+        .unwrap()
+        .unwrap();
+
+    try_from_slice_unchecked::<p2swap::state::Referrer>(&referrer_account.data[8..]).unwrap()
+}
+
+#[tokio::test]
+async fn success_native() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+    let maker_referrer_wallet = Keypair::new();
+    let taker_referrer_wallet = Keypair::new();
+
+    let base_amount = 10 * 10u64.pow(9);
+    let quote_amount = 20 * 10u64.pow(9);
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount * 2).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), quote_amount * 2).await;
+    utils::airdrop(&mut context, &treasury.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &maker_referrer_wallet.pubkey(), 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &taker_referrer_wallet.pubkey(), 10u64.pow(9)).await;
+
+    // 1% fee, referrers can receive up to 50% of it
+    utils::init_protocol(&mut context, &treasury.pubkey(), 100, 5000).await;
+
+    let maker_referrer = create_referrer(&mut context, &maker_referrer_wallet)
+        .await
+        .unwrap();
+    let taker_referrer = create_referrer(&mut context, &taker_referrer_wallet)
+        .await
+        .unwrap();
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams {
+            referral_share_bps: 5000,
+            remaining_accounts: vec![AccountMeta::new_readonly(maker_referrer, false)],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let treasury_balance_before = utils::get_lamports(&mut context, &treasury.pubkey()).await;
+    let funder_balance_before = utils::get_lamports(&mut context, &funder.pubkey()).await;
+
+    // Taker share is capped by `max_referral_share_bps`
+    utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            taker_referral_share_bps: 8000,
+            remaining_accounts: vec![
+                AccountMeta::new(maker_referrer, false),
+                AccountMeta::new(maker_referrer_wallet.pubkey(), false),
+                AccountMeta::new(taker_referrer, false),
+                AccountMeta::new(taker_referrer_wallet.pubkey(), false),
+            ],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let base_fee = base_amount / 100;
+    let quote_fee = quote_amount / 100;
+
+    let treasury_balance_after = utils::get_lamports(&mut context, &treasury.pubkey()).await;
+    assert_eq!(
+        treasury_balance_after - treasury_balance_before,
+        base_fee / 2 + quote_fee / 2
+    );
+
+    let funder_balance_after = utils::get_lamports(&mut context, &funder.pubkey()).await;
+    assert!(funder_balance_after - funder_balance_before >= quote_amount - quote_fee);
+
+    let maker_referrer_state = get_referrer(&mut context, &maker_referrer).await;
+    assert_eq!(maker_referrer_state.total_amount, base_fee / 2);
+    assert_eq!(maker_referrer_state.payouts_count, 1);
+
+    let taker_referrer_state = get_referrer(&mut context, &taker_referrer).await;
+    assert_eq!(taker_referrer_state.total_amount, quote_fee / 2);
+    assert_eq!(taker_referrer_state.payouts_count, 1);
+
+    let maker_referrer_balance =
+        utils::get_lamports(&mut context, &maker_referrer_wallet.pubkey()).await;
+    assert!(maker_referrer_balance > 10u64.pow(9));
+}
+
+#[tokio::test]
+async fn fail_invalid_referral_share() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let referrer_wallet = Keypair::new();
+
+    utils::airdrop(&mut context, &funder.pubkey(), 10 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &referrer_wallet.pubkey(), 10u64.pow(9)).await;
+
+    let referrer = create_referrer(&mut context, &referrer_wallet)
+        .await
+        .unwrap();
+
+    let error = utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        10u64.pow(9),
+        10u64.pow(9),
+        utils::OrderParams {
+            referral_share_bps: 10001,
+            remaining_accounts: vec![AccountMeta::new_readonly(referrer, false)],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6048 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn fail_referral_share_above_protocol_max() {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+    let referrer_wallet = Keypair::new();
+
+    utils::airdrop(&mut context, &funder.pubkey(), 10 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &referrer_wallet.pubkey(), 10u64.pow(9)).await;
+
+    // Referrers can receive up to 50% of protocol fee
+    utils::init_protocol(&mut context, &treasury.pubkey(), 100, 5000).await;
+
+    let referrer = create_referrer(&mut context, &referrer_wallet)
+        .await
+        .unwrap();
+
+    let error = utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        10u64.pow(9),
+        10u64.pow(9),
+        utils::OrderParams {
+            referral_share_bps: 5001,
+            remaining_accounts: vec![AccountMeta::new_readonly(referrer, false)],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(utils::get_error_code(error), Some(6048));
+}
@@ -0,0 +1,486 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{
+    borsh::try_from_slice_unchecked, instruction::AccountMeta, instruction::Instruction,
+    signature::Keypair, signer::Signer, sysvar,
+};
+
+fn native_legs(participants: &[&Keypair], amount: u64) -> Vec<p2swap::state::RingSwapLegArgs> {
+    participants
+        .iter()
+        .enumerate()
+        .map(|(i, participant)| p2swap::state::RingSwapLegArgs {
+            participant: participant.pubkey(),
+            mint: System::id(),
+            amount,
+            receive_token_account: participants[(i + 1) % participants.len()].pubkey(),
+        })
+        .collect()
+}
+
+async fn create_ring_swap(
+    context: &mut ProgramTestContext,
+    ring_swap: &Keypair,
+    creator: &Keypair,
+    legs: Vec<p2swap::state::RingSwapLegArgs>,
+    expire_date: i64,
+) -> std::result::Result<(), BanksClientError> {
+    let accounts = p2swap::accounts::CreateRingSwap {
+        ring_swap: ring_swap.pubkey(),
+        creator: creator.pubkey(),
+        clock_sysvar: sysvar::clock::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::CreateRingSwap { legs, expire_date }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, creator, &[creator, ring_swap]).await
+}
+
+async fn deposit_ring_swap_leg(
+    context: &mut ProgramTestContext,
+    ring_swap: &Pubkey,
+    participant: &Keypair,
+    participant_token_account: &Pubkey,
+    mint: &Pubkey,
+    leg_index: u8,
+) -> std::result::Result<(), BanksClientError> {
+    let (deposit, deposit_bump) =
+        p2swap::utils::find_order_escrow_address(&participant.pubkey(), ring_swap);
+
+    let accounts = p2swap::accounts::DepositRingSwapLeg {
+        ring_swap: *ring_swap,
+        participant: participant.pubkey(),
+        participant_token_account: *participant_token_account,
+        deposit,
+        mint: *mint,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::DepositRingSwapLeg {
+        deposit_bump,
+        leg_index,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    utils::process_instruction(context, instruction, participant, &[participant]).await
+}
+
+async fn settle_ring_swap(
+    context: &mut ProgramTestContext,
+    ring_swap: &Pubkey,
+    legs: &[p2swap::state::RingSwapLegArgs],
+) -> std::result::Result<(), BanksClientError> {
+    let mut accounts = p2swap::accounts::SettleRingSwap {
+        ring_swap: *ring_swap,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    for leg in legs {
+        let (deposit, _) = p2swap::utils::find_order_escrow_address(&leg.participant, ring_swap);
+
+        accounts.push(AccountMeta::new(deposit, false));
+        accounts.push(AccountMeta::new(leg.participant, false));
+        accounts.push(AccountMeta::new(leg.receive_token_account, false));
+    }
+
+    let data = p2swap::instruction::SettleRingSwap {}.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    utils::process_instruction(context, instruction, &payer, &[&payer]).await
+}
+
+async fn refund_ring_swap_leg(
+    context: &mut ProgramTestContext,
+    ring_swap: &Pubkey,
+    participant: &Pubkey,
+    leg_index: u8,
+) -> std::result::Result<(), BanksClientError> {
+    let (deposit, _) = p2swap::utils::find_order_escrow_address(participant, ring_swap);
+
+    let accounts = p2swap::accounts::RefundRingSwapLeg {
+        ring_swap: *ring_swap,
+        participant: *participant,
+        refund_token_account: *participant,
+        deposit,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::RefundRingSwapLeg { leg_index }.data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    utils::process_instruction(context, instruction, &payer, &[&payer]).await
+}
+
+#[tokio::test]
+async fn success_native() {
+    let mut context = utils::setup_test_context().await;
+
+    let ring_swap = Keypair::new();
+    let participant_a = Keypair::new();
+    let participant_b = Keypair::new();
+    let participant_c = Keypair::new();
+    let participants = [&participant_a, &participant_b, &participant_c];
+
+    let amount = 10u64.pow(9);
+    let balance = 10 * 10u64.pow(9);
+    let expire_date = 9999999999;
+
+    for participant in participants.iter() {
+        utils::airdrop(&mut context, &participant.pubkey(), balance).await;
+    }
+
+    let legs = native_legs(&participants, amount);
+
+    create_ring_swap(
+        &mut context,
+        &ring_swap,
+        &participant_a,
+        legs.clone(),
+        expire_date,
+    )
+    .await
+    .unwrap();
+
+    for (i, participant) in participants.iter().enumerate() {
+        deposit_ring_swap_leg(
+            &mut context,
+            &ring_swap.pubkey(),
+            participant,
+            &participant.pubkey(),
+            &System::id(),
+            i as u8,
+        )
+        .await
+        .unwrap();
+    }
+
+    settle_ring_swap(&mut context, &ring_swap.pubkey(), &legs)
+        .await
+        .unwrap();
+
+    for participant in participants.iter() {
+        let (deposit, _) =
+            p2swap::utils::find_order_escrow_address(&participant.pubkey(), &ring_swap.pubkey());
+        let deposit_account = context.banks_client.get_account(deposit).await.unwrap();
+        assert!(deposit_account.is_none());
+    }
+
+    // Everybody gave and received the same amount, so only fees (and creator rent) are lost
+    let participant_b_balance = context
+        .banks_client
+        .get_account(participant_b.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(participant_b_balance > balance - 10u64.pow(6));
+
+    let ring_swap_account = context
+        .banks_client
+        .get_account(ring_swap.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let ring_swap_state =
+        try_from_slice_unchecked::<p2swap::state::RingSwap>(&ring_swap_account.data[8..]).unwrap();
+    assert_eq!(
+        ring_swap_state.status,
+        p2swap::state::RingSwapStatus::Completed
+    );
+    assert_eq!(ring_swap_state.legs.len(), 3);
+    assert!(ring_swap_state.is_funded());
+}
+
+#[tokio::test]
+async fn fail_receive_account_wallet_mismatch() {
+    let mut context = utils::setup_test_context().await;
+
+    let ring_swap = Keypair::new();
+    let participant_a = Keypair::new();
+    let participant_b = Keypair::new();
+    let participant_c = Keypair::new();
+    let participants = [&participant_a, &participant_b, &participant_c];
+
+    let amount = 10u64.pow(9);
+
+    for participant in participants.iter() {
+        utils::airdrop(&mut context, &participant.pubkey(), 10 * 10u64.pow(9)).await;
+    }
+
+    let mint = Keypair::new();
+    let participant_a_token_account = Keypair::new();
+    let participant_c_token_account = Keypair::new();
+
+    utils::create_mint(&mut context, &mint, &participant_a.pubkey(), 9).await;
+    for (account, owner) in [
+        (&participant_a_token_account, &participant_a),
+        (&participant_c_token_account, &participant_c),
+    ] {
+        utils::create_token_account(&mut context, account, &mint.pubkey(), &owner.pubkey()).await;
+    }
+    utils::mint_to(
+        &mut context,
+        &mint.pubkey(),
+        &participant_a_token_account.pubkey(),
+        &participant_a,
+        amount,
+    )
+    .await;
+
+    // `participant_a` tokens should go to `participant_b`, but `participant_c` account is set
+    let mut legs = native_legs(&participants, amount);
+    legs[0].mint = mint.pubkey();
+    legs[0].receive_token_account = participant_c_token_account.pubkey();
+
+    create_ring_swap(
+        &mut context,
+        &ring_swap,
+        &participant_a,
+        legs.clone(),
+        9999999999,
+    )
+    .await
+    .unwrap();
+
+    deposit_ring_swap_leg(
+        &mut context,
+        &ring_swap.pubkey(),
+        &participant_a,
+        &participant_a_token_account.pubkey(),
+        &mint.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    for (i, participant) in participants.iter().enumerate().skip(1) {
+        deposit_ring_swap_leg(
+            &mut context,
+            &ring_swap.pubkey(),
+            participant,
+            &participant.pubkey(),
+            &System::id(),
+            i as u8,
+        )
+        .await
+        .unwrap();
+    }
+
+    let error = settle_ring_swap(&mut context, &ring_swap.pubkey(), &legs)
+        .await
+        .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6003 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn success_refund_after_expire() {
+    let mut context = utils::setup_test_context().await;
+
+    let ring_swap = Keypair::new();
+    let participant_a = Keypair::new();
+    let participant_b = Keypair::new();
+    let participant_c = Keypair::new();
+    let participants = [&participant_a, &participant_b, &participant_c];
+
+    let amount = 10u64.pow(9);
+    let balance = 10 * 10u64.pow(9);
+    let expire_date = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+        + 20;
+
+    for participant in participants.iter() {
+        utils::airdrop(&mut context, &participant.pubkey(), balance).await;
+    }
+
+    let legs = native_legs(&participants, amount);
+
+    create_ring_swap(&mut context, &ring_swap, &participant_a, legs, expire_date)
+        .await
+        .unwrap();
+
+    deposit_ring_swap_leg(
+        &mut context,
+        &ring_swap.pubkey(),
+        &participant_b,
+        &participant_b.pubkey(),
+        &System::id(),
+        1,
+    )
+    .await
+    .unwrap();
+
+    utils::wait_until(&mut context, expire_date).await;
+
+    refund_ring_swap_leg(
+        &mut context,
+        &ring_swap.pubkey(),
+        &participant_b.pubkey(),
+        1,
+    )
+    .await
+    .unwrap();
+
+    let participant_b_balance = context
+        .banks_client
+        .get_account(participant_b.pubkey())
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    assert!(participant_b_balance > balance - 10u64.pow(6));
+
+    let ring_swap_account = context
+        .banks_client
+        .get_account(ring_swap.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let ring_swap_state =
+        try_from_slice_unchecked::<p2swap::state::RingSwap>(&ring_swap_account.data[8..]).unwrap();
+    assert!(!ring_swap_state.legs[1].is_funded);
+}
+
+#[tokio::test]
+async fn fail_ring_swap_is_not_funded() {
+    let mut context = utils::setup_test_context().await;
+
+    let ring_swap = Keypair::new();
+    let participant_a = Keypair::new();
+    let participant_b = Keypair::new();
+    let participant_c = Keypair::new();
+    let participants = [&participant_a, &participant_b, &participant_c];
+
+    let amount = 10u64.pow(9);
+    let expire_date = 9999999999;
+
+    for participant in participants.iter() {
+        utils::airdrop(&mut context, &participant.pubkey(), amount * 2).await;
+    }
+
+    let legs = native_legs(&participants, amount);
+
+    create_ring_swap(
+        &mut context,
+        &ring_swap,
+        &participant_a,
+        legs.clone(),
+        expire_date,
+    )
+    .await
+    .unwrap();
+
+    deposit_ring_swap_leg(
+        &mut context,
+        &ring_swap.pubkey(),
+        &participant_a,
+        &participant_a.pubkey(),
+        &System::id(),
+        0,
+    )
+    .await
+    .unwrap();
+    deposit_ring_swap_leg(
+        &mut context,
+        &ring_swap.pubkey(),
+        &participant_b,
+        &participant_b.pubkey(),
+        &System::id(),
+        1,
+    )
+    .await
+    .unwrap();
+
+    let error = settle_ring_swap(&mut context, &ring_swap.pubkey(), &legs)
+        .await
+        .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6040 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn fail_invalid_ring_swap_legs() {
+    let mut context = utils::setup_test_context().await;
+
+    let ring_swap = Keypair::new();
+    let participant_a = Keypair::new();
+    let participant_b = Keypair::new();
+
+    let amount = 10u64.pow(9);
+    let expire_date = 9999999999;
+
+    utils::airdrop(&mut context, &participant_a.pubkey(), amount).await;
+
+    let legs = native_legs(&[&participant_a, &participant_b], amount);
+
+    let error = create_ring_swap(&mut context, &ring_swap, &participant_a, legs, expire_date)
+        .await
+        .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6035 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
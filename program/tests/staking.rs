@@ -0,0 +1,597 @@
+mod utils;
+
+use anchor_lang::{prelude::*, Id, InstructionData, System, ToAccountMetas};
+use solana_program_test::*;
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer, sysvar};
+
+async fn get_stake(context: &mut ProgramTestContext, wallet: &Pubkey) -> p2swap::state::Stake {
+    let (stake, _) = p2swap::utils::find_stake_address(wallet);
+
+    let stake_account = context
+        .banks_client
+        .get_account(stake)
+        .await
+        .unwrap()
+        .unwrap();
+
+    p2swap::state::Stake::try_deserialize(&mut stake_account.data.as_slice()).unwrap()
+}
+
+/// Create `Protocol` with p2swap token as discount mint and `StakingPool`.
+/// Return p2swap token mint authority.
+async fn init_staking_pool(
+    context: &mut ProgramTestContext,
+    stake_mint: &Keypair,
+    treasury: &Pubkey,
+    fee_bps: u16,
+    cooldown: i64,
+) -> Keypair {
+    let protocol = utils::init_protocol(context, treasury, fee_bps, 0).await;
+
+    let mint_authority = Keypair::new();
+    utils::create_mint(context, stake_mint, &mint_authority.pubkey(), 0).await;
+
+    // SetFeeTiers
+    let accounts = p2swap::accounts::SetFeeTiers {
+        protocol,
+        admin: context.payer.pubkey(),
+        discount_mint: stake_mint.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::SetFeeTiers {
+            fee_tiers: Vec::new(),
+        }
+        .data(),
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[])
+        .await
+        .unwrap();
+
+    // InitStakingPool
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let (stake_vault, stake_vault_bump) = p2swap::utils::find_stake_vault_address();
+
+    let accounts = p2swap::accounts::InitStakingPool {
+        staking_pool,
+        protocol,
+        admin: context.payer.pubkey(),
+        stake_mint: stake_mint.pubkey(),
+        stake_vault,
+        rent_sysvar: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::InitStakingPool {
+            stake_vault_bump,
+            cooldown,
+        }
+        .data(),
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[])
+        .await
+        .unwrap();
+
+    mint_authority
+}
+
+/// Create wallet with `amount` of `mint` tokens, return wallet token account.
+async fn create_token_holder(
+    context: &mut ProgramTestContext,
+    wallet: &Keypair,
+    mint: &Pubkey,
+    mint_authority: &Keypair,
+    amount: u64,
+) -> Pubkey {
+    let token_account = Keypair::new();
+
+    utils::airdrop(context, &wallet.pubkey(), 10u64.pow(8)).await;
+    utils::create_token_account(context, &token_account, mint, &wallet.pubkey()).await;
+    utils::mint_to(
+        context,
+        mint,
+        &token_account.pubkey(),
+        mint_authority,
+        amount,
+    )
+    .await;
+
+    token_account.pubkey()
+}
+
+async fn stake_tokens(
+    context: &mut ProgramTestContext,
+    wallet: &Keypair,
+    wallet_token_account: &Pubkey,
+    amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let (stake, _) = p2swap::utils::find_stake_address(&wallet.pubkey());
+    let (stake_vault, stake_vault_bump) = p2swap::utils::find_stake_vault_address();
+
+    let accounts = p2swap::accounts::StakeTokens {
+        staking_pool,
+        stake,
+        wallet: wallet.pubkey(),
+        wallet_token_account: *wallet_token_account,
+        stake_vault,
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::StakeTokens {
+            stake_vault_bump,
+            amount,
+        }
+        .data(),
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[wallet]).await
+}
+
+async fn unstake(
+    context: &mut ProgramTestContext,
+    wallet: &Keypair,
+    wallet_token_account: &Pubkey,
+    amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let (stake, _) = p2swap::utils::find_stake_address(&wallet.pubkey());
+    let (stake_vault, stake_vault_bump) = p2swap::utils::find_stake_vault_address();
+
+    let accounts = p2swap::accounts::Unstake {
+        staking_pool,
+        stake,
+        wallet: wallet.pubkey(),
+        wallet_token_account: *wallet_token_account,
+        stake_vault,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::Unstake {
+            stake_vault_bump,
+            amount,
+        }
+        .data(),
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[wallet]).await
+}
+
+async fn withdraw_stake(
+    context: &mut ProgramTestContext,
+    wallet: &Keypair,
+    wallet_token_account: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let (stake, _) = p2swap::utils::find_stake_address(&wallet.pubkey());
+    let (stake_vault, stake_vault_bump) = p2swap::utils::find_stake_vault_address();
+
+    let accounts = p2swap::accounts::WithdrawStake {
+        stake,
+        wallet: wallet.pubkey(),
+        wallet_token_account: *wallet_token_account,
+        stake_vault,
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::WithdrawStake { stake_vault_bump }.data(),
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[wallet]).await
+}
+
+async fn deposit_rewards(
+    context: &mut ProgramTestContext,
+    depositor: &Keypair,
+    depositor_token_account: &Pubkey,
+    reward_mint: &Pubkey,
+    amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let (reward_vault, reward_vault_bump) = p2swap::utils::find_reward_vault_address(reward_mint);
+
+    let accounts = p2swap::accounts::DepositRewards {
+        staking_pool,
+        depositor: depositor.pubkey(),
+        depositor_token_account: *depositor_token_account,
+        reward_mint: *reward_mint,
+        reward_vault,
+        rent_sysvar: sysvar::rent::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::DepositRewards {
+            reward_vault_bump,
+            amount,
+        }
+        .data(),
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[depositor]).await
+}
+
+async fn claim_rewards(
+    context: &mut ProgramTestContext,
+    wallet: &Keypair,
+    wallet_token_account: &Pubkey,
+    reward_mint: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let (staking_pool, _) = p2swap::utils::find_staking_pool_address();
+    let (stake, _) = p2swap::utils::find_stake_address(&wallet.pubkey());
+    let (reward_vault, reward_vault_bump) = p2swap::utils::find_reward_vault_address(reward_mint);
+
+    let accounts = p2swap::accounts::ClaimRewards {
+        staking_pool,
+        stake,
+        wallet: wallet.pubkey(),
+        wallet_token_account: *wallet_token_account,
+        reward_mint: *reward_mint,
+        reward_vault,
+        token_program: spl_token::id(),
+    }
+    .to_account_metas(None);
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::ClaimRewards { reward_vault_bump }.data(),
+        accounts,
+    };
+
+    utils::process_payer_instruction(context, instruction, &[wallet]).await
+}
+
+/// Claim native `SOL` rewards and return claimed amount.
+async fn claim_native_rewards(context: &mut ProgramTestContext, wallet: &Keypair) -> u64 {
+    let lamports_before = utils::get_lamports(context, &wallet.pubkey()).await;
+
+    claim_rewards(context, wallet, &wallet.pubkey(), &System::id())
+        .await
+        .unwrap();
+
+    utils::get_lamports(context, &wallet.pubkey()).await - lamports_before
+}
+
+#[tokio::test]
+async fn success_multiple_stakers() {
+    let mut context = utils::setup_test_context().await;
+
+    let stake_mint = Keypair::new();
+    let mint_authority =
+        init_staking_pool(&mut context, &stake_mint, &Pubkey::new_unique(), 0, 0).await;
+
+    let staker_a = Keypair::new();
+    let staker_b = Keypair::new();
+    let depositor = Keypair::new();
+
+    let staker_a_token_account = create_token_holder(
+        &mut context,
+        &staker_a,
+        &stake_mint.pubkey(),
+        &mint_authority,
+        100,
+    )
+    .await;
+    let staker_b_token_account = create_token_holder(
+        &mut context,
+        &staker_b,
+        &stake_mint.pubkey(),
+        &mint_authority,
+        300,
+    )
+    .await;
+
+    // Fees collected in `spl_token`'s
+    let reward_mint = Keypair::new();
+    let reward_mint_authority = Keypair::new();
+    utils::create_mint(
+        &mut context,
+        &reward_mint,
+        &reward_mint_authority.pubkey(),
+        0,
+    )
+    .await;
+
+    let depositor_token_account = create_token_holder(
+        &mut context,
+        &depositor,
+        &reward_mint.pubkey(),
+        &reward_mint_authority,
+        400,
+    )
+    .await;
+
+    let staker_b_reward_token_account = Keypair::new();
+    utils::create_token_account(
+        &mut context,
+        &staker_b_reward_token_account,
+        &reward_mint.pubkey(),
+        &staker_b.pubkey(),
+    )
+    .await;
+
+    // Staker A joins first and receives all rewards of first deposit
+    stake_tokens(&mut context, &staker_a, &staker_a_token_account, 100)
+        .await
+        .unwrap();
+
+    deposit_rewards(
+        &mut context,
+        &depositor,
+        &depositor.pubkey(),
+        &System::id(),
+        1000,
+    )
+    .await
+    .unwrap();
+
+    // Staker B joins with 3x stake and shares next deposits as 1:3
+    stake_tokens(&mut context, &staker_b, &staker_b_token_account, 300)
+        .await
+        .unwrap();
+
+    deposit_rewards(
+        &mut context,
+        &depositor,
+        &depositor.pubkey(),
+        &System::id(),
+        4000,
+    )
+    .await
+    .unwrap();
+
+    deposit_rewards(
+        &mut context,
+        &depositor,
+        &depositor_token_account,
+        &reward_mint.pubkey(),
+        400,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(claim_native_rewards(&mut context, &staker_a).await, 2000);
+    assert_eq!(claim_native_rewards(&mut context, &staker_b).await, 3000);
+
+    claim_rewards(
+        &mut context,
+        &staker_b,
+        &staker_b_reward_token_account.pubkey(),
+        &reward_mint.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &staker_b_reward_token_account.pubkey()).await,
+        300
+    );
+
+    // Staker A leaves, so next deposit goes to staker B only
+    unstake(&mut context, &staker_a, &staker_a_token_account, 100)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &staker_a_token_account).await,
+        100
+    );
+
+    deposit_rewards(
+        &mut context,
+        &depositor,
+        &depositor.pubkey(),
+        &System::id(),
+        600,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(claim_native_rewards(&mut context, &staker_a).await, 0);
+    assert_eq!(claim_native_rewards(&mut context, &staker_b).await, 600);
+
+    // Staker A didn't claim `spl_token` rewards yet
+    let stake_a = get_stake(&mut context, &staker_a.pubkey()).await;
+    assert_eq!(stake_a.amount, 0);
+    assert_eq!(stake_a.rewards[1].pending, 100);
+}
+
+#[tokio::test]
+async fn success_unstake_with_cooldown() {
+    let mut context = utils::setup_test_context().await;
+
+    let cooldown = 3600;
+
+    let stake_mint = Keypair::new();
+    let mint_authority = init_staking_pool(
+        &mut context,
+        &stake_mint,
+        &Pubkey::new_unique(),
+        0,
+        cooldown,
+    )
+    .await;
+
+    let staker = Keypair::new();
+    let staker_token_account = create_token_holder(
+        &mut context,
+        &staker,
+        &stake_mint.pubkey(),
+        &mint_authority,
+        100,
+    )
+    .await;
+
+    stake_tokens(&mut context, &staker, &staker_token_account, 100)
+        .await
+        .unwrap();
+
+    unstake(&mut context, &staker, &staker_token_account, 100)
+        .await
+        .unwrap();
+
+    // Tokens are locked during cooldown
+    assert_eq!(
+        utils::get_token_balance(&mut context, &staker_token_account).await,
+        0
+    );
+
+    let stake = get_stake(&mut context, &staker.pubkey()).await;
+    assert_eq!(stake.amount, 0);
+    assert_eq!(stake.unstaking_amount, 100);
+
+    utils::wait_until(&mut context, stake.unstake_available_at).await;
+
+    withdraw_stake(&mut context, &staker, &staker_token_account)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        utils::get_token_balance(&mut context, &staker_token_account).await,
+        100
+    );
+}
+
+#[tokio::test]
+async fn fail_withdraw_in_cooldown() {
+    let mut context = utils::setup_test_context().await;
+
+    let stake_mint = Keypair::new();
+    let mint_authority =
+        init_staking_pool(&mut context, &stake_mint, &Pubkey::new_unique(), 0, 3600).await;
+
+    let staker = Keypair::new();
+    let staker_token_account = create_token_holder(
+        &mut context,
+        &staker,
+        &stake_mint.pubkey(),
+        &mint_authority,
+        100,
+    )
+    .await;
+
+    stake_tokens(&mut context, &staker, &staker_token_account, 100)
+        .await
+        .unwrap();
+
+    unstake(&mut context, &staker, &staker_token_account, 100)
+        .await
+        .unwrap();
+
+    let error = withdraw_stake(&mut context, &staker, &staker_token_account)
+        .await
+        .unwrap_err();
+
+    let error_code = utils::get_error_code(error);
+    if let Some(error_code) = error_code {
+        if error_code == 6072 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn success_treasury_fees_to_stakers() {
+    let mut context = utils::setup_test_context().await;
+
+    let stake_mint = Keypair::new();
+    let treasury = Keypair::new();
+
+    // 1% fee
+    let mint_authority =
+        init_staking_pool(&mut context, &stake_mint, &treasury.pubkey(), 100, 0).await;
+
+    let staker = Keypair::new();
+    let staker_token_account = create_token_holder(
+        &mut context,
+        &staker,
+        &stake_mint.pubkey(),
+        &mint_authority,
+        100,
+    )
+    .await;
+
+    stake_tokens(&mut context, &staker, &staker_token_account, 100)
+        .await
+        .unwrap();
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    utils::airdrop(&mut context, &funder.pubkey(), 11 * 10u64.pow(9)).await;
+    utils::airdrop(&mut context, &recipient.pubkey(), 11 * 10u64.pow(9)).await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        10 * 10u64.pow(9),
+        10 * 10u64.pow(9),
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Fees of both sides are collected by treasury
+    let fees = utils::get_lamports(&mut context, &treasury.pubkey()).await;
+    assert_eq!(fees, 2 * 10u64.pow(8));
+
+    // Treasury deposits stakers share of fees to `StakingPool`
+    deposit_rewards(
+        &mut context,
+        &treasury,
+        &treasury.pubkey(),
+        &System::id(),
+        fees / 2,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(claim_native_rewards(&mut context, &staker).await, fees / 2);
+}
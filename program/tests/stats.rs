@@ -0,0 +1,167 @@
+mod utils;
+
+use anchor_lang::{prelude::*, System};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, sysvar};
+
+/// Create and execute `Order`, that sells native `SOL`'s for native `SOL`'s.
+async fn trade(
+    context: &mut ProgramTestContext,
+    treasury: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+) {
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+
+    utils::airdrop(context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(context, &recipient.pubkey(), quote_amount + 10u64.pow(8)).await;
+
+    utils::create_order(
+        context,
+        &order,
+        &funder,
+        &recipient.pubkey(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams::default(),
+    )
+    .await
+    .unwrap();
+
+    utils::execute_order(
+        context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(*treasury),
+            treasury_quote_token_account: Some(*treasury),
+            ..utils::ExecuteParams::default()
+        },
+    )
+    .await
+    .unwrap();
+}
+
+async fn get_pair_stats(context: &mut ProgramTestContext) -> p2swap::state::PairStats {
+    let (order_book, _) = p2swap::utils::find_order_book_address(&System::id(), &System::id());
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+
+    let pair_stats_account = context
+        .banks_client
+        .get_account(pair_stats)
+        .await
+        .unwrap()
+        .unwrap();
+
+    p2swap::state::PairStats::try_deserialize(&mut pair_stats_account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn success_execute_updates_stats() {
+    let mut context = utils::setup_test_context().await;
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    let treasury = Keypair::new();
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    trade(&mut context, &treasury.pubkey(), base_amount, quote_amount).await;
+
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let stats_account = context
+        .banks_client
+        .get_account(stats)
+        .await
+        .unwrap()
+        .unwrap();
+    let stats = p2swap::state::Stats::try_deserialize(&mut stats_account.data.as_slice()).unwrap();
+    assert_eq!(stats.trades_count, 1);
+    assert_eq!(stats.pairs_count, 1);
+    assert_eq!(stats.native_volume, base_amount as u128);
+
+    let pair_stats = get_pair_stats(&mut context).await;
+    assert_eq!(pair_stats.base_mint, System::id());
+    assert_eq!(pair_stats.quote_mint, System::id());
+    assert_eq!(pair_stats.trades_count, 1);
+    assert_eq!(pair_stats.base_volume, base_amount as u128);
+    assert_eq!(pair_stats.quote_volume, quote_amount as u128);
+    assert_eq!(pair_stats.last_base_amount, base_amount);
+    assert_eq!(pair_stats.last_quote_amount, quote_amount);
+}
+
+#[tokio::test]
+async fn success_twap() {
+    let mut context = utils::setup_test_context().await;
+
+    let base_amount = 10u64.pow(9);
+
+    let treasury = Keypair::new();
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    // Price is 2, then 4
+    trade(
+        &mut context,
+        &treasury.pubkey(),
+        base_amount,
+        2 * base_amount,
+    )
+    .await;
+
+    let clock = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap();
+    utils::wait_until(&mut context, clock.unix_timestamp + 100).await;
+
+    trade(
+        &mut context,
+        &treasury.pubkey(),
+        base_amount,
+        4 * base_amount,
+    )
+    .await;
+
+    let clock = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap();
+    utils::wait_until(&mut context, clock.unix_timestamp + 100).await;
+
+    let clock = context
+        .banks_client
+        .get_sysvar::<sysvar::clock::Clock>()
+        .await
+        .unwrap();
+    let now = clock.unix_timestamp;
+
+    let pair_stats = get_pair_stats(&mut context).await;
+    let observations: Vec<_> = pair_stats.observations().cloned().collect();
+    assert_eq!(observations.len(), 2);
+    assert_eq!(observations[0].price, 2 * p2swap::state::PRICE_SCALE);
+    assert_eq!(observations[1].price, 4 * p2swap::state::PRICE_SCALE);
+
+    let first_duration = (observations[1].timestamp - observations[0].timestamp) as u128;
+    let second_duration = (now - observations[1].timestamp) as u128;
+    assert!(first_duration > 0 && second_duration > 0);
+
+    // Time before first trade is not counted
+    let expected = (observations[0].price * first_duration
+        + observations[1].price * second_duration)
+        / (first_duration + second_duration);
+    assert_eq!(pair_stats.twap(now, now), Some(expected));
+
+    // Window after last trade holds only last price
+    assert_eq!(
+        pair_stats.twap(now, second_duration as i64),
+        Some(observations[1].price)
+    );
+
+    // There are no trades before window end
+    assert_eq!(pair_stats.twap(observations[0].timestamp - 1, now), None);
+}
@@ -0,0 +1,213 @@
+mod utils;
+
+use anchor_lang::{prelude::*, System};
+use solana_program_test::*;
+use solana_sdk::{instruction::AccountMeta, signature::Keypair, signer::Signer};
+
+/// Create open `Order` gated by minimum balance of gate mint and execute it
+/// by wallet, that holds `gate_balance` of gate mint tokens.
+async fn create_and_execute_min_balance(
+    gate_balance: u64,
+    min_amount: u64,
+) -> (
+    ProgramTestContext,
+    Keypair,
+    Keypair,
+    std::result::Result<(), BanksClientError>,
+) {
+    let mut context = utils::setup_test_context().await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+    let gate_mint = Keypair::new();
+    let mint_authority = Keypair::new();
+    let gate_token_account = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    utils::create_mint(&mut context, &gate_mint, &mint_authority.pubkey(), 0).await;
+    utils::create_token_account(
+        &mut context,
+        &gate_token_account,
+        &gate_mint.pubkey(),
+        &recipient.pubkey(),
+    )
+    .await;
+    utils::mint_to(
+        &mut context,
+        &gate_mint.pubkey(),
+        &gate_token_account.pubkey(),
+        &mint_authority,
+        gate_balance,
+    )
+    .await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &System::id(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams {
+            gate: Some(p2swap::state::Gate::MinBalance {
+                mint: gate_mint.pubkey(),
+                min_amount,
+            }),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let result = utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            remaining_accounts: vec![AccountMeta::new_readonly(
+                gate_token_account.pubkey(),
+                false,
+            )],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    (context, order, recipient, result)
+}
+
+#[tokio::test]
+async fn success_min_balance_open_order() {
+    let (mut context, order, recipient, result) = create_and_execute_min_balance(100, 50).await;
+
+    result.unwrap();
+
+    // Any wallet, that passes gate, becomes `recipient` of open `Order`
+    let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+    assert_eq!(
+        order_state.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
+    assert_eq!(order_state.recipient, recipient.pubkey());
+}
+
+#[tokio::test]
+async fn fail_gate_is_not_passed() {
+    let (_, _, _, result) = create_and_execute_min_balance(10, 50).await;
+
+    let error_code = utils::get_error_code(result.unwrap_err());
+    if let Some(error_code) = error_code {
+        if error_code == 6082 {
+            assert_eq!(true, true);
+            return;
+        }
+    }
+    assert_eq!(true, false);
+}
+
+#[tokio::test]
+async fn success_collection_open_order() {
+    let nft_mint = Keypair::new();
+    let collection = Pubkey::new_unique();
+    let (metadata, _) = p2swap::utils::find_metadata_address(&nft_mint.pubkey());
+
+    let mut context = utils::setup_test_context_with_accounts(vec![(
+        metadata,
+        utils::metadata_account(&nft_mint.pubkey(), &collection, true),
+    )])
+    .await;
+
+    let order = Keypair::new();
+    let funder = Keypair::new();
+    let recipient = Keypair::new();
+    let treasury = Keypair::new();
+    let mint_authority = Keypair::new();
+    let nft_token_account = Keypair::new();
+
+    let base_amount = 10u64.pow(9);
+    let quote_amount = 2 * 10u64.pow(9);
+
+    utils::init_protocol(&mut context, &treasury.pubkey(), 0, 0).await;
+
+    utils::airdrop(&mut context, &funder.pubkey(), base_amount + 10u64.pow(8)).await;
+    utils::airdrop(
+        &mut context,
+        &recipient.pubkey(),
+        quote_amount + 10u64.pow(8),
+    )
+    .await;
+
+    utils::create_mint(&mut context, &nft_mint, &mint_authority.pubkey(), 0).await;
+    utils::create_token_account(
+        &mut context,
+        &nft_token_account,
+        &nft_mint.pubkey(),
+        &recipient.pubkey(),
+    )
+    .await;
+    utils::mint_to(
+        &mut context,
+        &nft_mint.pubkey(),
+        &nft_token_account.pubkey(),
+        &mint_authority,
+        1,
+    )
+    .await;
+
+    utils::create_order(
+        &mut context,
+        &order,
+        &funder,
+        &System::id(),
+        base_amount,
+        quote_amount,
+        utils::OrderParams {
+            gate: Some(p2swap::state::Gate::Collection { collection }),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    utils::execute_order(
+        &mut context,
+        &order.pubkey(),
+        &funder.pubkey(),
+        &recipient,
+        utils::ExecuteParams {
+            treasury_base_token_account: Some(treasury.pubkey()),
+            treasury_quote_token_account: Some(treasury.pubkey()),
+            remaining_accounts: vec![
+                AccountMeta::new_readonly(nft_token_account.pubkey(), false),
+                AccountMeta::new_readonly(metadata, false),
+            ],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let order_state = utils::get_order(&mut context, &order.pubkey()).await;
+    assert_eq!(
+        order_state.status().unwrap(),
+        p2swap::state::OrderStatus::Completed
+    );
+    assert_eq!(order_state.recipient, recipient.pubkey());
+}
@@ -1,9 +1,14 @@
 #![allow(unused)]
 
+use anchor_lang::{
+    AccountDeserialize, AnchorSerialize, Id, InstructionData, System, ToAccountMetas,
+};
+use anchor_spl::token;
 use solana_program_test::*;
 use solana_sdk::{
+    account::Account as SolanaAccount,
     commitment_config::CommitmentLevel,
-    instruction::InstructionError,
+    instruction::{AccountMeta, Instruction, InstructionError},
     program_pack::Pack,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
@@ -15,10 +20,7 @@ use std::time;
 pub fn get_error_code(error: BanksClientError) -> Option<u32> {
     if let BanksClientError::TransactionError(transaction_error) = error {
         let error_code = match transaction_error {
-            TransactionError::InstructionError(_, instruction_error) => match instruction_error {
-                InstructionError::Custom(code) => code,
-                _ => return None,
-            },
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
             _ => return None,
         };
 
@@ -36,7 +38,7 @@ pub async fn wait(context: &mut ProgramTestContext, duration: time::Duration) {
         .unwrap()
         .unix_timestamp;
 
-    let end_time = (begin_time as u128 + duration.as_millis()) as i64;
+    let end_time = (begin_time as u128 + duration.as_secs() as u128) as i64;
     loop {
         let clock = context
             .banks_client
@@ -53,12 +55,90 @@ pub async fn wait(context: &mut ProgramTestContext, duration: time::Duration) {
     }
 }
 
-pub async fn setup_test_context() -> ProgramTestContext {
+pub async fn wait_until(context: &mut ProgramTestContext, timestamp: i64) {
+    loop {
+        let clock = context
+            .banks_client
+            .get_sysvar::<sysvar::clock::Clock>()
+            .await
+            .unwrap();
+
+        if clock.unix_timestamp >= timestamp {
+            break;
+        }
+
+        let current_slot = context.banks_client.get_root_slot().await.unwrap();
+        context.warp_to_slot(current_slot + 500).unwrap();
+    }
+}
+
+/// Return `ProgramTest` with p2swap program. Native processors are used
+/// unless program is tested as BPF (`cargo test-bpf`).
+pub fn program_test() -> ProgramTest {
     let mut program_test = ProgramTest::default();
-    program_test.add_program("p2swap", p2swap::id(), None);
-    let context = program_test.start_with_context().await;
+    program_test.add_program("p2swap", p2swap::id(), processor!(p2swap::entry));
 
-    context
+    #[cfg(not(feature = "test-bpf"))]
+    program_test.add_program(
+        "spl_token",
+        spl_token::id(),
+        processor!(spl_token::processor::Processor::process),
+    );
+
+    program_test
+}
+
+pub async fn setup_test_context() -> ProgramTestContext {
+    program_test().start_with_context().await
+}
+
+/// Setup context with accounts of external programs (seeded before start).
+pub async fn setup_test_context_with_accounts(
+    accounts: Vec<(Pubkey, solana_sdk::account::Account)>,
+) -> ProgramTestContext {
+    let mut program_test = program_test();
+
+    for (address, account) in accounts {
+        program_test.add_account(address, account);
+    }
+
+    program_test.start_with_context().await
+}
+
+/// Size of Metaplex token metadata account.
+pub const METADATA_SIZE: usize = 679;
+
+/// Return Metaplex token metadata account of `mint` with (un)verified `collection`.
+pub fn metadata_account(mint: &Pubkey, collection: &Pubkey, verified: bool) -> SolanaAccount {
+    let metadata = p2swap::state::Metadata {
+        key: p2swap::state::METADATA_V1_KEY,
+        update_authority: Pubkey::new_unique(),
+        mint: *mint,
+        name: String::from("p2swap NFT"),
+        symbol: String::from("P2S"),
+        uri: String::from("https://p2swap.io/nft.json"),
+        seller_fee_basis_points: 500,
+        creators: None,
+        primary_sale_happened: true,
+        is_mutable: true,
+        edition_nonce: Some(255),
+        token_standard: None,
+        collection: Some(p2swap::state::MetadataCollection {
+            verified,
+            key: *collection,
+        }),
+    };
+
+    let mut data = metadata.try_to_vec().unwrap();
+    data.resize(METADATA_SIZE, 0);
+
+    SolanaAccount {
+        lamports: 10u64.pow(9),
+        data,
+        owner: p2swap::state::token_metadata_program::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
 }
 
 pub async fn airdrop(context: &mut ProgramTestContext, receiver: &Pubkey, amount: u64) {
@@ -127,7 +207,7 @@ pub async fn create_token_account(
             .unwrap(),
         ],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &account],
+        &[&context.payer, account],
         context.last_blockhash,
     );
 
@@ -167,3 +247,397 @@ pub async fn create_mint(
 
     context.banks_client.process_transaction(tx).await.unwrap();
 }
+
+pub async fn init_protocol(
+    context: &mut ProgramTestContext,
+    treasury: &Pubkey,
+    fee_bps: u16,
+    max_referral_share_bps: u16,
+) -> Pubkey {
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let accounts = p2swap::accounts::InitProtocol {
+        protocol,
+        admin: context.payer.pubkey(),
+        treasury: *treasury,
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+
+    let data = p2swap::instruction::InitProtocol {
+        fee_bps,
+        max_referral_share_bps,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    protocol
+}
+
+/// Sign `instruction` by `signers` and process it, fee is paid by `payer`.
+pub async fn process_instruction(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &signers.to_vec(),
+        last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Sign `instruction` by context payer and `signers` and process it,
+/// fee is paid by context payer.
+pub async fn process_payer_instruction(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend(signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+/// `CreateOrder` parameters. Default is native `SOL` order with fixed price,
+/// that is paid by `funder` and has no extensions.
+pub struct OrderParams {
+    pub escrow_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// `funder` wallet if not set.
+    pub funder_token_account: Option<Pubkey>,
+    /// `funder` wallet if not set.
+    pub quote_token_account: Option<Pubkey>,
+    /// `funder` if not set.
+    pub rent_payer: Option<Pubkey>,
+    pub expire_date: i64,
+    pub referral_share_bps: u16,
+    pub collection_offer: bool,
+    pub cancel_locked_until: Option<i64>,
+    pub hashlock: Option<[u8; 32]>,
+    pub recipient_commitment: Option<[u8; 32]>,
+    pub gate: Option<p2swap::state::Gate>,
+    pub arbitration: Option<p2swap::state::ArbitrationArgs>,
+    pub remaining_accounts: Vec<AccountMeta>,
+}
+
+impl Default for OrderParams {
+    fn default() -> Self {
+        Self {
+            escrow_mint: System::id(),
+            quote_mint: System::id(),
+            funder_token_account: None,
+            quote_token_account: None,
+            rent_payer: None,
+            expire_date: 9999999999,
+            referral_share_bps: 0,
+            collection_offer: false,
+            cancel_locked_until: None,
+            hashlock: None,
+            recipient_commitment: None,
+            gate: None,
+            arbitration: None,
+            remaining_accounts: Vec::new(),
+        }
+    }
+}
+
+/// Return `CreateOrder` instruction of `funder` to `recipient`.
+pub fn create_order_instruction(
+    order: &Pubkey,
+    funder: &Pubkey,
+    recipient: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+    params: OrderParams,
+) -> Instruction {
+    let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(funder, order);
+    let (order_book, _) =
+        p2swap::utils::find_order_book_address(&params.escrow_mint, &params.quote_mint);
+    let (funder_profile, _) = p2swap::utils::find_profile_address(funder);
+
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let mut accounts = p2swap::accounts::CreateOrder {
+        order: *order,
+        funder: *funder,
+        rent_payer: params.rent_payer.unwrap_or(*funder),
+        recipient: *recipient,
+        funder_token_account: params.funder_token_account.unwrap_or(*funder),
+        escrow,
+        quote_token_account: params.quote_token_account.unwrap_or(*funder),
+        escrow_mint: params.escrow_mint,
+        quote_mint: params.quote_mint,
+        order_book,
+        funder_profile,
+        protocol,
+        rent_sysvar: sysvar::rent::id(),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+    accounts.extend(params.remaining_accounts);
+
+    let data = p2swap::instruction::CreateOrder {
+        escrow_bump,
+        base_amount,
+        quote_amount,
+        start_date: None,
+        expire_date: params.expire_date,
+        price_curve: p2swap::state::PriceCurve::Fixed,
+        floor_quote_amount: quote_amount,
+        referral_share_bps: params.referral_share_bps,
+        collection_offer: params.collection_offer,
+        cancel_locked_until: params.cancel_locked_until,
+        hashlock: params.hashlock,
+        arbitration: params.arbitration,
+        memo: None,
+        reference: None,
+        recipient_commitment: params.recipient_commitment,
+        gate: params.gate,
+    }
+    .data();
+
+    Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    }
+}
+
+/// Create `Order` of `funder` to `recipient`, `funder` pays fee and rent.
+pub async fn create_order(
+    context: &mut ProgramTestContext,
+    order: &Keypair,
+    funder: &Keypair,
+    recipient: &Pubkey,
+    base_amount: u64,
+    quote_amount: u64,
+    params: OrderParams,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = create_order_instruction(
+        &order.pubkey(),
+        &funder.pubkey(),
+        recipient,
+        base_amount,
+        quote_amount,
+        params,
+    );
+
+    process_instruction(context, instruction, funder, &[funder, order]).await
+}
+
+/// `ExecuteOrder` parameters. Default is native `SOL` order, that is executed
+/// without taker referrer.
+pub struct ExecuteParams {
+    pub escrow_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// `recipient` wallet if not set.
+    pub recipient_token_account: Option<Pubkey>,
+    /// `recipient` wallet if not set.
+    pub recipient_receive_token_account: Option<Pubkey>,
+    /// `funder` wallet if not set.
+    pub quote_token_account: Option<Pubkey>,
+    /// `recipient` wallet if not set.
+    pub treasury_base_token_account: Option<Pubkey>,
+    /// `recipient` wallet if not set.
+    pub treasury_quote_token_account: Option<Pubkey>,
+    pub taker_referral_share_bps: u16,
+    pub preimage: Option<[u8; 32]>,
+    pub recipient_salt: Option<[u8; 32]>,
+    pub remaining_accounts: Vec<AccountMeta>,
+}
+
+impl Default for ExecuteParams {
+    fn default() -> Self {
+        Self {
+            escrow_mint: System::id(),
+            quote_mint: System::id(),
+            recipient_token_account: None,
+            recipient_receive_token_account: None,
+            quote_token_account: None,
+            treasury_base_token_account: None,
+            treasury_quote_token_account: None,
+            taker_referral_share_bps: 0,
+            preimage: None,
+            recipient_salt: None,
+            remaining_accounts: Vec::new(),
+        }
+    }
+}
+
+/// Return `ExecuteOrder` instruction of `recipient` for `funder`'s `order`.
+pub fn execute_order_instruction(
+    order: &Pubkey,
+    funder: &Pubkey,
+    recipient: &Pubkey,
+    params: ExecuteParams,
+) -> Instruction {
+    let (escrow, escrow_bump) = p2swap::utils::find_order_escrow_address(funder, order);
+    let (order_book, _) =
+        p2swap::utils::find_order_book_address(&params.escrow_mint, &params.quote_mint);
+    let (funder_profile, _) = p2swap::utils::find_profile_address(funder);
+    let (recipient_profile, _) = p2swap::utils::find_profile_address(recipient);
+    let (stats, _) = p2swap::utils::find_stats_address();
+    let (pair_stats, _) = p2swap::utils::find_pair_stats_address(&order_book);
+    let (protocol, _) = p2swap::utils::find_protocol_address();
+
+    let mut accounts = p2swap::accounts::ExecuteOrder {
+        order: *order,
+        funder: *funder,
+        rent_payer: *funder,
+        recipient: *recipient,
+        recipient_token_account: params.recipient_token_account.unwrap_or(*recipient),
+        recipient_receive_token_account: params
+            .recipient_receive_token_account
+            .unwrap_or(*recipient),
+        escrow,
+        quote_token_account: params.quote_token_account.unwrap_or(*funder),
+        order_book,
+        funder_profile,
+        recipient_profile,
+        stats,
+        pair_stats,
+        protocol,
+        treasury_base_token_account: params.treasury_base_token_account.unwrap_or(*recipient),
+        treasury_quote_token_account: params.treasury_quote_token_account.unwrap_or(*recipient),
+        clock_sysvar: sysvar::clock::id(),
+        token_program: spl_token::id(),
+        system_program: System::id(),
+    }
+    .to_account_metas(None);
+    accounts.extend(params.remaining_accounts);
+
+    let data = p2swap::instruction::ExecuteOrder {
+        escrow_bump,
+        taker_referral_share_bps: params.taker_referral_share_bps,
+        preimage: params.preimage,
+        recipient_salt: params.recipient_salt,
+    }
+    .data();
+
+    Instruction {
+        program_id: p2swap::id(),
+        data,
+        accounts,
+    }
+}
+
+/// Execute `funder`'s `order` by `recipient`, `recipient` pays fee.
+pub async fn execute_order(
+    context: &mut ProgramTestContext,
+    order: &Pubkey,
+    funder: &Pubkey,
+    recipient: &Keypair,
+    params: ExecuteParams,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = execute_order_instruction(order, funder, &recipient.pubkey(), params);
+
+    process_instruction(context, instruction, recipient, &[recipient]).await
+}
+
+pub async fn get_order(context: &mut ProgramTestContext, order: &Pubkey) -> p2swap::state::Order {
+    let order_account = context
+        .banks_client
+        .get_account(*order)
+        .await
+        .unwrap()
+        .unwrap();
+
+    p2swap::state::Order::try_deserialize(&mut order_account.data.as_slice()).unwrap()
+}
+
+/// Return `account` lamports, zero if account doesn't exist.
+pub async fn get_lamports(context: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    context
+        .banks_client
+        .get_account(*account)
+        .await
+        .unwrap()
+        .map_or(0, |account| account.lamports)
+}
+
+pub async fn get_token_balance(context: &mut ProgramTestContext, token_account: &Pubkey) -> u64 {
+    let data = context
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    token::TokenAccount::try_deserialize_unchecked(&mut data.as_ref())
+        .unwrap()
+        .amount
+}
+
+pub async fn get_order_book(
+    context: &mut ProgramTestContext,
+    order_book: &Pubkey,
+) -> p2swap::state::OrderBook {
+    let order_book_account = context
+        .banks_client
+        .get_account(*order_book)
+        .await
+        .unwrap()
+        .unwrap();
+
+    p2swap::state::OrderBook::try_deserialize(&mut order_book_account.data.as_slice()).unwrap()
+}
+
+/// Prune `order_book` by `context.payer`, `orders` are passed as remaining accounts.
+pub async fn prune_order_book(
+    context: &mut ProgramTestContext,
+    order_book: &Pubkey,
+    orders: &[Pubkey],
+) -> std::result::Result<(), BanksClientError> {
+    let mut accounts = p2swap::accounts::PruneOrderBook {
+        order_book: *order_book,
+        clock_sysvar: sysvar::clock::id(),
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        orders
+            .iter()
+            .map(|order| AccountMeta::new_readonly(*order, false)),
+    );
+
+    let instruction = Instruction {
+        program_id: p2swap::id(),
+        data: p2swap::instruction::PruneOrderBook {}.data(),
+        accounts,
+    };
+
+    process_payer_instruction(context, instruction, &[]).await
+}